@@ -2,19 +2,17 @@
 /// Runs before app files are removed, so the binary is still available.
 /// No window is shown — exits after cleanup completes.
 pub fn run() {
-    // 1. Remove our hook entries from ~/.claude/settings.json
-    if let Err(e) = crate::commands::hooks::cmd_remove_hooks() {
-        eprintln!("[cleanup] hook removal failed: {}", e);
-    }
-
-    // 2. Delete ~/.claude/theassociate/ (hook.js + hook-events.jsonl)
-    let home = std::env::var("USERPROFILE")
-        .or_else(|_| std::env::var("HOME"))
-        .unwrap_or_default();
-    if !home.is_empty() {
-        let dir = std::path::PathBuf::from(&home)
-            .join(".claude")
-            .join("theassociate");
+    // For every Claude home (~/.claude plus any registered ones):
+    // 1. Remove our hook entries from its settings.json and project-scoped settings.local.json
+    // 2. Delete its theassociate/ (hook.js + hook-events.jsonl)
+    let homes = crate::paths::all_claude_homes().unwrap_or_default();
+    for claude_home in &homes {
+        // Project hook scopes are read from the active home's theassociate/
+        crate::paths::set_active_claude_home(Some(claude_home.clone()));
+        if let Err(e) = crate::commands::hooks::remove_all_hooks(claude_home) {
+            eprintln!("[cleanup] hook removal failed for {}: {}", claude_home.display(), e);
+        }
+        let dir = claude_home.join("theassociate");
         if dir.exists() {
             if let Err(e) = std::fs::remove_dir_all(&dir) {
                 eprintln!("[cleanup] failed to remove theassociate dir: {}", e);
@@ -0,0 +1,13 @@
+use crate::paths::theassociate_home;
+use crate::data::auto_resume;
+use crate::models::auto_resume::AutoResumePolicy;
+
+#[tauri::command]
+pub async fn cmd_get_auto_resume_policy() -> Result<AutoResumePolicy, String> {
+    Ok(auto_resume::load_auto_resume_policy(&theassociate_home()?))
+}
+
+#[tauri::command]
+pub async fn cmd_save_auto_resume_policy(policy: AutoResumePolicy) -> Result<(), String> {
+    auto_resume::save_auto_resume_policy(&theassociate_home()?, &policy).map_err(|e| e.to_string())
+}
@@ -0,0 +1,266 @@
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::commands::prompt_queue::deliver_prompt;
+use crate::commands::pty::{tab_for_claude_session, PtyState};
+use crate::models::hook_event::HookEvent;
+use crate::models::terminal_profile::SessionKind;
+
+/// Broadcasts kept for status queries, newest first.
+const MAX_BROADCASTS: usize = 20;
+
+/// Where one target session stands on a broadcast prompt.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "state", rename_all = "camelCase")]
+pub enum TargetState {
+    /// Prompt delivered, no `Stop` yet
+    Running,
+    /// Claude is waiting on an answer (a question trigger fired)
+    #[serde(rename_all = "camelCase")]
+    Question { question: String },
+    /// Claude stopped after the prompt
+    #[serde(rename_all = "camelCase")]
+    Finished { last_message: Option<String> },
+    /// The PTY exited before Claude stopped
+    Exited,
+    /// The prompt could not be delivered
+    Failed { error: String },
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BroadcastTarget {
+    pub tab_id: String,
+    #[serde(flatten)]
+    pub state: TargetState,
+    /// Unix millis of the last state change
+    pub updated_at: i64,
+    /// Unix millis the prompt was typed in; `None` when it wasn't sent
+    pub delivered_at: Option<i64>,
+}
+
+/// Target counts by state; `done` once nothing is running or asking.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BroadcastSummary {
+    pub running: usize,
+    pub question: usize,
+    pub finished: usize,
+    pub exited: usize,
+    pub failed: usize,
+    pub done: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Broadcast {
+    pub id: String,
+    pub prompt: String,
+    /// Unix millis
+    pub started_at: i64,
+    pub targets: Vec<BroadcastTarget>,
+    pub summary: BroadcastSummary,
+}
+
+impl Broadcast {
+    fn refresh_summary(&mut self) {
+        let mut summary = BroadcastSummary::default();
+        for target in &self.targets {
+            match target.state {
+                TargetState::Running => summary.running += 1,
+                TargetState::Question { .. } => summary.question += 1,
+                TargetState::Finished { .. } => summary.finished += 1,
+                TargetState::Exited => summary.exited += 1,
+                TargetState::Failed { .. } => summary.failed += 1,
+            }
+        }
+        summary.done = summary.running == 0 && summary.question == 0;
+        self.summary = summary;
+    }
+}
+
+/// Recent broadcasts, newest first.
+pub struct BroadcastState(pub Arc<Mutex<Vec<Broadcast>>>);
+
+impl BroadcastState {
+    pub fn new() -> Self {
+        Self(Arc::new(Mutex::new(Vec::new())))
+    }
+}
+
+/// Move every still-pending target on `tab_id` to the state `next` returns
+/// (or leave it when `next` gives `None`), and emit each broadcast that changed.
+fn update_tab(app: &AppHandle, tab_id: &str, next: impl Fn(&BroadcastTarget) -> Option<TargetState>) {
+    let state = app.state::<BroadcastState>();
+    let Ok(mut broadcasts) = state.0.lock() else {
+        return;
+    };
+    let now = chrono::Utc::now().timestamp_millis();
+    for broadcast in broadcasts.iter_mut() {
+        let mut changed = false;
+        for target in broadcast.targets.iter_mut().filter(|t| t.tab_id == tab_id) {
+            if !matches!(target.state, TargetState::Running | TargetState::Question { .. }) {
+                continue;
+            }
+            if let Some(state) = next(target) {
+                target.state = state;
+                target.updated_at = now;
+                changed = true;
+            }
+        }
+        if changed {
+            broadcast.refresh_summary();
+            let _ = app.emit("broadcast-changed", &*broadcast);
+        }
+    }
+}
+
+/// Called by the hook watcher for every `Stop` event: the bound tab has
+/// finished its turn. A `Stop` from before the prompt was typed in ends the
+/// turn that was already running, so it's ignored.
+pub fn on_session_stop(app: &AppHandle, event: &HookEvent) {
+    let Some(tab_id) = tab_for_claude_session(&app.state::<PtyState>().0, &event.session_id) else {
+        return;
+    };
+    // Events from an older hook.js carry no timestamp; the time they're read is the best guess
+    let stopped_at = event
+        .timestamp
+        .unwrap_or_else(|| chrono::Utc::now().timestamp_millis());
+    update_tab(app, &tab_id, |target| {
+        if target.delivered_at.is_some_and(|t| stopped_at < t) {
+            return None;
+        }
+        Some(TargetState::Finished {
+            last_message: event.last_assistant_message.clone(),
+        })
+    });
+}
+
+/// Called when a question trigger fires in a tab's output.
+pub fn on_session_question(app: &AppHandle, tab_id: &str, question: &str) {
+    update_tab(app, tab_id, |target| match &target.state {
+        TargetState::Question { question: q } if q == question => None,
+        _ => Some(TargetState::Question {
+            question: question.to_string(),
+        }),
+    });
+}
+
+/// Called when a tab's PTY exits.
+pub fn on_session_exit(app: &AppHandle, tab_id: &str) {
+    update_tab(app, tab_id, |_| Some(TargetState::Exited));
+}
+
+/// Type `text` into each of `tab_ids` and track them until Claude stops.
+/// Tabs that are gone, aren't Claude sessions or have no Claude session bound
+/// yet (so their `Stop` couldn't be matched) are reported as failed.
+#[tauri::command]
+pub async fn cmd_broadcast_prompt(
+    tab_ids: Vec<String>,
+    text: String,
+    app_handle: AppHandle,
+    pty_state: State<'_, PtyState>,
+    state: State<'_, BroadcastState>,
+) -> Result<Broadcast, String> {
+    if text.trim().is_empty() {
+        return Err("Prompt is empty".to_string());
+    }
+    if tab_ids.is_empty() {
+        return Err("No target sessions selected".to_string());
+    }
+    let now = chrono::Utc::now().timestamp_millis();
+    let mut targets = {
+        let sessions = pty_state.0.lock().map_err(|e| e.to_string())?;
+        let mut targets: Vec<BroadcastTarget> = Vec::new();
+        for tab_id in tab_ids {
+            if targets.iter().any(|t| t.tab_id == tab_id) {
+                continue;
+            }
+            let state = match sessions.get(&tab_id) {
+                Some(s) if s.kind != SessionKind::Claude => TargetState::Failed {
+                    error: "Not a Claude session".to_string(),
+                },
+                Some(s) if s.claude_session_id.is_none() => TargetState::Failed {
+                    error: "Claude session has not started yet".to_string(),
+                },
+                Some(_) => TargetState::Running,
+                None => TargetState::Failed {
+                    error: format!("PTY session not found: {}", tab_id),
+                },
+            };
+            let delivered_at = matches!(state, TargetState::Running).then_some(now);
+            targets.push(BroadcastTarget {
+                tab_id,
+                state,
+                updated_at: now,
+                delivered_at,
+            });
+        }
+        targets
+    };
+    // Deliver to all tabs at once; each write waits out the submit delay
+    std::thread::scope(|scope| {
+        let deliveries: Vec<_> = targets
+            .iter_mut()
+            .filter(|t| matches!(t.state, TargetState::Running))
+            .map(|target| {
+                let (app, text, tab_id) = (&app_handle, &text, target.tab_id.clone());
+                (target, scope.spawn(move || deliver_prompt(app, &tab_id, text)))
+            })
+            .collect();
+        for (target, delivery) in deliveries {
+            let result = delivery
+                .join()
+                .unwrap_or_else(|_| Err("Delivery thread panicked".to_string()));
+            if let Err(error) = result {
+                target.state = TargetState::Failed { error };
+                target.delivered_at = None;
+            }
+        }
+    });
+
+    let mut broadcast = Broadcast {
+        id: format!("broadcast-{}", chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()),
+        prompt: text,
+        started_at: now,
+        targets,
+        summary: BroadcastSummary::default(),
+    };
+    broadcast.refresh_summary();
+    let mut broadcasts = state.0.lock().map_err(|e| e.to_string())?;
+    broadcasts.insert(0, broadcast.clone());
+    broadcasts.truncate(MAX_BROADCASTS);
+    let _ = app_handle.emit("broadcast-changed", &broadcast);
+    Ok(broadcast)
+}
+
+#[tauri::command]
+pub async fn cmd_get_broadcast(
+    broadcast_id: String,
+    state: State<'_, BroadcastState>,
+) -> Result<Broadcast, String> {
+    let broadcasts = state.0.lock().map_err(|e| e.to_string())?;
+    broadcasts
+        .iter()
+        .find(|b| b.id == broadcast_id)
+        .cloned()
+        .ok_or_else(|| format!("Broadcast not found: {}", broadcast_id))
+}
+
+#[tauri::command]
+pub async fn cmd_list_broadcasts(state: State<'_, BroadcastState>) -> Result<Vec<Broadcast>, String> {
+    let broadcasts = state.0.lock().map_err(|e| e.to_string())?;
+    Ok(broadcasts.clone())
+}
+
+#[tauri::command]
+pub async fn cmd_dismiss_broadcast(
+    broadcast_id: String,
+    state: State<'_, BroadcastState>,
+) -> Result<(), String> {
+    let mut broadcasts = state.0.lock().map_err(|e| e.to_string())?;
+    broadcasts.retain(|b| b.id != broadcast_id);
+    Ok(())
+}
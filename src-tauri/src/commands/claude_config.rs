@@ -1,7 +1,10 @@
 use crate::data::claude_config::discover_extensions;
 use crate::models::claude_config::ClaudeExtension;
+use crate::paths::{claude_home, claude_json_path};
 
 #[tauri::command]
 pub async fn cmd_load_extensions(project_dir: String) -> Result<Vec<ClaudeExtension>, String> {
-    Ok(discover_extensions(&project_dir))
+    let claude_home = claude_home()?;
+    let claude_json = claude_json_path(&claude_home)?;
+    Ok(discover_extensions(&claude_home, &claude_json, &project_dir))
 }
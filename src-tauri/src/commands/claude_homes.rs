@@ -0,0 +1,136 @@
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+use crate::data::claude_homes::{load_claude_homes, save_claude_homes};
+use crate::models::claude_home::{ClaudeHome, ClaudeHomes};
+use crate::paths::{claude_homes_dir, default_claude_home, set_active_claude_home};
+
+/// Id of the implicit default root (`CLAUDE_CONFIG_DIR` or `~/.claude`).
+const DEFAULT_HOME_ID: &str = "default";
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeHomeInfo {
+    pub id: String,
+    pub name: String,
+    pub path: String,
+    pub active: bool,
+    /// False until Claude has been run (or logged in) with this root
+    pub exists: bool,
+    /// The default root, which can't be edited or removed
+    pub builtin: bool,
+}
+
+fn home_infos(registry: &ClaudeHomes) -> Result<Vec<ClaudeHomeInfo>, String> {
+    let default = default_claude_home()?;
+    let from_env = std::env::var("CLAUDE_CONFIG_DIR").is_ok_and(|d| !d.is_empty());
+    let mut infos = vec![ClaudeHomeInfo {
+        id: DEFAULT_HOME_ID.to_string(),
+        name: if from_env { "Default (CLAUDE_CONFIG_DIR)" } else { "Default" }.to_string(),
+        path: default.to_string_lossy().replace('\\', "/"),
+        active: registry.active.is_none(),
+        exists: default.is_dir(),
+        builtin: true,
+    }];
+    infos.extend(registry.homes.iter().map(|h| ClaudeHomeInfo {
+        id: h.id.clone(),
+        name: h.name.clone(),
+        path: h.path.replace('\\', "/"),
+        active: registry.active.as_deref() == Some(h.id.as_str()),
+        exists: PathBuf::from(&h.path).is_dir(),
+        builtin: false,
+    }));
+    Ok(infos)
+}
+
+/// Make the registry's active root the one every command, watcher and new
+/// Claude session uses, then tell the frontend to reload.
+fn activate(app: &AppHandle, registry: &ClaudeHomes) -> Result<(), String> {
+    let path = registry
+        .active
+        .as_ref()
+        .and_then(|id| registry.homes.iter().find(|h| &h.id == id))
+        .map(|h| PathBuf::from(&h.path));
+    set_active_claude_home(path);
+
+    if let Err(e) = crate::commands::projects::migrate_project_data_dirs() {
+        eprintln!("[ide] project data migration failed: {}", e);
+    }
+    if let Err(e) = crate::commands::hooks::auto_setup_hooks(app) {
+        eprintln!("[ide] hook setup failed: {}", e);
+    }
+    crate::watcher::claude_watcher::start_claude_watcher(app.clone());
+
+    let active = home_infos(registry)?.into_iter().find(|h| h.active);
+    let _ = app.emit("claude-home-changed", &active);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn cmd_list_claude_homes() -> Result<Vec<ClaudeHomeInfo>, String> {
+    home_infos(&load_claude_homes(&claude_homes_dir()?))
+}
+
+/// Replace the registered roots. Removing the active one switches back to the default.
+#[tauri::command]
+pub async fn cmd_save_claude_homes(
+    app: AppHandle,
+    homes: Vec<ClaudeHome>,
+) -> Result<Vec<ClaudeHomeInfo>, String> {
+    let mut ids = HashSet::new();
+    for home in &homes {
+        if home.id == DEFAULT_HOME_ID || !ids.insert(home.id.as_str()) {
+            return Err(format!("Duplicate Claude home id: {}", home.id));
+        }
+        if home.name.trim().is_empty() {
+            return Err("Claude home name is required".to_string());
+        }
+        if !PathBuf::from(&home.path).is_absolute() {
+            return Err(format!("Claude home path must be absolute: {}", home.path));
+        }
+    }
+
+    let dir = claude_homes_dir()?;
+    let mut registry = load_claude_homes(&dir);
+    let active_removed = registry
+        .active
+        .as_ref()
+        .is_some_and(|id| !homes.iter().any(|h| &h.id == id));
+    let active_moved = registry.active.as_ref().is_some_and(|id| {
+        let old = registry.homes.iter().find(|h| &h.id == id).map(|h| &h.path);
+        let new = homes.iter().find(|h| &h.id == id).map(|h| &h.path);
+        old != new
+    });
+    registry.homes = homes;
+    if active_removed {
+        registry.active = None;
+    }
+    save_claude_homes(&dir, &registry).map_err(|e| e.to_string())?;
+    if active_removed || active_moved {
+        activate(&app, &registry)?;
+    }
+    home_infos(&registry)
+}
+
+/// Switch the whole studio to another Claude home.
+#[tauri::command]
+pub async fn cmd_switch_claude_home(app: AppHandle, id: String) -> Result<ClaudeHomeInfo, String> {
+    let dir = claude_homes_dir()?;
+    let mut registry = load_claude_homes(&dir);
+    registry.active = if id == DEFAULT_HOME_ID {
+        None
+    } else if registry.homes.iter().any(|h| h.id == id) {
+        Some(id)
+    } else {
+        return Err(format!("Unknown Claude home: {}", id));
+    };
+    save_claude_homes(&dir, &registry).map_err(|e| e.to_string())?;
+    activate(&app, &registry)?;
+    home_infos(&registry)?
+        .into_iter()
+        .find(|h| h.active)
+        .ok_or_else(|| "No active Claude home".to_string())
+}
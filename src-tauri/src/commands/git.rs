@@ -1,7 +1,12 @@
-use crate::data::git;
-use crate::models::git::{DiffLine, GitStatus};
+use crate::data::{git, git_commit, git_conflict, git_log, git_push, git_stash, git_worktree};
+use crate::data::git::SelectionAction;
+use crate::data::git_conflict::ConflictStep;
+use crate::models::git::{
+    BlameLine, CommitDetail, CommitOptions, ConflictResolution, ConflictState, CreatedCommit, DiffLine,
+    DiffSelection, GitStatus, LogPage, LogQuery, PushOptions, PushProgress, PushResult, RemoveWorktreeOptions,
+    StashEntry, StashOptions, WorktreeInfo, WorktreeSource,
+};
 use crate::watcher::git_watcher::GitWatcherState;
-use std::fs;
 use std::path::PathBuf;
 
 #[tauri::command]
@@ -14,6 +19,15 @@ pub async fn cmd_git_diff(cwd: String, path: String, staged: bool) -> Result<Vec
     git::load_diff(&PathBuf::from(&cwd), &path, staged).map_err(|e| e.to_string())
 }
 
+/// Per-line blame of a file, flagging lines from agent commits.
+#[tauri::command]
+pub async fn cmd_git_blame(cwd: String, path: String) -> Result<Vec<BlameLine>, String> {
+    tokio::task::spawn_blocking(move || git::load_blame(&PathBuf::from(&cwd), &path))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn cmd_git_branches(cwd: String) -> Result<Vec<String>, String> {
     git::load_branches(&PathBuf::from(&cwd)).map_err(|e| e.to_string())
@@ -24,58 +38,24 @@ pub async fn cmd_git_current_branch(cwd: String) -> Result<String, String> {
     git::load_current_branch(&PathBuf::from(&cwd)).map_err(|e| e.to_string())
 }
 
-// ─── Git Log Types ────────────────────────────────────────────────────────────
-
-#[derive(serde::Serialize)]
-pub struct CommitInfo {
-    pub hash: String,
-    pub message: String,
-    pub author: String,
-    pub date: String,
-    pub refs: Vec<String>,
+/// One page of history with graph lanes; pass the returned `nextCursor` back
+/// for the next page.
+#[tauri::command]
+pub async fn cmd_git_log(cwd: String, query: Option<LogQuery>) -> Result<LogPage, String> {
+    let query = query.unwrap_or_default();
+    tokio::task::spawn_blocking(move || git_log::load_log(&PathBuf::from(&cwd), &query))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+        .map_err(|e| e.to_string())
 }
 
+/// Full message, changed files and per-file diffs of one commit.
 #[tauri::command]
-pub async fn cmd_git_log(cwd: String, limit: Option<u32>) -> Result<Vec<CommitInfo>, String> {
-    let max = limit.unwrap_or(100).to_string();
-    let output = crate::utils::silent_command("git")
-        .args([
-            "-C", &cwd,
-            "log",
-            "--pretty=format:%h%x00%s%x00%an%x00%ar%x00%D",
-            "--max-count", &max,
-        ])
-        .output()
-        .map_err(|e| format!("Failed to run git log: {}", e))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("git log failed: {}", stderr.trim()));
-    }
-
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let commits = stdout
-        .lines()
-        .filter(|l| !l.is_empty())
-        .map(|line| {
-            let parts: Vec<&str> = line.splitn(5, '\x00').collect();
-            let refs_raw = parts.get(4).copied().unwrap_or("");
-            let refs: Vec<String> = refs_raw
-                .split(',')
-                .map(|s| s.trim().to_string())
-                .filter(|s| !s.is_empty())
-                .collect();
-            CommitInfo {
-                hash: parts.first().copied().unwrap_or("").to_string(),
-                message: parts.get(1).copied().unwrap_or("").to_string(),
-                author: parts.get(2).copied().unwrap_or("").to_string(),
-                date: parts.get(3).copied().unwrap_or("").to_string(),
-                refs,
-            }
-        })
-        .collect();
-
-    Ok(commits)
+pub async fn cmd_git_commit_detail(cwd: String, hash: String) -> Result<CommitDetail, String> {
+    tokio::task::spawn_blocking(move || git_log::load_commit_detail(&PathBuf::from(&cwd), &hash))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+        .map_err(|e| e.to_string())
 }
 
 // ─── Remote Branch Types ──────────────────────────────────────────────────────
@@ -90,175 +70,28 @@ pub struct RemoteBranch {
 
 #[tauri::command]
 pub async fn cmd_git_remote_branches(cwd: String) -> Result<Vec<RemoteBranch>, String> {
-    let output = crate::utils::silent_command("git")
-        .args(["-C", &cwd, "branch", "-r", "--format=%(refname:short)"])
-        .output()
-        .map_err(|e| format!("Failed to run git branch -r: {}", e))?;
-
-    if !output.status.success() {
-        return Ok(vec![]);
-    }
-
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let branches = stdout
-        .lines()
-        .filter(|l| !l.is_empty() && !l.trim().ends_with("/HEAD"))
-        .filter_map(|line| {
-            let trimmed = line.trim();
-            let slash = trimmed.find('/')?;
-            Some(RemoteBranch {
-                remote: trimmed[..slash].to_string(),
-                branch: trimmed[slash + 1..].to_string(),
-                full_ref: trimmed.to_string(),
-            })
-        })
-        .collect();
-
-    Ok(branches)
-}
-
-// ─── Worktree Types ───────────────────────────────────────────────────────────
-
-#[derive(serde::Serialize)]
-#[serde(rename_all = "camelCase")]
-pub struct WorktreeInfo {
-    pub path: String,
-    pub head: String,       // short SHA (first 8 chars)
-    pub branch: String,     // short: "feature/auth" not "refs/heads/feature/auth"
-    pub is_main: bool,      // true for the first (main) worktree entry
-    pub is_prunable: bool,  // true if "prunable" line present in porcelain output
-}
-
-// ─── Private Helpers ─────────────────────────────────────────────────────────
-
-/// Returns the path of the main worktree root by parsing `git worktree list --porcelain`.
-fn find_main_worktree_root(project_path: &str) -> Result<String, String> {
-    let output = crate::utils::silent_command("git")
-        .args(["-C", project_path, "worktree", "list", "--porcelain"])
-        .output()
-        .map_err(|e| format!("Failed to run git: {}", e))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("git worktree list failed: {}", stderr.trim()));
-    }
-
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    for line in stdout.lines() {
-        if let Some(path) = line.strip_prefix("worktree ") {
-            return Ok(path.trim().to_string());
-        }
-    }
-    Err("Could not find main worktree".to_string())
-}
-
-/// Parses `git worktree list --porcelain` stdout into a Vec<WorktreeInfo>.
-fn parse_worktrees(output: &str) -> Vec<WorktreeInfo> {
-    let mut worktrees = Vec::new();
-    let mut is_first = true;
-
-    // Blocks are separated by blank lines
-    for block in output.split("\n\n") {
-        let block = block.trim();
-        if block.is_empty() {
-            continue;
-        }
-
-        let mut path = String::new();
-        let mut head = String::new();
-        let mut branch = String::new();
-        let mut is_prunable = false;
-
-        for line in block.lines() {
-            if let Some(v) = line.strip_prefix("worktree ") {
-                path = v.trim().to_string();
-            } else if let Some(v) = line.strip_prefix("HEAD ") {
-                let sha = v.trim();
-                head = if sha.len() >= 8 {
-                    sha[..8].to_string()
-                } else {
-                    sha.to_string()
-                };
-            } else if let Some(v) = line.strip_prefix("branch ") {
-                let b = v.trim();
-                branch = b.strip_prefix("refs/heads/").unwrap_or(b).to_string();
-            } else if line.starts_with("prunable") {
-                is_prunable = true;
-            } else if line == "detached" {
-                branch = "(detached)".to_string();
-            }
-        }
-
-        if path.is_empty() {
-            continue;
-        }
-
-        worktrees.push(WorktreeInfo {
-            path,
-            head,
+    let branches = git::load_remote_branches(&PathBuf::from(&cwd)).map_err(|e| e.to_string())?;
+    Ok(branches
+        .into_iter()
+        .map(|(remote, branch)| RemoteBranch {
+            full_ref: format!("{}/{}", remote, branch),
+            remote,
             branch,
-            is_main: is_first,
-            is_prunable,
-        });
-        is_first = false;
-    }
-
-    worktrees
-}
-
-/// Reads `.worktree_copy` from `project_path` as a JSON array of strings.
-fn read_worktree_copy(project_path: &str) -> Result<Vec<String>, String> {
-    let copy_file = PathBuf::from(project_path).join(".worktree_copy");
-    if !copy_file.exists() {
-        return Ok(vec![]);
-    }
-    let content = fs::read_to_string(&copy_file)
-        .map_err(|e| format!("Failed to read .worktree_copy: {}", e))?;
-    let entries: Vec<String> = serde_json::from_str(&content)
-        .map_err(|e| format!("Failed to parse .worktree_copy: {}", e))?;
-    Ok(entries)
-}
-
-/// Recursively copies `src` directory into `dst`.
-fn copy_dir_recursive(src: &PathBuf, dst: &PathBuf) -> std::io::Result<()> {
-    if !dst.exists() {
-        fs::create_dir_all(dst)?;
-    }
-    for entry in fs::read_dir(src)? {
-        let entry = entry?;
-        let src_path = entry.path();
-        let dst_path = dst.join(entry.file_name());
-        if src_path.is_dir() {
-            copy_dir_recursive(&src_path, &dst_path)?;
-        } else {
-            fs::copy(&src_path, &dst_path)?;
-        }
-    }
-    Ok(())
+        })
+        .collect())
 }
 
-// ─── Commands ────────────────────────────────────────────────────────────────
+// ─── Worktrees ───────────────────────────────────────────────────────────────
 
 #[tauri::command]
 pub async fn cmd_list_worktrees(project_path: String) -> Result<Vec<WorktreeInfo>, String> {
-    let output = crate::utils::silent_command("git")
-        .args(["-C", &project_path, "worktree", "list", "--porcelain"])
-        .output()
-        .map_err(|e| format!("Failed to run git: {}", e))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("git worktree list failed: {}", stderr.trim()));
-    }
-
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    Ok(parse_worktrees(&stdout))
+    git_worktree::list_worktrees(&PathBuf::from(&project_path)).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
 pub async fn cmd_get_worktree_copy(project_path: String) -> Result<Vec<String>, String> {
-    let main_root = find_main_worktree_root(&project_path)?;
-    read_worktree_copy(&main_root)
+    let main_root = git_worktree::main_worktree_root(&PathBuf::from(&project_path)).map_err(|e| e.to_string())?;
+    git_worktree::read_worktree_copy(&main_root).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -266,91 +99,56 @@ pub async fn cmd_set_worktree_copy(
     project_path: String,
     entries: Vec<String>,
 ) -> Result<(), String> {
-    let main_root = find_main_worktree_root(&project_path)?;
-    let copy_file = PathBuf::from(&main_root).join(".worktree_copy");
-    let content = serde_json::to_string_pretty(&entries)
-        .map_err(|e| format!("Failed to serialize entries: {}", e))?;
-    fs::write(&copy_file, content)
-        .map_err(|e| format!("Failed to write .worktree_copy: {}", e))?;
-    Ok(())
+    let main_root = git_worktree::main_worktree_root(&PathBuf::from(&project_path)).map_err(|e| e.to_string())?;
+    git_worktree::write_worktree_copy(&main_root, &entries).map_err(|e| e.to_string())
 }
 
+/// Create a worktree next to the project for `branch_name`: a new branch by
+/// default, or an existing, remote or pull request branch per `source`.
 #[tauri::command]
 pub async fn cmd_create_worktree(
     project_path: String,
     branch_name: String,
+    source: Option<WorktreeSource>,
 ) -> Result<String, String> {
-    // Sanitize branch_name for use as a directory name: / and spaces → -, lowercase
-    let dir_name = branch_name
-        .replace('/', "-")
-        .replace(' ', "-")
-        .to_lowercase();
-
-    // Compute parent dir of project
-    let project = PathBuf::from(&project_path);
-    let parent = project
-        .parent()
-        .ok_or_else(|| format!("Cannot determine parent directory of {}", project_path))?;
-
-    // worktree_path = parent / sanitized_name
-    let worktree_path = parent.join(&dir_name);
-    let worktree_path_str = worktree_path
-        .to_str()
-        .ok_or_else(|| "Invalid path encoding".to_string())?
-        .to_string();
-
-    // Run: git -C <project_path> worktree add <worktree_path> -b <branch_name>
-    let output = crate::utils::silent_command("git")
-        .args([
-            "-C",
-            &project_path,
-            "worktree",
-            "add",
-            &worktree_path_str,
-            "-b",
-            &branch_name,
-        ])
-        .output()
-        .map_err(|e| format!("Failed to run git: {}", e))?;
+    let source = source.unwrap_or_default();
+    tokio::task::spawn_blocking(move || {
+        git_worktree::create_worktree(&PathBuf::from(&project_path), &branch_name, &source)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+    .map_err(|e| e.to_string())
+}
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("git worktree add failed: {}", stderr.trim()));
-    }
+#[tauri::command]
+pub async fn cmd_remove_worktree(
+    project_path: String,
+    path: String,
+    options: Option<RemoveWorktreeOptions>,
+) -> Result<(), String> {
+    let options = options.unwrap_or_default();
+    tokio::task::spawn_blocking(move || {
+        git_worktree::remove_worktree(&PathBuf::from(&project_path), &path, &options)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+    .map_err(|e| e.to_string())
+}
 
-    // Post-creation: copy files listed in .worktree_copy from base project
-    let mut copy_warnings: Vec<String> = Vec::new();
-    if let Ok(entries) = read_worktree_copy(&project_path) {
-        for entry in entries {
-            let src = PathBuf::from(&project_path).join(&entry);
-            let dst = PathBuf::from(&worktree_path_str).join(&entry);
-            if src.is_file() {
-                if let Some(parent) = dst.parent() {
-                    if let Err(e) = fs::create_dir_all(parent) {
-                        copy_warnings.push(format!("mkdir {}: {}", parent.display(), e));
-                        continue;
-                    }
-                }
-                if let Err(e) = fs::copy(&src, &dst) {
-                    copy_warnings.push(format!("copy {}: {}", entry, e));
-                }
-            } else if src.is_dir() {
-                if let Err(e) = copy_dir_recursive(&src, &dst) {
-                    copy_warnings.push(format!("copy dir {}: {}", entry, e));
-                }
-            }
-        }
-    }
+/// Drop entries of worktrees whose directories are gone; returns their paths.
+#[tauri::command]
+pub async fn cmd_prune_worktrees(project_path: String) -> Result<Vec<String>, String> {
+    git_worktree::prune_worktrees(&PathBuf::from(&project_path)).map_err(|e| e.to_string())
+}
 
-    if copy_warnings.is_empty() {
-        Ok(worktree_path_str)
-    } else {
-        Ok(format!(
-            "{}\nWarnings copying files: {}",
-            worktree_path_str,
-            copy_warnings.join("; ")
-        ))
-    }
+#[tauri::command]
+pub async fn cmd_lock_worktree(project_path: String, path: String, reason: Option<String>) -> Result<(), String> {
+    git_worktree::lock_worktree(&PathBuf::from(&project_path), &path, reason.as_deref()).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn cmd_unlock_worktree(project_path: String, path: String) -> Result<(), String> {
+    git_worktree::unlock_worktree(&PathBuf::from(&project_path), &path).map_err(|e| e.to_string())
 }
 
 /// Helper: combine stdout+stderr and return Ok/Err based on exit status.
@@ -383,6 +181,86 @@ pub async fn cmd_git_pull(cwd: String) -> Result<String, String> {
     git_output_result(output)
 }
 
+#[derive(Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PushFinishedPayload {
+    cwd: String,
+    result: Option<PushResult>,
+    error: Option<String>,
+}
+
+/// Push the checked-out branch, setting upstream when missing. Streams
+/// `git-push-progress` while running and `git-push-finished` at the end.
+/// Rejected refs come back in the result with a reason, not as an error.
+#[tauri::command]
+pub async fn cmd_git_push(
+    app: tauri::AppHandle,
+    cwd: String,
+    options: PushOptions,
+) -> Result<PushResult, String> {
+    use tauri::Emitter;
+
+    let progress_app = app.clone();
+    let progress_cwd = cwd.clone();
+    let dir = PathBuf::from(&cwd);
+    let result = tokio::task::spawn_blocking(move || {
+        git_push::push_branch(&dir, &options, |stage, percent, line| {
+            let _ = progress_app.emit(
+                "git-push-progress",
+                PushProgress {
+                    cwd: progress_cwd.clone(),
+                    stage: stage.to_string(),
+                    percent,
+                    line: line.to_string(),
+                },
+            );
+        })
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+    .map_err(|e| e.to_string());
+
+    let _ = app.emit(
+        "git-push-finished",
+        PushFinishedPayload {
+            cwd,
+            result: result.as_ref().ok().cloned(),
+            error: result.as_ref().err().cloned(),
+        },
+    );
+    result
+}
+
+#[tauri::command]
+pub async fn cmd_git_stash_list(cwd: String) -> Result<Vec<StashEntry>, String> {
+    git_stash::list_stashes(&PathBuf::from(&cwd)).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn cmd_git_stash_save(cwd: String, options: StashOptions) -> Result<StashEntry, String> {
+    git_stash::save_stash(&PathBuf::from(&cwd), &options).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn cmd_git_stash_show(cwd: String, hash: String) -> Result<Vec<DiffLine>, String> {
+    git_stash::show_stash(&PathBuf::from(&cwd), &hash).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn cmd_git_stash_apply(cwd: String, hash: String) -> Result<(), String> {
+    git_stash::apply_stash(&PathBuf::from(&cwd), &hash).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn cmd_git_stash_pop(cwd: String, hash: String) -> Result<(), String> {
+    git_stash::pop_stash(&PathBuf::from(&cwd), &hash).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn cmd_git_stash_drop(cwd: String, hash: String) -> Result<(), String> {
+    git_stash::drop_stash(&PathBuf::from(&cwd), &hash).map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn cmd_git_create_branch(
     cwd: String,
@@ -405,6 +283,37 @@ pub async fn cmd_git_add(cwd: String, path: String) -> Result<String, String> {
     git_output_result(output)
 }
 
+/// Stage the selected hunks and lines of the file's unstaged diff.
+#[tauri::command]
+pub async fn cmd_git_stage_lines(cwd: String, path: String, selection: DiffSelection) -> Result<(), String> {
+    git::apply_selection(&PathBuf::from(&cwd), &path, &selection, SelectionAction::Stage)
+        .map_err(|e| e.to_string())
+}
+
+/// Unstage the selected hunks and lines of the file's staged diff.
+#[tauri::command]
+pub async fn cmd_git_unstage_lines(cwd: String, path: String, selection: DiffSelection) -> Result<(), String> {
+    git::apply_selection(&PathBuf::from(&cwd), &path, &selection, SelectionAction::Unstage)
+        .map_err(|e| e.to_string())
+}
+
+/// Revert the selected hunks and lines of the file's unstaged diff in the working tree.
+#[tauri::command]
+pub async fn cmd_git_discard_lines(cwd: String, path: String, selection: DiffSelection) -> Result<(), String> {
+    git::apply_selection(&PathBuf::from(&cwd), &path, &selection, SelectionAction::Discard)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn cmd_git_unstage(cwd: String, path: String) -> Result<(), String> {
+    git::unstage_file(&PathBuf::from(&cwd), &path).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn cmd_git_discard(cwd: String, path: String) -> Result<(), String> {
+    git::discard_file(&PathBuf::from(&cwd), &path).map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn cmd_git_ignore(cwd: String, file_path: String) -> Result<String, String> {
     use std::io::Write;
@@ -449,6 +358,8 @@ pub async fn cmd_git_exclude(cwd: String, file_path: String) -> Result<String, S
     Ok("Added to .git/info/exclude".to_string())
 }
 
+/// Rebase the current branch onto `onto_branch`. Stopping on conflicts is not
+/// an error: the rebase is left in progress for the conflict commands.
 #[tauri::command]
 pub async fn cmd_git_rebase(cwd: String, onto_branch: String) -> Result<String, String> {
     let dir = std::path::PathBuf::from(&cwd);
@@ -456,45 +367,221 @@ pub async fn cmd_git_rebase(cwd: String, onto_branch: String) -> Result<String,
         return Err(format!("Directory does not exist: {}", cwd));
     }
 
-    // Validate branch name to prevent prompt injection
     let valid_branch = onto_branch.chars().all(|c| {
         c.is_ascii_alphanumeric() || matches!(c, '/' | '_' | '.' | '-')
     });
-    if !valid_branch || onto_branch.is_empty() {
+    if !valid_branch || onto_branch.is_empty() || onto_branch.starts_with('-') {
         return Err("Invalid branch name: must match [a-zA-Z0-9/_.-]+".to_string());
     }
 
-    let prompt = format!(
-        "Rebase the current branch onto '{}'. Run 'git rebase {}'. \
-         If there are merge conflicts, attempt to resolve them sensibly using git commands, \
-         then continue with 'git rebase --continue'. \
-         If rebase cannot be completed cleanly, abort with 'git rebase --abort' and clearly explain why. \
-         Output the final result.",
-        onto_branch, onto_branch
-    );
+    let output = tokio::task::spawn_blocking(move || {
+        crate::utils::silent_command("git")
+            .args(["-C", &cwd, "rebase", &onto_branch])
+            .env("GIT_EDITOR", "true")
+            .output()
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+    .map_err(|e| format!("Failed to run git rebase: {}", e))?;
+
+    if output.status.success() {
+        return git_output_result(output);
+    }
+    let state = git_conflict::load_conflict_state(&dir).map_err(|e| e.to_string())?;
+    if state.operation.is_some() && !state.conflicts.is_empty() {
+        Ok(format!(
+            "Rebase stopped on conflicts in {} file{}; resolve them in the Git panel",
+            state.conflicts.len(),
+            if state.conflicts.len() == 1 { "" } else { "s" }
+        ))
+    } else {
+        git_output_result(output)
+    }
+}
+
+// ─── Conflicts ───────────────────────────────────────────────────────────────
+
+/// The merge, rebase, cherry-pick or revert in progress and its conflicted files.
+#[tauri::command]
+pub async fn cmd_git_conflicts(cwd: String) -> Result<ConflictState, String> {
+    tokio::task::spawn_blocking(move || git_conflict::load_conflict_state(&PathBuf::from(&cwd)))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn cmd_git_resolve_conflict(
+    cwd: String,
+    path: String,
+    resolution: ConflictResolution,
+) -> Result<(), String> {
+    git_conflict::resolve_conflict(&PathBuf::from(&cwd), &path, &resolution).map_err(|e| e.to_string())
+}
+
+/// Stage conflicted files as edited in the working tree.
+#[tauri::command]
+pub async fn cmd_git_mark_resolved(cwd: String, paths: Vec<String>) -> Result<(), String> {
+    git_conflict::mark_resolved(&PathBuf::from(&cwd), &paths).map_err(|e| e.to_string())
+}
 
+async fn conflict_step(cwd: String, step: ConflictStep) -> Result<ConflictState, String> {
+    tokio::task::spawn_blocking(move || git_conflict::run_conflict_step(&PathBuf::from(&cwd), step))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn cmd_git_operation_continue(cwd: String) -> Result<ConflictState, String> {
+    conflict_step(cwd, ConflictStep::Continue).await
+}
+
+#[tauri::command]
+pub async fn cmd_git_operation_skip(cwd: String) -> Result<ConflictState, String> {
+    conflict_step(cwd, ConflictStep::Skip).await
+}
+
+#[tauri::command]
+pub async fn cmd_git_operation_abort(cwd: String) -> Result<ConflictState, String> {
+    conflict_step(cwd, ConflictStep::Abort).await
+}
+
+/// Largest conflict (all versions together) sent to Claude for a resolution.
+const RESOLVE_INPUT_LIMIT: usize = 200_000;
+
+/// Ask Claude to merge one conflicted file. Only returns the proposed
+/// content; nothing is written until it is applied with
+/// `cmd_git_resolve_conflict`. Claude runs without tools that could edit the
+/// file, stage it or continue the operation.
+#[tauri::command]
+pub async fn cmd_git_suggest_resolution(cwd: String, path: String) -> Result<String, String> {
+    use std::io::Write;
+
+    let dir = PathBuf::from(&cwd);
+    let input = git_conflict::conflict_prompt_input(&dir, &path).map_err(|e| e.to_string())?;
+    if input.len() > RESOLVE_INPUT_LIMIT {
+        return Err(format!("{} is too large to resolve with Claude; resolve it by hand", path));
+    }
+
+    let prompt = "Resolve the git merge conflict described on stdin. It gives the base, ours, theirs and working-tree versions of one file. Combine both sides' intent; where they truly contradict, prefer the incoming change and keep the file valid. Output only the complete resolved file content, with no conflict markers, code fences or commentary.";
     let output = tokio::task::spawn_blocking(move || {
-        crate::utils::silent_command("claude")
-            .args(["-p", &prompt, "--dangerously-skip-permissions"])
+        let mut child = crate::utils::silent_command("claude")
+            .args(["-p", prompt, "--disallowedTools"])
+            .args(TEXT_ONLY_DISALLOWED_TOOLS)
             .current_dir(&dir)
             .env_remove("CLAUDECODE")
-            .output()
+            .envs(crate::paths::claude_config_env())
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .spawn()?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(input.as_bytes())?;
+        }
+        child.wait_with_output()
     })
     .await
     .map_err(|e| format!("Task join error: {}", e))?
     .map_err(|e| format!("Failed to run claude: {}", e))?;
 
     let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-    let combined = format!("{}{}", stdout, stderr);
+    if output.status.success() && !stdout.trim().is_empty() {
+        Ok(strip_code_fence(&stdout))
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        Err(if stderr.is_empty() {
+            format!("claude exited with code {}", output.status)
+        } else {
+            stderr
+        })
+    }
+}
 
-    if output.status.success() {
-        Ok(combined.trim().to_string())
+/// Drop a code fence wrapped around the whole reply despite the prompt.
+fn strip_code_fence(text: &str) -> String {
+    let trimmed = text.trim();
+    if let Some(rest) = trimmed.strip_prefix("```") {
+        if let Some(body) = rest.strip_suffix("```") {
+            // Skip the language tag line
+            let body = body.split_once('\n').map(|(_, b)| b).unwrap_or_default();
+            return body.to_string();
+        }
+    }
+    let mut out = trimmed.to_string();
+    out.push('\n');
+    out
+}
+
+/// Commit the staged changes. Blocking because a signing program may wait on
+/// a passphrase prompt.
+#[tauri::command]
+pub async fn cmd_git_commit(cwd: String, options: CommitOptions) -> Result<CreatedCommit, String> {
+    tokio::task::spawn_blocking(move || git_commit::create_commit(&PathBuf::from(&cwd), &options))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+        .map_err(|e| e.to_string())
+}
+
+/// Largest staged diff sent to Claude for a message suggestion.
+const SUGGEST_DIFF_LIMIT: usize = 100_000;
+
+/// Tools denied to `claude -p` calls that must only produce text. A deny
+/// overrides the user's allow rules, so a `Bash(git …)` rule in settings.json
+/// can't let the call run git or touch files in `cwd`.
+const TEXT_ONLY_DISALLOWED_TOOLS: &[&str] = &["Bash", "Edit", "MultiEdit", "Write", "NotebookEdit"];
+
+/// Ask Claude for a commit message describing the staged diff. Only returns
+/// text; nothing is staged or committed, and Claude runs without tools that
+/// could change the repository.
+#[tauri::command]
+pub async fn cmd_git_suggest_commit_message(cwd: String) -> Result<String, String> {
+    use std::io::Write;
+
+    let dir = PathBuf::from(&cwd);
+    let mut diff = git::staged_diff_text(&dir).map_err(|e| e.to_string())?;
+    if diff.trim().is_empty() {
+        return Err("No staged changes to describe".to_string());
+    }
+    if diff.len() > SUGGEST_DIFF_LIMIT {
+        let mut end = SUGGEST_DIFF_LIMIT;
+        while !diff.is_char_boundary(end) {
+            end -= 1;
+        }
+        diff.truncate(end);
+        diff.push_str("\n[diff truncated]\n");
+    }
+
+    let prompt = "Write a git commit message for the staged diff given on stdin. Use an imperative subject line of at most 72 characters; add a blank line and a short body only if the change needs explaining. Output only the commit message, without code fences, trailers or commentary.";
+    let output = tokio::task::spawn_blocking(move || {
+        let mut child = crate::utils::silent_command("claude")
+            .args(["-p", prompt, "--disallowedTools"])
+            .args(TEXT_ONLY_DISALLOWED_TOOLS)
+            .current_dir(&dir)
+            .env_remove("CLAUDECODE")
+            .envs(crate::paths::claude_config_env())
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .spawn()?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(diff.as_bytes())?;
+        }
+        child.wait_with_output()
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?
+    .map_err(|e| format!("Failed to run claude: {}", e))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if output.status.success() && !stdout.is_empty() {
+        Ok(stdout)
     } else {
-        Err(if combined.trim().is_empty() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        Err(if stderr.is_empty() {
             format!("claude exited with code {}", output.status)
         } else {
-            combined.trim().to_string()
+            stderr
         })
     }
 }
@@ -518,6 +605,7 @@ pub async fn cmd_claude_git_action(cwd: String, action: String) -> Result<String
             .args(["-p", &prompt, "--dangerously-skip-permissions"])
             .current_dir(&dir)
             .env_remove("CLAUDECODE")
+            .envs(crate::paths::claude_config_env())
             .output()
     })
     .await
@@ -540,10 +628,16 @@ pub async fn cmd_claude_git_action(cwd: String, action: String) -> Result<String
 }
 
 #[tauri::command]
-pub async fn cmd_watch_git_head(
+pub async fn cmd_watch_git(
     cwd: String,
     app_handle: tauri::AppHandle,
     state: tauri::State<'_, GitWatcherState>,
 ) -> Result<(), String> {
-    crate::watcher::git_watcher::start_git_head_watch(app_handle, cwd, &state)
+    crate::watcher::git_watcher::start_repo_watch(app_handle, cwd, &state)
+}
+
+#[tauri::command]
+pub async fn cmd_unwatch_git(cwd: String, state: tauri::State<'_, GitWatcherState>) -> Result<(), String> {
+    crate::watcher::git_watcher::stop_repo_watch(&cwd, &state);
+    Ok(())
 }
@@ -1,15 +1,17 @@
-use crate::data::hook_state::{build_active_sessions, parse_hook_events, ActiveSession};
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
 use serde_json::Value;
+use tauri::{AppHandle, Manager};
 
-fn get_claude_home() -> Result<std::path::PathBuf, String> {
-    let home = std::env::var("USERPROFILE")
-        .or_else(|_| std::env::var("HOME"))
-        .map_err(|_| "Neither USERPROFILE nor HOME environment variable is set".to_string())?;
-    if home.is_empty() {
-        return Err("Home directory environment variable is empty".to_string());
-    }
-    Ok(std::path::PathBuf::from(home).join(".claude"))
-}
+use crate::commands::projects::{load_project_settings, save_project_settings};
+use crate::data::claude_settings::{diff_settings, read_settings, write_settings};
+use crate::data::hook_state::{build_active_sessions, parse_hook_events, ActiveSession};
+use crate::data::path_encoding::encode_project_path;
+use crate::data::projects::discover_projects;
+use crate::models::git::DiffLine;
+use crate::models::hook_scope::HookScope;
+use crate::paths::claude_home;
 
 /// Returns the hook command string: `node /path/to/hook.js`
 /// Using a Node.js script file avoids cmd.exe double-quote quoting issues
@@ -22,8 +24,10 @@ fn hook_command(hook_dir: &std::path::Path) -> String {
     format!("node {}", hook_js_path)
 }
 
-/// The Node.js script that reads stdin and appends the JSON line to hook-events.jsonl.
-/// Written to ~/.claude/theassociate/hook.js by cmd_setup_hooks.
+/// The Node.js script that reads stdin, stamps the event with the time it ran
+/// (`timestamp`, Unix millis) and appends the JSON line to hook-events.jsonl.
+/// Written to `<claude home>/theassociate/hook.js` by cmd_setup_hooks; it logs next
+/// to itself so each Claude home keeps its own event log.
 fn hook_js_content() -> &'static str {
     r#"'use strict';
 var d = '';
@@ -31,93 +35,93 @@ process.stdin.on('data', function(c) { d += c; });
 process.stdin.on('end', function() {
   var line = d.trim();
   if (!line) return;
+  try {
+    var ev = JSON.parse(line);
+    ev.timestamp = Date.now();
+    line = JSON.stringify(ev);
+  } catch(e) {}
   try {
     var path = require('path');
     var fs = require('fs');
-    var home = process.env.USERPROFILE || process.env.HOME || '';
-    var out = path.join(home, '.claude', 'theassociate', 'hook-events.jsonl');
+    var out = path.join(__dirname, 'hook-events.jsonl');
     fs.appendFileSync(out, line + '\n');
   } catch(e) {}
 });
 "#
 }
 
-#[tauri::command]
-pub fn cmd_setup_hooks() -> Result<(), String> {
-    let claude_home = get_claude_home()?;
-    let settings_path = claude_home.join("settings.json");
+/// Events the studio subscribes to in settings.json.
+const HOOK_EVENTS: &[&str] = &[
+    "SessionStart",
+    "SessionEnd",
+    "SubagentStart",
+    "SubagentStop",
+    "Stop",
+];
 
-    // --- Migration: remove stale .claude/ide references ---
-    let old_dir = claude_home.join("ide");
-    let old_cmd = hook_command(&old_dir);
+/// Result of a dry-run hook install/removal against settings.json.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsPreview {
+    pub path: String,
+    pub changed: bool,
+    pub diff: Vec<DiffLine>,
+}
 
-    // Read existing settings (needed for migration and for setup below)
-    let mut settings: Value = if settings_path.exists() {
-        let content = std::fs::read_to_string(&settings_path)
-            .map_err(|e| format!("Failed to read settings.json: {}", e))?;
-        serde_json::from_str(&content).unwrap_or(Value::Object(serde_json::Map::new()))
-    } else {
-        Value::Object(serde_json::Map::new())
-    };
+/// True if any hook in this group runs one of `commands`.
+fn group_runs_any(group: &Value, commands: &[&str]) -> bool {
+    group
+        .get("hooks")
+        .and_then(|h| h.as_array())
+        .map(|hs| {
+            hs.iter().any(|h| {
+                h.get("command")
+                    .and_then(|c| c.as_str())
+                    .map(|c| commands.contains(&c))
+                    .unwrap_or(false)
+            })
+        })
+        .unwrap_or(false)
+}
 
-    // Remove old ide/hook.js entries from settings.json
-    let mut settings_dirty = false;
+/// Remove every hook group that runs one of `commands`, then drop event arrays
+/// (and the `hooks` object itself) left empty by the removal.
+fn strip_hook_commands(settings: &mut Value, commands: &[&str]) {
+    let mut removed_any = false;
+    let mut now_empty = false;
     if let Some(hooks) = settings.get_mut("hooks").and_then(|h| h.as_object_mut()) {
-        for event_name in &["SessionStart", "SessionEnd", "SubagentStart", "SubagentStop", "Stop"] {
+        for event_name in HOOK_EVENTS {
             if let Some(arr) = hooks.get_mut(*event_name).and_then(|v| v.as_array_mut()) {
                 let before = arr.len();
-                arr.retain(|group| {
-                    !group
-                        .get("hooks")
-                        .and_then(|h| h.as_array())
-                        .map(|hs| hs.iter().any(|h| h.get("command").and_then(|c| c.as_str()) == Some(&old_cmd)))
-                        .unwrap_or(false)
-                });
+                // Remove our hook groups, leave others intact
+                arr.retain(|group| !group_runs_any(group, commands));
                 if arr.len() != before {
-                    settings_dirty = true;
-                }
-            }
-        }
-        // Clean up empty event arrays and the hooks object itself
-        if settings_dirty {
-            let event_names: Vec<String> = hooks.keys().cloned().collect();
-            for event_name in &event_names {
-                if hooks.get(event_name).and_then(|v| v.as_array()).map(|a| a.is_empty()).unwrap_or(false) {
-                    hooks.remove(event_name);
-                }
-            }
-            if hooks.is_empty() {
-                if let Some(obj) = settings.as_object_mut() {
-                    obj.remove("hooks");
+                    removed_any = true;
+                    // Remove the event key entirely if we emptied its array
+                    if arr.is_empty() {
+                        hooks.remove(*event_name);
+                    }
                 }
             }
         }
+        now_empty = hooks.is_empty();
     }
-
-    // Delete old .claude/ide directory
-    if old_dir.exists() {
-        std::fs::remove_dir_all(&old_dir).ok();
+    if removed_any && now_empty {
+        if let Some(obj) = settings.as_object_mut() {
+            obj.remove("hooks");
+        }
     }
-    // --- End migration ---
-
-    let theassociate_dir = claude_home.join("theassociate");
-    std::fs::create_dir_all(&theassociate_dir)
-        .map_err(|e| format!("Failed to create theassociate dir: {}", e))?;
-
-    // Write the Node.js hook script
-    let hook_js_path = theassociate_dir.join("hook.js");
-    std::fs::write(&hook_js_path, hook_js_content())
-        .map_err(|e| format!("Failed to write hook.js: {}", e))?;
+}
 
-    // Touch hook-events.jsonl if it doesn't exist
-    let hook_file = theassociate_dir.join("hook-events.jsonl");
-    if !hook_file.exists() {
-        std::fs::write(&hook_file, "")
-            .map_err(|e| format!("Failed to create hook-events.jsonl: {}", e))?;
-    }
+/// Apply the studio hook install to an in-memory settings value: drop the
+/// stale `.claude/ide` entries and append our group to each event's array.
+fn apply_hook_install(settings: &mut Value, claude_home: &Path) -> Result<(), String> {
+    // --- Migration: remove stale .claude/ide references ---
+    let old_cmd = hook_command(&claude_home.join("ide"));
+    strip_hook_commands(settings, &[&old_cmd]);
 
     // Build our hook group (one entry to add to each event's array)
-    let cmd = hook_command(&theassociate_dir);
+    let cmd = hook_command(&claude_home.join("theassociate"));
     let our_group = serde_json::json!({
         "hooks": [{
             "type": "command",
@@ -134,98 +138,249 @@ pub fn cmd_setup_hooks() -> Result<(), String> {
         .as_object_mut()
         .ok_or("hooks is not an object")?;
 
-    for event_name in &[
-        "SessionStart",
-        "SessionEnd",
-        "SubagentStart",
-        "SubagentStop",
-        "Stop",
-    ] {
+    for event_name in HOOK_EVENTS {
         let event_arr = hooks
             .entry(event_name.to_string())
             .or_insert(Value::Array(vec![]));
         let arr = event_arr.as_array_mut().ok_or("hook event is not an array")?;
         // Only add if our command isn't already present
-        let already_present = arr.iter().any(|group| {
-            group
-                .get("hooks")
-                .and_then(|h| h.as_array())
-                .map(|hs| hs.iter().any(|h| h.get("command").and_then(|c| c.as_str()) == Some(&cmd)))
-                .unwrap_or(false)
-        });
-        if !already_present {
+        if !arr.iter().any(|group| group_runs_any(group, &[&cmd])) {
             arr.push(our_group.clone());
         }
     }
+    Ok(())
+}
+
+/// Apply the studio hook removal to an in-memory settings value.
+fn apply_hook_removal(settings: &mut Value, claude_home: &Path) {
+    // Commands to remove: current theassociate path and stale ide path
+    let cmd = hook_command(&claude_home.join("theassociate"));
+    let old_cmd = hook_command(&claude_home.join("ide"));
+    strip_hook_commands(settings, &[&cmd, &old_cmd]);
+}
 
-    let json_str = serde_json::to_string_pretty(&settings)
-        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
-    std::fs::write(&settings_path, json_str)
-        .map_err(|e| format!("Failed to write settings.json: {}", e))?;
+/// Hook installation state reported to the settings UI.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HookStatus {
+    /// Hooks are active for the project (or at user level when no project given)
+    pub configured: bool,
+    /// Scope the active hooks come from; project-local wins when both are present
+    pub effective_scope: Option<HookScope>,
+    /// Scope recorded in the project's `ProjectSettings`
+    pub preferred_scope: HookScope,
+    pub user_installed: bool,
+    pub project_installed: bool,
+}
 
-    Ok(())
+/// The settings file hooks live in for `scope`.
+fn scope_settings_path(
+    claude_home: &Path,
+    scope: HookScope,
+    project_path: Option<&str>,
+) -> Result<PathBuf, String> {
+    match scope {
+        HookScope::User => Ok(claude_home.join("settings.json")),
+        HookScope::Project => {
+            let project = project_path.ok_or("Project-scoped hooks require a project path")?;
+            let dir = PathBuf::from(project);
+            if !dir.is_dir() {
+                return Err(format!("Directory does not exist: {}", project));
+            }
+            Ok(dir.join(".claude").join("settings.local.json"))
+        }
+    }
 }
 
-#[tauri::command]
-pub fn cmd_remove_hooks() -> Result<(), String> {
-    let claude_home = get_claude_home()?;
-    let theassociate_dir = claude_home.join("theassociate");
+/// Where backups of the scope's settings file go: next to `settings.json` for
+/// user scope, and under `theassociate/backups/{encoded}/` for project scope,
+/// so no `.bak` files land in the client repo's `.claude/` directory.
+fn scope_backup_dir(claude_home: &Path, scope: HookScope, project_path: Option<&str>) -> PathBuf {
+    match (scope, project_path) {
+        (HookScope::Project, Some(project)) => claude_home
+            .join("theassociate")
+            .join("backups")
+            .join(encode_project_path(Path::new(project))),
+        _ => claude_home.to_path_buf(),
+    }
+}
+
+/// Scope recorded for `project_path`, or user-level when there is no project.
+fn preferred_scope(project_path: Option<&str>) -> Result<HookScope, String> {
+    match project_path {
+        Some(p) => Ok(load_project_settings(p)?.hook_scope.unwrap_or_default()),
+        None => Ok(HookScope::User),
+    }
+}
+
+/// Write hook.js, create hook-events.jsonl and delete the stale `.claude/ide` dir.
+fn ensure_hook_files(claude_home: &Path) -> Result<(), String> {
+    // Delete old .claude/ide directory
     let old_dir = claude_home.join("ide");
-    let settings_path = claude_home.join("settings.json");
+    if old_dir.exists() {
+        std::fs::remove_dir_all(&old_dir).ok();
+    }
 
+    let theassociate_dir = claude_home.join("theassociate");
+    std::fs::create_dir_all(&theassociate_dir)
+        .map_err(|e| format!("Failed to create theassociate dir: {}", e))?;
+
+    // Write the Node.js hook script
+    let hook_js_path = theassociate_dir.join("hook.js");
+    std::fs::write(&hook_js_path, hook_js_content())
+        .map_err(|e| format!("Failed to write hook.js: {}", e))?;
+
+    // Touch hook-events.jsonl if it doesn't exist
+    let hook_file = theassociate_dir.join("hook-events.jsonl");
+    if !hook_file.exists() {
+        std::fs::write(&hook_file, "")
+            .map_err(|e| format!("Failed to create hook-events.jsonl: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Install the studio hooks into `settings_path`, backing it up to `backup_dir`.
+fn install_hooks_at(settings_path: &Path, backup_dir: &Path, claude_home: &Path) -> Result<(), String> {
+    let name = settings_path.file_name().and_then(|n| n.to_str()).unwrap_or("settings.json");
+    // Parse before touching anything on disk — an unreadable settings file aborts setup
+    let mut settings = read_settings(settings_path).map_err(|e| e.to_string())?;
+    apply_hook_install(&mut settings, claude_home)?;
+    ensure_hook_files(claude_home)?;
+    // No-op when the hooks are already installed, so launches don't churn backups
+    write_settings(settings_path, &settings, backup_dir)
+        .map_err(|e| format!("Failed to write {}: {}", name, e))?;
+    Ok(())
+}
+
+/// Remove the studio hooks from `settings_path`, if the file exists, backing it
+/// up to `backup_dir`.
+fn remove_hooks_at(settings_path: &Path, backup_dir: &Path, claude_home: &Path) -> Result<(), String> {
     if !settings_path.exists() {
-        // Still clean up the old directory if present
-        if old_dir.exists() {
-            std::fs::remove_dir_all(&old_dir).ok();
-        }
         return Ok(());
     }
+    let name = settings_path.file_name().and_then(|n| n.to_str()).unwrap_or("settings.json");
+    let mut settings = read_settings(settings_path).map_err(|e| e.to_string())?;
+    apply_hook_removal(&mut settings, claude_home);
+    write_settings(settings_path, &settings, backup_dir)
+        .map_err(|e| format!("Failed to write {}: {}", name, e))?;
+    Ok(())
+}
 
-    let content = std::fs::read_to_string(&settings_path)
-        .map_err(|e| format!("Failed to read settings.json: {}", e))?;
-    let mut settings: Value =
-        serde_json::from_str(&content).unwrap_or(Value::Object(serde_json::Map::new()));
+/// True if `settings_path` registers the current hook command for SessionStart.
+fn hooks_present_at(settings_path: &Path, claude_home: &Path) -> Result<bool, String> {
+    if !settings_path.exists() {
+        return Ok(false);
+    }
+    let settings = read_settings(settings_path).map_err(|e| e.to_string())?;
+    let cmd = hook_command(&claude_home.join("theassociate"));
+    Ok(settings
+        .get("hooks")
+        .and_then(|h| h.get("SessionStart"))
+        .and_then(|arr| arr.as_array())
+        .map(|groups| groups.iter().any(|group| group_runs_any(group, &[&cmd])))
+        .unwrap_or(false))
+}
 
-    // Commands to remove: current theassociate path and stale ide path
-    let cmd = hook_command(&theassociate_dir);
-    let old_cmd = hook_command(&old_dir);
+/// Scope recorded for a known project; unreadable settings count as the default.
+fn project_scope(project_path: &str) -> HookScope {
+    load_project_settings(project_path)
+        .ok()
+        .and_then(|s| s.hook_scope)
+        .unwrap_or_default()
+}
 
-    if let Some(hooks) = settings.get_mut("hooks").and_then(|h| h.as_object_mut()) {
-        for event_name in &[
-            "SessionStart",
-            "SessionEnd",
-            "SubagentStart",
-            "SubagentStop",
-            "Stop",
-        ] {
-            if let Some(arr) = hooks.get_mut(*event_name).and_then(|v| v.as_array_mut()) {
-                // Remove our hook groups (both theassociate and stale ide), leave others intact
-                arr.retain(|group| {
-                    let command = group
-                        .get("hooks")
-                        .and_then(|h| h.as_array())
-                        .and_then(|hs| hs.iter().find_map(|h| h.get("command").and_then(|c| c.as_str()).map(|s| s.to_string())));
-                    !matches!(command.as_deref(), Some(c) if c == cmd || c == old_cmd)
-                });
-            }
-            // Remove the event key entirely if its array is now empty
-            if hooks.get(*event_name).and_then(|v| v.as_array()).map(|a| a.is_empty()).unwrap_or(false) {
-                hooks.remove(*event_name);
-            }
+/// Paths of the projects recently opened in the studio: the project
+/// switcher's `recentProjectIds` in the app's `settings.json` store, matched
+/// against the discovered Claude projects.
+fn opened_project_paths(app: &AppHandle, claude_home: &Path) -> Vec<String> {
+    let ids: Vec<String> = app
+        .path()
+        .app_data_dir()
+        .ok()
+        .and_then(|dir| std::fs::read_to_string(dir.join("settings.json")).ok())
+        .and_then(|content| serde_json::from_str::<Value>(&content).ok())
+        .and_then(|store| serde_json::from_value(store.get("recentProjectIds")?.clone()).ok())
+        .unwrap_or_default();
+    if ids.is_empty() {
+        return vec![];
+    }
+    let projects = discover_projects(claude_home).unwrap_or_default();
+    ids.iter()
+        .filter_map(|id| projects.iter().find(|p| &p.id == id))
+        .map(|p| p.path.clone())
+        .collect()
+}
+
+/// Whether a project opened in the studio relies on the user-level hooks.
+/// Every Claude project the machine has seen would almost always include one
+/// without an explicit scope, so only the studio's own projects count. New
+/// projects start on user scope, so before any is opened they are wanted.
+fn user_scope_in_use(app: &AppHandle, claude_home: &Path) -> bool {
+    let opened = opened_project_paths(app, claude_home);
+    opened.is_empty() || opened.iter().any(|p| project_scope(p) == HookScope::User)
+}
+
+/// Launch-time install of the user-level hooks. Skipped when every project
+/// opened in the studio uses project scope, so `settings.json` is left
+/// untouched where user-level hooks aren't allowed.
+pub fn auto_setup_hooks(app: &AppHandle) -> Result<(), String> {
+    let claude_home = claude_home()?;
+    if !user_scope_in_use(app, &claude_home) {
+        // Keep hook.js current for the projects that install it themselves
+        return ensure_hook_files(&claude_home);
+    }
+    install_hooks_at(&claude_home.join("settings.json"), &claude_home, &claude_home)
+}
+
+/// Uninstall path: strip the hooks from `<claude_home>/settings.json` and from the
+/// `settings.local.json` of every known project that uses project scope.
+pub fn remove_all_hooks(claude_home: &Path) -> Result<(), String> {
+    remove_hooks_at(&claude_home.join("settings.json"), claude_home, claude_home)?;
+
+    let projects = discover_projects(claude_home).map_err(|e| e.to_string())?;
+    for project in projects {
+        if project_scope(&project.path) != HookScope::Project {
+            continue;
         }
-        if hooks.is_empty() {
-            if let Some(obj) = settings.as_object_mut() {
-                obj.remove("hooks");
+        if let Ok(local) = scope_settings_path(claude_home, HookScope::Project, Some(&project.path)) {
+            let backups = scope_backup_dir(claude_home, HookScope::Project, Some(&project.path));
+            if let Err(e) = remove_hooks_at(&local, &backups, claude_home) {
+                eprintln!("[ide] hook removal failed for {}: {}", project.path, e);
             }
         }
     }
 
-    let json_str = serde_json::to_string_pretty(&settings)
-        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
-    std::fs::write(&settings_path, json_str)
-        .map_err(|e| format!("Failed to write settings.json: {}", e))?;
+    // Remove old .claude/ide directory if still present
+    let old_dir = claude_home.join("ide");
+    if old_dir.exists() {
+        std::fs::remove_dir_all(&old_dir).ok();
+    }
+    Ok(())
+}
+
+/// Install the hooks at the project's recorded scope, or at user level when
+/// `project_path` is omitted.
+#[tauri::command]
+pub fn cmd_setup_hooks(project_path: Option<String>) -> Result<(), String> {
+    let claude_home = claude_home()?;
+    let scope = preferred_scope(project_path.as_deref())?;
+    let settings_path = scope_settings_path(&claude_home, scope, project_path.as_deref())?;
+    let backups = scope_backup_dir(&claude_home, scope, project_path.as_deref());
+    install_hooks_at(&settings_path, &backups, &claude_home)
+}
+
+/// Remove the hooks from the project's recorded scope, or from user level when
+/// `project_path` is omitted.
+#[tauri::command]
+pub fn cmd_remove_hooks(project_path: Option<String>) -> Result<(), String> {
+    let claude_home = claude_home()?;
+    let scope = preferred_scope(project_path.as_deref())?;
+    let settings_path = scope_settings_path(&claude_home, scope, project_path.as_deref())?;
+    let backups = scope_backup_dir(&claude_home, scope, project_path.as_deref());
+    remove_hooks_at(&settings_path, &backups, &claude_home)?;
 
     // Remove old .claude/ide directory if still present
+    let old_dir = claude_home.join("ide");
     if old_dir.exists() {
         std::fs::remove_dir_all(&old_dir).ok();
     }
@@ -233,9 +388,67 @@ pub fn cmd_remove_hooks() -> Result<(), String> {
     Ok(())
 }
 
+/// Record `scope` for the project and move its hooks there. Switching to user
+/// scope strips the project-local entries. Switching to project scope strips
+/// the user-level hooks from `~/.claude/settings.json`, since a policy against
+/// them is the reason to pick project scope; launch-time setup puts them back
+/// only while another project opened in the studio still uses user scope.
+#[tauri::command]
+pub fn cmd_set_hook_scope(project_path: String, scope: HookScope) -> Result<(), String> {
+    let claude_home = claude_home()?;
+    let settings_path = scope_settings_path(&claude_home, scope, Some(&project_path))?;
+    let backups = scope_backup_dir(&claude_home, scope, Some(&project_path));
+    install_hooks_at(&settings_path, &backups, &claude_home)?;
+
+    match scope {
+        HookScope::User => {
+            let local = scope_settings_path(&claude_home, HookScope::Project, Some(&project_path))?;
+            let backups = scope_backup_dir(&claude_home, HookScope::Project, Some(&project_path));
+            remove_hooks_at(&local, &backups, &claude_home)?;
+        }
+        HookScope::Project => {
+            remove_hooks_at(&claude_home.join("settings.json"), &claude_home, &claude_home)?;
+        }
+    }
+
+    let mut settings = load_project_settings(&project_path)?;
+    settings.hook_scope = Some(scope);
+    save_project_settings(&project_path, &settings)
+}
+
+/// Dry run of `cmd_setup_hooks` (or `cmd_remove_hooks` when `remove` is set):
+/// returns the diff that would be applied to the scope's settings file without writing it.
+#[tauri::command]
+pub fn cmd_preview_hooks(remove: bool, project_path: Option<String>) -> Result<SettingsPreview, String> {
+    let claude_home = claude_home()?;
+    let scope = preferred_scope(project_path.as_deref())?;
+    let settings_path = scope_settings_path(&claude_home, scope, project_path.as_deref())?;
+
+    let mut settings = read_settings(&settings_path).map_err(|e| e.to_string())?;
+    if remove {
+        if !settings_path.exists() {
+            return Ok(SettingsPreview {
+                path: settings_path.to_string_lossy().replace('\\', "/"),
+                changed: false,
+                diff: vec![],
+            });
+        }
+        apply_hook_removal(&mut settings, &claude_home);
+    } else {
+        apply_hook_install(&mut settings, &claude_home)?;
+    }
+
+    let diff = diff_settings(&settings_path, &settings).map_err(|e| e.to_string())?;
+    Ok(SettingsPreview {
+        path: settings_path.to_string_lossy().replace('\\', "/"),
+        changed: !diff.is_empty(),
+        diff,
+    })
+}
+
 #[tauri::command]
 pub fn cmd_get_active_sessions() -> Result<Vec<ActiveSession>, String> {
-    let claude_home = get_claude_home()?;
+    let claude_home = claude_home()?;
     let hook_file = claude_home.join("theassociate").join("hook-events.jsonl");
 
     // Truncate hook-events.jsonl if it exceeds 1000 lines to prevent unbounded growth
@@ -255,31 +468,32 @@ pub fn cmd_get_active_sessions() -> Result<Vec<ActiveSession>, String> {
     Ok(sessions_map.into_values().collect())
 }
 
+/// Report where the hooks are installed. With `project_path`, the effective
+/// scope is the project-local file if it has them, else the user-level file.
 #[tauri::command]
-pub fn cmd_hooks_configured() -> Result<bool, String> {
-    let claude_home = get_claude_home()?;
-    let ide_dir = claude_home.join("theassociate");
-    let settings_path = claude_home.join("settings.json");
-    if !settings_path.exists() {
-        return Ok(false);
-    }
-    let content = std::fs::read_to_string(&settings_path)
-        .map_err(|e| format!("Failed to read settings.json: {}\n", e))?;
-    let settings: Value = serde_json::from_str(&content).unwrap_or(Value::Null);
-    let cmd = hook_command(&ide_dir);
-    let configured = settings
-        .get("hooks")
-        .and_then(|h| h.get("SessionStart"))
-        .and_then(|arr| arr.as_array())
-        .map(|groups| {
-            groups.iter().any(|group| {
-                group
-                    .get("hooks")
-                    .and_then(|h| h.as_array())
-                    .map(|hs| hs.iter().any(|h| h.get("command").and_then(|c| c.as_str()) == Some(&cmd)))
-                    .unwrap_or(false)
-            })
-        })
-        .unwrap_or(false);
-    Ok(configured)
+pub fn cmd_hooks_configured(project_path: Option<String>) -> Result<HookStatus, String> {
+    let claude_home = claude_home()?;
+    let preferred_scope = preferred_scope(project_path.as_deref())?;
+    let user_installed = hooks_present_at(&claude_home.join("settings.json"), &claude_home)?;
+    let project_installed = match project_path.as_deref() {
+        Some(p) => {
+            let local = scope_settings_path(&claude_home, HookScope::Project, Some(p))?;
+            hooks_present_at(&local, &claude_home)?
+        }
+        None => false,
+    };
+    let effective_scope = if project_installed {
+        Some(HookScope::Project)
+    } else if user_installed {
+        Some(HookScope::User)
+    } else {
+        None
+    };
+    Ok(HookStatus {
+        configured: effective_scope.is_some(),
+        effective_scope,
+        preferred_scope,
+        user_installed,
+        project_installed,
+    })
 }
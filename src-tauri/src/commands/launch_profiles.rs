@@ -0,0 +1,42 @@
+use crate::paths::theassociate_home;
+use crate::data::launch_profiles;
+use crate::models::launch_profile::{LaunchProfile, LaunchProfileScope};
+
+/// Global launch profiles, merged with the project's when `project_path` is given.
+#[tauri::command]
+pub async fn cmd_list_launch_profiles(
+    project_path: Option<String>,
+) -> Result<Vec<LaunchProfile>, String> {
+    Ok(launch_profiles::load_launch_profiles(
+        &theassociate_home()?,
+        project_path.as_deref(),
+    ))
+}
+
+/// Replace all profiles stored at `scope` with `profiles`.
+#[tauri::command]
+pub async fn cmd_save_launch_profiles(
+    scope: LaunchProfileScope,
+    project_path: Option<String>,
+    profiles: Vec<LaunchProfile>,
+) -> Result<(), String> {
+    let mut seen = std::collections::HashSet::new();
+    for profile in &profiles {
+        if profile.id.trim().is_empty() || profile.name.trim().is_empty() {
+            return Err("Launch profiles need an id and a name".to_string());
+        }
+        if !seen.insert(profile.id.as_str()) {
+            return Err(format!("Duplicate launch profile id: {}", profile.id));
+        }
+    }
+    let path = launch_profiles::profiles_path(&theassociate_home()?, project_path.as_deref(), scope)
+        .ok_or("Project-scoped launch profiles need a project path")?;
+    let profiles: Vec<LaunchProfile> = profiles
+        .into_iter()
+        .map(|mut p| {
+            p.scope = scope;
+            p
+        })
+        .collect();
+    launch_profiles::save_launch_profiles(&path, &profiles).map_err(|e| e.to_string())
+}
@@ -1,20 +1,29 @@
 pub mod app_info;
+pub mod auto_resume;
+pub mod broadcast;
 pub mod claude_config;
+pub mod claude_homes;
 pub mod files;
 pub mod git;
 pub mod hooks;
 pub mod inbox;
 pub mod integrations;
 pub mod issues;
+pub mod launch_profiles;
 pub mod notes;
 pub mod plan_links;
 pub mod plans;
 pub mod projects;
+pub mod prompt_queue;
 pub mod pty;
+pub mod recordings;
 pub mod remote_run;
+pub mod resource_limits;
 pub mod sessions;
 pub mod summaries;
 pub mod tasks;
 pub mod teams;
+pub mod terminal_profiles;
+pub mod trigger_rules;
 pub mod todos;
 pub mod workflows;
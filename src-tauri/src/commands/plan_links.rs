@@ -1,8 +1,8 @@
 use std::collections::HashMap;
-use crate::commands::projects::get_theassociate_home;
+use crate::paths::theassociate_home;
 
 fn get_plan_links_path(project_dir: &str) -> Result<std::path::PathBuf, String> {
-    let dir = get_theassociate_home()?.join("projects").join(project_dir);
+    let dir = theassociate_home()?.join("projects").join(project_dir);
     std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
     Ok(dir.join("plan-links.json"))
 }
@@ -1,26 +1,16 @@
 use crate::data::plans::load_plans;
 use crate::models::plan::PlanFile;
-use std::path::PathBuf;
-
-fn get_claude_home() -> Result<PathBuf, String> {
-    let home = std::env::var("USERPROFILE")
-        .or_else(|_| std::env::var("HOME"))
-        .map_err(|_| "Neither USERPROFILE nor HOME environment variable is set".to_string())?;
-    if home.is_empty() {
-        return Err("Home directory environment variable is empty".to_string());
-    }
-    Ok(PathBuf::from(home).join(".claude"))
-}
+use crate::paths::claude_home;
 
 #[tauri::command]
 pub async fn cmd_load_plans() -> Result<Vec<PlanFile>, String> {
-    let claude_home = get_claude_home()?;
+    let claude_home = claude_home()?;
     load_plans(&claude_home).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
 pub async fn cmd_read_plan(filename: String) -> Result<String, String> {
-    let claude_home = get_claude_home()?;
+    let claude_home = claude_home()?;
     let plans_dir = claude_home.join("plans");
     let path = plans_dir.join(&filename);
     // Guard against path traversal
@@ -32,7 +22,7 @@ pub async fn cmd_read_plan(filename: String) -> Result<String, String> {
 
 #[tauri::command]
 pub async fn cmd_save_plan(filename: String, content: String) -> Result<(), String> {
-    let claude_home = get_claude_home()?;
+    let claude_home = claude_home()?;
     let plans_dir = claude_home.join("plans");
     let path = plans_dir.join(&filename);
     // Guard against path traversal
@@ -3,7 +3,11 @@ use std::path::PathBuf;
 use serde::{Deserialize, Serialize};
 
 use crate::data::path_encoding::encode_project_path;
-use crate::data::projects::{discover_orphaned_projects, discover_projects, ProjectInfo};
+use crate::data::projects::{
+    discover_orphaned_projects, discover_projects, migrate_legacy_data_dirs, ProjectInfo,
+};
+use crate::models::hook_scope::HookScope;
+use crate::paths::{claude_home, home_dir, theassociate_home};
 
 // ---- Per-project IDE settings ----
 
@@ -13,6 +17,7 @@ pub struct ProjectSettings {
     pub docs_folder: Option<String>,
     pub show_hidden_files: Option<bool>,   // None = use global default
     pub issue_filters: Option<IssueFiltersSettings>,
+    pub hook_scope: Option<HookScope>,     // None = user-level (~/.claude/settings.json)
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -27,44 +32,20 @@ pub struct IssueFiltersSettings {
     pub pr_state: Option<String>,
 }
 
-fn get_home_dir_str() -> Result<String, String> {
-    let home = std::env::var("USERPROFILE")
-        .or_else(|_| std::env::var("HOME"))
-        .map_err(|_| "Neither USERPROFILE nor HOME environment variable is set".to_string())?;
-    if home.is_empty() {
-        return Err("Home directory environment variable is empty".to_string());
-    }
-    Ok(home.replace('\\', "/"))
-}
-
-fn get_claude_home() -> Result<PathBuf, String> {
-    let home = std::env::var("USERPROFILE")
-        .or_else(|_| std::env::var("HOME"))
-        .map_err(|_| "Neither USERPROFILE nor HOME environment variable is set".to_string())?;
-    if home.is_empty() {
-        return Err("Home directory environment variable is empty".to_string());
-    }
-    Ok(PathBuf::from(home).join(".claude"))
-}
-
-pub(crate) fn get_theassociate_home() -> Result<PathBuf, String> {
-    Ok(get_claude_home()?.join("theassociate"))
-}
-
 #[tauri::command]
 pub async fn cmd_get_home_dir() -> Result<String, String> {
-    get_home_dir_str()
+    Ok(home_dir()?.to_string_lossy().replace('\\', "/"))
 }
 
 #[tauri::command]
 pub async fn cmd_list_projects() -> Result<Vec<ProjectInfo>, String> {
-    let claude_home = get_claude_home()?;
+    let claude_home = claude_home()?;
     discover_projects(&claude_home).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
 pub async fn cmd_list_orphaned_projects() -> Result<Vec<ProjectInfo>, String> {
-    let claude_home = get_claude_home()?;
+    let claude_home = claude_home()?;
     discover_orphaned_projects(&claude_home).map_err(|e| e.to_string())
 }
 
@@ -109,7 +90,7 @@ pub async fn cmd_write_file(path: String, content: String) -> Result<(), String>
 
 #[tauri::command]
 pub async fn cmd_delete_project(id: String) -> Result<(), String> {
-    let claude_home = get_claude_home()?;
+    let claude_home = claude_home()?;
     let projects_dir = claude_home.join("projects");
     let project_dir = projects_dir.join(&id);
     // Canonicalize to resolve symlinks and .. components, then verify containment
@@ -137,6 +118,7 @@ pub async fn cmd_run_claude_init(project_path: String) -> Result<String, String>
             .args(["-p", "/init", "--dangerously-skip-permissions"])
             .current_dir(&dir)
             .env_remove("CLAUDECODE")
+            .envs(crate::paths::claude_config_env())
             .output()
     })
     .await
@@ -168,7 +150,7 @@ pub async fn cmd_create_project(project_path: String) -> Result<ProjectInfo, Str
         return Err(format!("Directory does not exist: {}", project_path));
     }
 
-    let claude_home = get_claude_home()?;
+    let claude_home = claude_home()?;
     let encoded = encode_project_path(&PathBuf::from(&project_path));
     let project_dir = claude_home.join("projects").join(&encoded);
 
@@ -217,10 +199,19 @@ pub async fn cmd_create_project(project_path: String) -> Result<ProjectInfo, Str
     })
 }
 
-#[tauri::command]
-pub async fn cmd_get_project_settings(project_path: String) -> Result<ProjectSettings, String> {
-    let encoded = encode_project_path(&PathBuf::from(&project_path));
-    let settings_path = get_theassociate_home()?
+/// Launch path: move per-project data saved under the legacy path encoding to
+/// its current dir (see `migrate_legacy_data_dirs`).
+pub fn migrate_project_data_dirs() -> Result<(), String> {
+    let migrated = migrate_legacy_data_dirs(&claude_home()?).map_err(|e| e.to_string())?;
+    if migrated > 0 {
+        eprintln!("[ide] migrated {} project data dir(s) to the current path encoding", migrated);
+    }
+    Ok(())
+}
+
+pub(crate) fn load_project_settings(project_path: &str) -> Result<ProjectSettings, String> {
+    let encoded = encode_project_path(&PathBuf::from(project_path));
+    let settings_path = theassociate_home()?
         .join("projects")
         .join(&encoded)
         .join("ide-settings.json");
@@ -233,16 +224,25 @@ pub async fn cmd_get_project_settings(project_path: String) -> Result<ProjectSet
     serde_json::from_str(&content).map_err(|e| e.to_string())
 }
 
-#[tauri::command]
-pub async fn cmd_set_project_settings(project_path: String, settings: ProjectSettings) -> Result<(), String> {
-    let encoded = encode_project_path(&PathBuf::from(&project_path));
-    let project_dir = get_theassociate_home()?.join("projects").join(&encoded);
+pub(crate) fn save_project_settings(project_path: &str, settings: &ProjectSettings) -> Result<(), String> {
+    let encoded = encode_project_path(&PathBuf::from(project_path));
+    let project_dir = theassociate_home()?.join("projects").join(&encoded);
     std::fs::create_dir_all(&project_dir).map_err(|e| e.to_string())?;
     let settings_path = project_dir.join("ide-settings.json");
-    let content = serde_json::to_string_pretty(&settings).map_err(|e| e.to_string())?;
+    let content = serde_json::to_string_pretty(settings).map_err(|e| e.to_string())?;
     std::fs::write(&settings_path, content).map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub async fn cmd_get_project_settings(project_path: String) -> Result<ProjectSettings, String> {
+    load_project_settings(&project_path)
+}
+
+#[tauri::command]
+pub async fn cmd_set_project_settings(project_path: String, settings: ProjectSettings) -> Result<(), String> {
+    save_project_settings(&project_path, &settings)
+}
+
 /// Scan project root for common documentation folder names.
 /// Returns the first match in priority order: docs, doc, documents, documentation.
 #[tauri::command]
@@ -308,6 +308,7 @@ pub async fn cmd_run_docs_index_gen(project_path: String, docs_folder: String) -
             .args(["-p", &prompt, "--dangerously-skip-permissions"])
             .current_dir(&dir)
             .env_remove("CLAUDECODE")
+            .envs(crate::paths::claude_config_env())
             .output()
     })
     .await
@@ -345,6 +346,7 @@ pub async fn cmd_run_readme_gen(project_path: String) -> Result<String, String>
             ])
             .current_dir(&dir)
             .env_remove("CLAUDECODE")
+            .envs(crate::paths::claude_config_env())
             .output()
     })
     .await
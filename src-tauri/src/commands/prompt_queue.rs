@@ -0,0 +1,209 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::commands::pty::{tab_for_claude_session, write_to_session, PtyState};
+use crate::models::hook_event::HookEvent;
+use crate::pty::prompt_queue::{prompt_input, PromptCondition, PromptQueue, QueuedPrompt};
+
+/// Prompt queues keyed by PTY tab id.
+pub struct PromptQueueState(pub Arc<Mutex<HashMap<String, PromptQueue>>>);
+
+impl PromptQueueState {
+    pub fn new() -> Self {
+        Self(Arc::new(Mutex::new(HashMap::new())))
+    }
+}
+
+/// Pause between typing a prompt and pressing Enter, so the TUI sees two inputs.
+const SUBMIT_DELAY: Duration = Duration::from_millis(150);
+
+/// Type `text` into the tab's PTY and submit it. Blocks for `SUBMIT_DELAY`.
+pub(crate) fn deliver_prompt(app: &AppHandle, tab_id: &str, text: &str) -> Result<(), String> {
+    let sessions = &app.state::<PtyState>().0;
+    write_to_session(sessions, tab_id, &prompt_input(text))?;
+    std::thread::sleep(SUBMIT_DELAY);
+    write_to_session(sessions, tab_id, "\r")
+}
+
+/// Deliver the queue's next eligible prompt and record the outcome. A prompt
+/// that can't be written goes back to the head of the queue.
+fn send_next(app: &AppHandle, tab_id: &str, queue: &mut PromptQueue) -> Result<Option<QueuedPrompt>, String> {
+    let Some(prompt) = queue.take_next() else {
+        return Ok(None);
+    };
+    match deliver_prompt(app, tab_id, &prompt.text) {
+        Ok(()) => {
+            queue.sent(&prompt);
+            Ok(Some(prompt))
+        }
+        Err(e) => {
+            queue.failed(prompt, &e);
+            Err(format!("Failed to send prompt: {}", e))
+        }
+    }
+}
+
+fn notify_changed(app: &AppHandle, tab_id: &str, queue: &PromptQueue) {
+    let _ = app.emit(
+        "prompt-queue-changed",
+        serde_json::json!({ "tab_id": tab_id, "queue": queue }),
+    );
+}
+
+/// Called by the hook watcher for every `Stop` event: remember Claude's last
+/// message and, unless the queue is paused, send the next eligible prompt to
+/// the PTY tab bound to that session.
+pub fn on_session_stop(app: &AppHandle, event: &HookEvent) {
+    let Some(tab_id) = tab_for_claude_session(&app.state::<PtyState>().0, &event.session_id) else {
+        return;
+    };
+    let state = app.state::<PromptQueueState>();
+    let Ok(mut queues) = state.0.lock() else {
+        return;
+    };
+    let Some(queue) = queues.get_mut(&tab_id) else {
+        return;
+    };
+    queue.last_message = event.last_assistant_message.clone();
+    if queue.paused || queue.items.is_empty() {
+        return;
+    }
+    if let Err(e) = send_next(app, &tab_id, queue) {
+        eprintln!("[prompt-queue] {}: {}", tab_id, e);
+    }
+    notify_changed(app, &tab_id, queue);
+}
+
+/// Drop a tab's queue and history once its PTY is gone. Called from
+/// `pty_kill` and when a session exits without being resumed.
+pub(crate) fn clear_queue(app: &AppHandle, tab_id: &str) {
+    let state = app.state::<PromptQueueState>();
+    let Ok(mut queues) = state.0.lock() else {
+        return;
+    };
+    if queues.remove(tab_id).is_some() {
+        notify_changed(app, tab_id, &PromptQueue::default());
+    }
+}
+
+/// Run `f` on the tab's queue (created on first use), then broadcast the result.
+fn with_queue<T>(
+    app: &AppHandle,
+    state: &PromptQueueState,
+    tab_id: &str,
+    f: impl FnOnce(&mut PromptQueue) -> Result<T, String>,
+) -> Result<T, String> {
+    let mut queues = state.0.lock().map_err(|e| e.to_string())?;
+    let queue = queues.entry(tab_id.to_string()).or_default();
+    let out = f(queue)?;
+    notify_changed(app, tab_id, queue);
+    Ok(out)
+}
+
+#[tauri::command]
+pub async fn cmd_get_prompt_queue(
+    tab_id: String,
+    state: State<'_, PromptQueueState>,
+) -> Result<PromptQueue, String> {
+    let queues = state.0.lock().map_err(|e| e.to_string())?;
+    Ok(queues.get(&tab_id).cloned().unwrap_or_default())
+}
+
+#[tauri::command]
+pub async fn cmd_enqueue_prompt(
+    tab_id: String,
+    text: String,
+    condition: Option<PromptCondition>,
+    app_handle: AppHandle,
+    state: State<'_, PromptQueueState>,
+) -> Result<QueuedPrompt, String> {
+    if text.trim().is_empty() {
+        return Err("Prompt is empty".to_string());
+    }
+    let prompt = QueuedPrompt {
+        id: format!("prompt-{}", chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()),
+        text,
+        condition,
+    };
+    with_queue(&app_handle, &state, &tab_id, |queue| {
+        queue.items.push(prompt.clone());
+        Ok(prompt)
+    })
+}
+
+/// Replace a queued prompt's text and condition.
+#[tauri::command]
+pub async fn cmd_update_queued_prompt(
+    tab_id: String,
+    prompt: QueuedPrompt,
+    app_handle: AppHandle,
+    state: State<'_, PromptQueueState>,
+) -> Result<(), String> {
+    with_queue(&app_handle, &state, &tab_id, |queue| {
+        let existing = queue
+            .items
+            .iter_mut()
+            .find(|p| p.id == prompt.id)
+            .ok_or_else(|| format!("Queued prompt not found: {}", prompt.id))?;
+        *existing = prompt;
+        Ok(())
+    })
+}
+
+#[tauri::command]
+pub async fn cmd_remove_queued_prompt(
+    tab_id: String,
+    prompt_id: String,
+    app_handle: AppHandle,
+    state: State<'_, PromptQueueState>,
+) -> Result<(), String> {
+    with_queue(&app_handle, &state, &tab_id, |queue| {
+        queue.items.retain(|p| p.id != prompt_id);
+        Ok(())
+    })
+}
+
+#[tauri::command]
+pub async fn cmd_reorder_prompt_queue(
+    tab_id: String,
+    prompt_ids: Vec<String>,
+    app_handle: AppHandle,
+    state: State<'_, PromptQueueState>,
+) -> Result<(), String> {
+    with_queue(&app_handle, &state, &tab_id, |queue| {
+        queue.reorder(&prompt_ids);
+        Ok(())
+    })
+}
+
+#[tauri::command]
+pub async fn cmd_set_prompt_queue_paused(
+    tab_id: String,
+    paused: bool,
+    app_handle: AppHandle,
+    state: State<'_, PromptQueueState>,
+) -> Result<(), String> {
+    with_queue(&app_handle, &state, &tab_id, |queue| {
+        queue.paused = paused;
+        Ok(())
+    })
+}
+
+/// Send the next eligible prompt now, without waiting for a `Stop` (and even
+/// while paused). Returns the prompt sent, if any.
+#[tauri::command]
+pub async fn cmd_send_next_prompt(
+    tab_id: String,
+    app_handle: AppHandle,
+    state: State<'_, PromptQueueState>,
+) -> Result<Option<QueuedPrompt>, String> {
+    let mut queues = state.0.lock().map_err(|e| e.to_string())?;
+    let queue = queues.entry(tab_id.clone()).or_default();
+    let result = send_next(&app_handle, &tab_id, queue);
+    // Also after a failure, which put the prompt back and logged it
+    notify_changed(&app_handle, &tab_id, queue);
+    result
+}
@@ -1,8 +1,26 @@
 use std::collections::HashMap;
 use std::io::{Read, Write};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use portable_pty::{CommandBuilder, PtySize, native_pty_system};
-use tauri::{AppHandle, Emitter, State};
+use serde::{Deserialize, Serialize};
+use tauri::ipc::{Channel, InvokeResponseBody};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::paths::theassociate_home;
+use crate::commands::prompt_queue::clear_queue;
+use crate::commands::recordings::new_recording_path;
+use crate::data::auto_resume::load_auto_resume_policy;
+use crate::data::launch_profiles::{launch_args, load_launch_profiles};
+use crate::data::terminal_profiles::load_terminal_profiles;
+use crate::data::trigger_rules::{builtin_rules, load_trigger_rules, QUESTION_EVENT};
+use crate::models::terminal_profile::SessionKind;
+use crate::pty::exit::{take_retry, ExitReason, PtyExit, ResumeAttempt, EXIT_TAIL_LINES};
+use crate::pty::recording::Recorder;
+use crate::pty::screen::{ScreenModel, ScreenSnapshot};
+use crate::pty::scrollback::ScrollbackMatch;
+use crate::pty::stream::{OutputSnapshot, OutputStream, FRAME_INTERVAL};
+use crate::pty::triggers::{FiredAction, Firing, TriggerEngine};
 
 pub struct PtyState(pub Arc<Mutex<HashMap<String, PtySession>>>);
 
@@ -10,35 +28,226 @@ pub struct PtySession {
     pub writer: Box<dyn Write + Send>,
     pub master: Box<dyn portable_pty::MasterPty + Send>,
     pub child: Box<dyn portable_pty::Child + Send + Sync>,
+    /// OS process id of `child`, for resource monitoring
+    pub pid: Option<u32>,
+    /// Scrollback plus batched delivery to the attached frontend channel
+    pub output: Arc<OutputStream>,
+    /// Headless emulator of what the terminal currently shows
+    pub screen: Arc<Mutex<ScreenModel>>,
+    /// Active asciicast recording, if any (see `pty_start_recording`)
+    pub recorder: Arc<Mutex<Option<Recorder>>>,
+    pub cwd: String,
+    pub kind: SessionKind,
+    /// Launch or terminal profile the session was started from, for recording titles
+    pub profile_id: Option<String>,
+    /// Claude session running in this PTY, once known (see `pty_bind_session`)
+    pub claude_session_id: Option<String>,
+    /// How a Claude session was launched, to relaunch it on auto-resume
+    pub launch: Option<ClaudeLaunch>,
+    /// Times (unix millis) this tab was auto-resumed, for the retry budget
+    pub resume_attempts: Vec<i64>,
+    /// Set once the process has ended
+    pub exit: Option<PtyExit>,
+    /// Why the backend killed the process (e.g. a resource limit), reported as the exit description
+    pub kill_reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PtySessionInfo {
+    pub session_id: String,
+    pub kind: SessionKind,
+    pub cwd: String,
+    pub claude_session_id: Option<String>,
+    pub exit: Option<PtyExit>,
+}
+
+/// `pty_spawn` options that carry over to an auto-resumed relaunch.
+#[derive(Debug, Clone)]
+pub struct ClaudeLaunch {
+    pub launch_profile_id: Option<String>,
+    pub skip_permissions: bool,
+    pub record: bool,
+}
+
+/// Everything `spawn_session` needs besides the command itself.
+struct SpawnRequest {
+    session_id: String,
+    kind: SessionKind,
+    cwd: String,
+    rows: u16,
+    cols: u16,
+    record: bool,
+    /// Launch or terminal profile, for profile-scoped trigger rules
+    profile_id: Option<String>,
+    claude_session_id: Option<String>,
+    launch: Option<ClaudeLaunch>,
+    resume_attempts: Vec<i64>,
+}
+
+/// Arguments of `pty_spawn`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeSpawnOptions {
+    pub session_id: String,
+    /// Start with `--resume {id}`
+    #[serde(default)]
+    pub resume_session_id: Option<String>,
+    /// Add `--fork-session` to a resume
+    #[serde(default)]
+    pub fork_session: bool,
+    pub cwd: String,
+    pub rows: u16,
+    pub cols: u16,
+    #[serde(default)]
+    pub skip_permissions: bool,
+    /// Record the session from the start (see `pty_start_recording`)
+    #[serde(default)]
+    pub record: bool,
+    #[serde(default)]
+    pub launch_profile_id: Option<String>,
 }
 
 #[tauri::command]
 pub async fn pty_spawn(
-    session_id: String,
-    resume_session_id: Option<String>,
+    options: ClaudeSpawnOptions,
+    app_handle: AppHandle,
+    state: State<'_, PtyState>,
+) -> Result<(), String> {
+    let ClaudeSpawnOptions {
+        session_id,
+        resume_session_id,
+        fork_session,
+        cwd,
+        rows,
+        cols,
+        skip_permissions,
+        record,
+        launch_profile_id,
+    } = options;
+    let launch = ClaudeLaunch {
+        launch_profile_id,
+        skip_permissions,
+        record,
+    };
+    let cmd = claude_command(&launch, &cwd, resume_session_id.as_deref(), fork_session)?;
+    let req = SpawnRequest {
+        session_id,
+        kind: SessionKind::Claude,
+        cwd,
+        rows,
+        cols,
+        record: launch.record,
+        profile_id: launch.launch_profile_id.clone(),
+        // A plain resume keeps the session id; a fork gets a new one from SessionStart
+        claude_session_id: resume_session_id.filter(|_| !fork_session),
+        launch: Some(launch),
+        resume_attempts: vec![],
+    };
+    spawn_session(req, cmd, &app_handle, &state)
+}
+
+/// Build the `claude` command line for `launch` in `cwd`.
+fn claude_command(
+    launch: &ClaudeLaunch,
+    cwd: &str,
+    resume_session_id: Option<&str>,
     fork_session: bool,
+) -> Result<CommandBuilder, String> {
+    let mut cmd = CommandBuilder::new("claude");
+    cmd.cwd(cwd);
+
+    if let Some(ref id) = launch.launch_profile_id {
+        let profiles = load_launch_profiles(&theassociate_home()?, Some(cwd));
+        let profile = profiles
+            .iter()
+            .find(|p| &p.id == id)
+            .ok_or_else(|| format!("Launch profile not found: {}", id))?;
+        cmd.args(launch_args(profile));
+        for (key, value) in &profile.env {
+            cmd.env(key, value);
+        }
+    }
+
+    if let Some(id) = resume_session_id {
+        cmd.args(["--resume", id]);
+        if fork_session {
+            cmd.arg("--fork-session");
+        }
+    }
+
+    if launch.skip_permissions {
+        cmd.arg("--dangerously-skip-permissions");
+    }
+    Ok(cmd)
+}
+
+/// Spawn a general-purpose terminal from a terminal profile (default profile
+/// when `profile_id` is omitted). The profile's own cwd wins over `cwd`.
+/// Use `pty_start_recording` to record it.
+#[tauri::command]
+pub async fn pty_spawn_shell(
+    session_id: String,
+    profile_id: Option<String>,
     cwd: String,
     rows: u16,
     cols: u16,
-    skip_permissions: bool,
     app_handle: AppHandle,
     state: State<'_, PtyState>,
+) -> Result<(), String> {
+    let theassociate_dir = theassociate_home()?;
+    let profiles = load_terminal_profiles(&theassociate_dir);
+    let profile = match profile_id.as_deref() {
+        Some(id) => profiles
+            .iter()
+            .find(|p| p.id == id)
+            .ok_or_else(|| format!("Terminal profile not found: {}", id))?,
+        None => profiles.first().ok_or("No terminal profiles available")?,
+    };
+
+    let cwd = profile.cwd.clone().filter(|c| !c.is_empty()).unwrap_or(cwd);
+    let mut cmd = CommandBuilder::new(&profile.command);
+    cmd.args(&profile.args);
+    cmd.cwd(&cwd);
+    for (key, value) in &profile.env {
+        cmd.env(key, value);
+    }
+
+    let req = SpawnRequest {
+        session_id,
+        kind: SessionKind::Shell,
+        cwd,
+        rows,
+        cols,
+        record: false,
+        profile_id: Some(profile.id.clone()),
+        claude_session_id: None,
+        launch: None,
+        resume_attempts: vec![],
+    };
+    spawn_session(req, cmd, &app_handle, &state)
+}
+
+/// Open a PTY, spawn `cmd` in it, register the session and start the reader thread.
+fn spawn_session(
+    req: SpawnRequest,
+    mut cmd: CommandBuilder,
+    app_handle: &AppHandle,
+    state: &PtyState,
 ) -> Result<(), String> {
     let pty_system = native_pty_system();
 
     let pair = pty_system
         .openpty(PtySize {
-            rows,
-            cols,
+            rows: req.rows,
+            cols: req.cols,
             pixel_width: 0,
             pixel_height: 0,
         })
         .map_err(|e| format!("Failed to open PTY: {}", e))?;
 
-    let mut cmd = CommandBuilder::new("claude");
-    cmd.cwd(&cwd);
-
-    // Remove env vars that cause Claude to detect it's running nested inside another session
+    // Remove env vars that cause Claude to detect it's running nested inside another
+    // session — also for shells, so `claude` can be started from a shell tab
     cmd.env_remove("CLAUDECODE");
     cmd.env_remove("CLAUDE_CODE_SESSION_ID");
     cmd.env_remove("CLAUDE_SESSION_ID");
@@ -46,24 +255,22 @@ pub async fn pty_spawn(
     cmd.env_remove("ANTHROPIC_CLAUDE_ENTRYPOINT");
     cmd.env_remove("CLAUDE_CODE_IS_SIDE_CHANNEL");
 
-    if let Some(ref id) = resume_session_id {
-        cmd.args(["--resume", id]);
-        if fork_session {
-            cmd.arg("--fork-session");
-        }
-    }
-
-    if skip_permissions {
-        cmd.arg("--dangerously-skip-permissions");
-    }
-
     cmd.env("TERM", "xterm-256color");
     cmd.env("COLORTERM", "truecolor");
+    // Point Claude (and shells that start it) at the selected Claude home
+    if let Some((key, dir)) = crate::paths::claude_config_env() {
+        cmd.env(key, dir);
+    }
 
+    let program = cmd
+        .get_argv()
+        .first()
+        .map(|a| a.to_string_lossy().to_string())
+        .unwrap_or_default();
     let mut child = pair
         .slave
         .spawn_command(cmd)
-        .map_err(|e| format!("Failed to spawn claude: {}", e))?;
+        .map_err(|e| format!("Failed to spawn {}: {}", program, e))?;
 
     let writer = match pair.master.take_writer() {
         Ok(w) => w,
@@ -81,28 +288,79 @@ pub async fn pty_spawn(
         }
     };
 
+    let output = Arc::new(OutputStream::new());
+    let screen = Arc::new(Mutex::new(ScreenModel::new(req.rows, req.cols)));
+    let recorder = Arc::new(Mutex::new(None));
+    if req.record {
+        match start_recorder(&req.cwd, &req.session_id, req.kind, req.profile_id.as_deref(), req.cols, req.rows) {
+            Ok(r) => *recorder.lock().map_err(|e| e.to_string())? = Some(r),
+            Err(e) => eprintln!("[pty] recording not started for {}: {}", req.session_id, e),
+        }
+    }
     {
         let mut sessions = state.0.lock().map_err(|e| e.to_string())?;
-        sessions.insert(session_id.clone(), PtySession { writer, master: pair.master, child });
+        sessions.insert(
+            req.session_id.clone(),
+            PtySession {
+                writer,
+                master: pair.master,
+                pid: child.process_id(),
+                child,
+                output: output.clone(),
+                screen: screen.clone(),
+                recorder: recorder.clone(),
+                cwd: req.cwd.clone(),
+                kind: req.kind,
+                profile_id: req.profile_id.clone(),
+                claude_session_id: req.claude_session_id.clone(),
+                launch: req.launch.clone(),
+                resume_attempts: req.resume_attempts.clone(),
+                exit: None,
+                kill_reason: None,
+            },
+        );
     }
 
-    // Stream PTY output to frontend as raw bytes (xterm.js handles ANSI sequences natively)
-    let sid = session_id.clone();
+    // Flush coalesced output to the attached channel once per frame
+    let flusher = {
+        let output = output.clone();
+        std::thread::spawn(move || {
+            while output.flush() {
+                std::thread::sleep(FRAME_INTERVAL);
+            }
+        })
+    };
+
+    // Read PTY output; raw bytes go to the stream (xterm.js handles ANSI sequences
+    // and split UTF-8 natively) and the screen model, decoded text feeds the
+    // recorder and output trigger rules, the rendered screen the screen rules
+    let sid = req.session_id.clone();
     let app = app_handle.clone();
+    let sessions = state.0.clone();
+    let rules = theassociate_home()
+        .map(|dir| load_trigger_rules(&dir))
+        .unwrap_or_else(|_| builtin_rules());
+    let mut triggers = TriggerEngine::new(&rules, req.kind, req.profile_id.as_deref());
+    let scan_screen = triggers.has_screen_rules();
     std::thread::spawn(move || {
         let mut reader = reader;
-        let mut buf = [0u8; 4096];
+        let mut buf = [0u8; 16 * 1024];
         // Carry buffer for incomplete UTF-8 sequences split across reads
         let mut carry: Vec<u8> = Vec::new();
-        // Track which plan filenames have already been emitted for this session
-        // so we fire plan-linked exactly once per plan (Claude's TUI redraws continuously)
-        let mut emitted_plans = std::collections::HashSet::<String>::new();
-        // Track last question to avoid spamming (same question across buffer reads)
-        let mut last_question: Option<(String, std::time::Instant)> = None;
         loop {
             match reader.read(&mut buf) {
                 Ok(0) => break,
                 Ok(n) => {
+                    output.push(&buf[..n]);
+                    let rows = screen.lock().ok().and_then(|mut screen| {
+                        screen.process(&buf[..n]);
+                        scan_screen.then(|| screen.lines())
+                    });
+                    if let Some(rows) = rows {
+                        for firing in triggers.scan_screen(&rows) {
+                            run_trigger(&firing, &sid, &app, &sessions);
+                        }
+                    }
                     carry.extend_from_slice(&buf[..n]);
                     // Find the longest valid UTF-8 prefix
                     let valid_up_to = match std::str::from_utf8(&carry) {
@@ -127,35 +385,23 @@ pub async fn pty_spawn(
                     // Keep the remainder (incomplete trailing bytes) for the next read
                     let remainder = carry[valid_up_to..].to_vec();
                     carry = remainder;
-                    // Detect plan file references and emit once per filename
-                    if let Some(filename) = find_plan_filename(&data) {
-                        if emitted_plans.insert(filename.clone()) {
-                            let _ = app.emit("plan-linked", serde_json::json!({
-                                "tab_id": sid,
-                                "filename": filename
-                            }));
+                    if let Ok(mut rec) = recorder.lock() {
+                        if let Some(r) = rec.as_mut() {
+                            r.output(&data);
                         }
                     }
-                    // Detect Claude CLI question prompts and notify the frontend
-                    if let Some(question) = find_claude_question(&data) {
-                        let should_emit = match &last_question {
-                            None => true,
-                            Some((prev, t)) => prev != &question || t.elapsed().as_secs() > 10,
-                        };
-                        if should_emit {
-                            last_question = Some((question.clone(), std::time::Instant::now()));
-                            app.emit("claude-question", QuestionPayload {
-                                tab_id: sid.clone(),
-                                question,
-                            }).ok();
-                        }
+                    for firing in triggers.scan(&data) {
+                        run_trigger(&firing, &sid, &app, &sessions);
                     }
-                    let _ = app.emit(&format!("pty-data-{}", sid), data);
                 }
                 Err(_) => break,
             }
         }
-        let _ = app.emit(&format!("pty-exit-{}", sid), ());
+        // Deliver the tail of the output before announcing the exit
+        output.close();
+        let _ = flusher.join();
+        crate::commands::broadcast::on_session_exit(&app, &sid);
+        finish_session(&app, &sid, &sessions, &output);
     });
 
     Ok(())
@@ -179,20 +425,67 @@ pub async fn pty_resize(
                 pixel_height: 0,
             })
             .map_err(|e| e.to_string())?;
+        if let Ok(mut screen) = session.screen.lock() {
+            screen.resize(rows, cols);
+        }
+        if let Ok(mut rec) = session.recorder.lock() {
+            if let Some(r) = rec.as_mut() {
+                r.resize(cols, rows);
+            }
+        }
         Ok(())
     } else {
         Err(format!("No session with id {}", session_id))
     }
 }
 
+/// Grow a session's PTY by one row and restore it shortly after, so the child
+/// redraws. Windows ConPTY only signals a real size change, and background tabs
+/// never resize on their own. The screen model and recorder keep the real size.
+#[tauri::command]
+pub async fn pty_nudge(session_id: String, state: State<'_, PtyState>) -> Result<(), String> {
+    let size = {
+        let sessions = state.0.lock().map_err(|e| e.to_string())?;
+        let session = sessions
+            .get(&session_id)
+            .ok_or_else(|| format!("No session with id {}", session_id))?;
+        let size = session.master.get_size().map_err(|e| e.to_string())?;
+        session
+            .master
+            .resize(PtySize { rows: size.rows + 1, ..size })
+            .map_err(|e| e.to_string())?;
+        size
+    };
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    let sessions = state.0.lock().map_err(|e| e.to_string())?;
+    if let Some(session) = sessions.get(&session_id) {
+        // A real resize in the meantime wins
+        let current = session.master.get_size().map_err(|e| e.to_string())?;
+        if current.rows == size.rows + 1 && current.cols == size.cols {
+            session.master.resize(size).map_err(|e| e.to_string())?;
+        }
+    }
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn pty_write(
     session_id: String,
     data: String,
     state: State<'_, PtyState>,
 ) -> Result<(), String> {
-    let mut sessions = state.0.lock().map_err(|e| e.to_string())?;
-    if let Some(session) = sessions.get_mut(&session_id) {
+    write_to_session(&state.0, &session_id, &data)
+}
+
+/// Write input to a session's PTY — shared by `pty_write`, trigger replies and
+/// the prompt queue.
+pub(crate) fn write_to_session(
+    sessions: &Mutex<HashMap<String, PtySession>>,
+    session_id: &str,
+    data: &str,
+) -> Result<(), String> {
+    let mut sessions = sessions.lock().map_err(|e| e.to_string())?;
+    if let Some(session) = sessions.get_mut(session_id) {
         session
             .writer
             .write_all(data.as_bytes())
@@ -204,111 +497,420 @@ pub async fn pty_write(
     }
 }
 
+/// Record which Claude session runs in a PTY tab, so hook events (e.g. `Stop`
+/// for the prompt queue) can be routed to it. Unknown tab ids are ignored.
+#[tauri::command]
+pub async fn pty_bind_session(
+    session_id: String,
+    claude_session_id: String,
+    state: State<'_, PtyState>,
+) -> Result<(), String> {
+    let mut sessions = state.0.lock().map_err(|e| e.to_string())?;
+    if let Some(session) = sessions.get_mut(&session_id) {
+        session.claude_session_id = Some(claude_session_id);
+    }
+    Ok(())
+}
+
+/// PTY tab currently bound to `claude_session_id`, if any.
+pub(crate) fn tab_for_claude_session(
+    sessions: &Mutex<HashMap<String, PtySession>>,
+    claude_session_id: &str,
+) -> Option<String> {
+    let sessions = sessions.lock().ok()?;
+    sessions
+        .iter()
+        .find(|(_, s)| s.claude_session_id.as_deref() == Some(claude_session_id))
+        .map(|(id, _)| id.clone())
+}
+
+/// Subscribe `output` to a live session's output and return its scrollback so far,
+/// e.g. after a webview reload or right after `pty_spawn`. Output is delivered as
+/// raw byte batches prefixed with their stream offset (see `OutputStream`), each
+/// of which must be confirmed with `pty_ack`.
+/// Returns `None` if no PTY with this id is running, so the caller should spawn one.
+#[tauri::command]
+pub async fn pty_attach(
+    session_id: String,
+    output: Channel<InvokeResponseBody>,
+    state: State<'_, PtyState>,
+) -> Result<Option<OutputSnapshot>, String> {
+    let stream = {
+        let sessions = state.0.lock().map_err(|e| e.to_string())?;
+        match sessions.get(&session_id) {
+            Some(session) => session.output.clone(),
+            None => return Ok(None),
+        }
+    };
+    Ok(Some(stream.attach(output)))
+}
+
+/// Confirm that the frontend has processed `bytes` of output; sending pauses
+/// while too much is unconfirmed.
+#[tauri::command]
+pub async fn pty_ack(
+    session_id: String,
+    bytes: usize,
+    state: State<'_, PtyState>,
+) -> Result<(), String> {
+    let sessions = state.0.lock().map_err(|e| e.to_string())?;
+    if let Some(session) = sessions.get(&session_id) {
+        session.output.ack(bytes);
+    }
+    Ok(())
+}
+
+/// Search a session's scrollback (ANSI stripped) for `query`.
+#[tauri::command]
+pub async fn pty_search(
+    session_id: String,
+    query: String,
+    case_sensitive: Option<bool>,
+    limit: Option<usize>,
+    state: State<'_, PtyState>,
+) -> Result<Vec<ScrollbackMatch>, String> {
+    let stream = {
+        let sessions = state.0.lock().map_err(|e| e.to_string())?;
+        sessions
+            .get(&session_id)
+            .map(|s| s.output.clone())
+            .ok_or_else(|| format!("Session {} not found", session_id))?
+    };
+    Ok(stream.search(&query, case_sensitive.unwrap_or(false), limit.unwrap_or(200)))
+}
+
+/// The session's rendered screen: text per row, cursor, title and any
+/// selection prompt on it. `ansi` adds escape sequences that redraw it.
+#[tauri::command]
+pub async fn pty_snapshot(
+    session_id: String,
+    ansi: Option<bool>,
+    state: State<'_, PtyState>,
+) -> Result<ScreenSnapshot, String> {
+    let screen = {
+        let sessions = state.0.lock().map_err(|e| e.to_string())?;
+        sessions
+            .get(&session_id)
+            .map(|s| s.screen.clone())
+            .ok_or_else(|| format!("Session {} not found", session_id))?
+    };
+    let screen = screen.lock().map_err(|e| e.to_string())?;
+    Ok(screen.snapshot(ansi.unwrap_or(false)))
+}
+
+fn start_recorder(
+    cwd: &str,
+    session_id: &str,
+    kind: SessionKind,
+    profile_id: Option<&str>,
+    cols: u16,
+    rows: u16,
+) -> Result<Recorder, String> {
+    let path = new_recording_path(cwd, session_id)?;
+    let title = std::path::Path::new(cwd)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .map(|n| format!("{} — {}", recording_label(cwd, kind, profile_id), n));
+    Recorder::create(&path, cols, rows, title).map_err(|e| e.to_string())
+}
+
+/// What a recording title calls the session: `claude` (plus the launch profile's
+/// name, if any) or the terminal profile's name, `shell` if it's gone.
+fn recording_label(cwd: &str, kind: SessionKind, profile_id: Option<&str>) -> String {
+    let home = theassociate_home().ok();
+    let name = match (&home, profile_id) {
+        (Some(dir), Some(id)) => match kind {
+            SessionKind::Claude => load_launch_profiles(dir, Some(cwd))
+                .into_iter()
+                .find(|p| p.id == id)
+                .map(|p| p.name),
+            SessionKind::Shell => load_terminal_profiles(dir)
+                .into_iter()
+                .find(|p| p.id == id)
+                .map(|p| p.name),
+        },
+        _ => None,
+    };
+    match (kind, name) {
+        (SessionKind::Claude, Some(name)) => format!("claude ({})", name),
+        (SessionKind::Claude, None) => "claude".to_string(),
+        (SessionKind::Shell, Some(name)) => name,
+        (SessionKind::Shell, None) => "shell".to_string(),
+    }
+}
+
+/// Start recording a running session to an asciicast v2 file. Returns the file path.
+#[tauri::command]
+pub async fn pty_start_recording(
+    session_id: String,
+    state: State<'_, PtyState>,
+) -> Result<String, String> {
+    let sessions = state.0.lock().map_err(|e| e.to_string())?;
+    let session = sessions
+        .get(&session_id)
+        .ok_or_else(|| format!("Session {} not found", session_id))?;
+    let mut rec = session.recorder.lock().map_err(|e| e.to_string())?;
+    if let Some(r) = rec.as_ref() {
+        return Ok(r.path().to_string_lossy().replace('\\', "/"));
+    }
+    let size = session.master.get_size().map_err(|e| e.to_string())?;
+    let recorder = start_recorder(
+        &session.cwd,
+        &session_id,
+        session.kind,
+        session.profile_id.as_deref(),
+        size.cols,
+        size.rows,
+    )?;
+    let path = recorder.path().to_string_lossy().replace('\\', "/");
+    *rec = Some(recorder);
+    Ok(path)
+}
+
+/// Stop recording a session. Returns the finished file's path, if it was recording.
+#[tauri::command]
+pub async fn pty_stop_recording(
+    session_id: String,
+    state: State<'_, PtyState>,
+) -> Result<Option<String>, String> {
+    let sessions = state.0.lock().map_err(|e| e.to_string())?;
+    let session = sessions
+        .get(&session_id)
+        .ok_or_else(|| format!("Session {} not found", session_id))?;
+    let mut rec = session.recorder.lock().map_err(|e| e.to_string())?;
+    Ok(rec
+        .take()
+        .map(|r| r.path().to_string_lossy().replace('\\', "/")))
+}
+
 #[tauri::command]
 pub async fn pty_kill(
     session_id: String,
+    app_handle: AppHandle,
     state: State<'_, PtyState>,
 ) -> Result<(), String> {
     let mut sessions = state.0.lock().map_err(|e| e.to_string())?;
     if let Some(mut session) = sessions.remove(&session_id) {
         let _ = session.child.kill();
     }
+    drop(sessions);
+    clear_queue(&app_handle, &session_id);
     Ok(())
 }
 
 #[tauri::command]
-pub async fn pty_kill_all(state: State<'_, PtyState>) -> Result<(), String> {
+pub async fn pty_kill_all(app_handle: AppHandle, state: State<'_, PtyState>) -> Result<(), String> {
     let mut sessions = state.0.lock().map_err(|e| e.to_string())?;
-    for (_, mut session) in sessions.drain() {
-        let _ = session.child.kill();
+    let killed: Vec<String> = sessions
+        .drain()
+        .map(|(id, mut session)| {
+            let _ = session.child.kill();
+            id
+        })
+        .collect();
+    drop(sessions);
+    for id in killed {
+        clear_queue(&app_handle, &id);
     }
     Ok(())
 }
 
-/// Strip ANSI escape sequences from a string.
-fn strip_ansi(s: &str) -> String {
-    let mut result = String::with_capacity(s.len());
-    let mut chars = s.chars().peekable();
-    while let Some(c) = chars.next() {
-        if c == '\x1b' {
-            match chars.next() {
-                Some('[') => { while let Some(c) = chars.next() { if c.is_ascii_alphabetic() { break; } } }
-                Some(']') => { while let Some(c) = chars.next() { if c == '\x07' || c == '\\' { break; } } }
-                _ => {}
-            }
-        } else {
-            result.push(c);
-        }
-    }
-    result
+/// Running PTY sessions, Claude and shell alike.
+#[tauri::command]
+pub async fn pty_list(state: State<'_, PtyState>) -> Result<Vec<PtySessionInfo>, String> {
+    let sessions = state.0.lock().map_err(|e| e.to_string())?;
+    Ok(sessions
+        .iter()
+        .map(|(id, s)| PtySessionInfo {
+            session_id: id.clone(),
+            kind: s.kind,
+            cwd: s.cwd.clone(),
+            claude_session_id: s.claude_session_id.clone(),
+            exit: s.exit.clone(),
+        })
+        .collect())
 }
 
-/// Detect Claude CLI question prompts (enquirer.js / Y/N style).
-/// Returns the extracted question text if found.
-fn find_claude_question(data: &str) -> Option<String> {
-    let clean = strip_ansi(data);
-    // Split on both \n and \r — PTY output uses \r\n and ANSI cursor codes
-    // leave bare \r characters that prevent clean line matching.
-    let lines: Vec<&str> = clean.split(|c: char| c == '\n' || c == '\r').collect();
-
-    for line in &lines {
-        let t = line.trim();
-        // enquirer.js / inquirer style: "? question text"
-        // Exclude known Claude CLI UI hint strings that share this prefix but are not questions.
-        const KNOWN_UI_HINTS: &[&str] = &["for shortcuts"];
-        if t.starts_with("? ") && t.len() > 2 {
-            let extracted = &t[2..];
-            if !KNOWN_UI_HINTS.iter().any(|hint| extracted.starts_with(hint)) {
-                return Some(extracted.to_string());
-            }
+/// How often, and how many times, to poll for the child's exit status once
+/// its output has closed.
+const REAP_INTERVAL: Duration = Duration::from_millis(50);
+const REAP_ATTEMPTS: usize = 60;
+
+/// Called by the reader thread once a PTY's output has closed: reap the child,
+/// record and emit how it ended (`pty-exit-{id}`), and relaunch a crashed
+/// Claude session with `--resume` when the auto-resume policy allows it.
+fn finish_session(
+    app: &AppHandle,
+    sid: &str,
+    sessions: &Mutex<HashMap<String, PtySession>>,
+    output: &OutputStream,
+) {
+    let mut status = None;
+    let mut killed = false;
+    let mut kill_reason = None;
+    for attempt in 0..REAP_ATTEMPTS {
+        let Ok(mut map) = sessions.lock() else {
+            return;
+        };
+        let Some(session) = map.get_mut(sid) else {
+            // pty_kill removed the session before the child exited
+            killed = true;
+            break;
+        };
+        if let Ok(Some(s)) = session.child.try_wait() {
+            status = Some(s);
+            kill_reason = session.kill_reason.clone();
+            break;
         }
-        // Y/N confirmation prompts
-        if !t.is_empty()
-            && (t.ends_with("[Y/n]")
-                || t.ends_with("[y/N]")
-                || t.ends_with("(Y/n)")
-                || t.ends_with("(y/N)"))
-        {
-            return Some(t.to_string());
+        if attempt + 1 == REAP_ATTEMPTS {
+            // Output closed but the process lingers; don't leave it behind
+            let _ = session.child.kill();
+            break;
         }
+        drop(map);
+        std::thread::sleep(REAP_INTERVAL);
     }
 
-    // Fallback: enquirer navigation hint is a reliable indicator of an interactive
-    // selection prompt (rendered as a full-screen TUI box without a leading "? ").
-    if clean.contains("Enter to select") && clean.contains("navigate") {
-        // Try to find the question text — look for a line ending with "?"
-        for line in &lines {
-            let t = line.trim();
-            if t.ends_with('?') && t.len() > 4 && !t.starts_with("Enter") {
-                return Some(t.to_string());
+    let reason = match &status {
+        _ if killed || kill_reason.is_some() => ExitReason::Killed,
+        Some(s) if s.success() => ExitReason::Clean,
+        _ => ExitReason::Crashed,
+    };
+    let mut exit = PtyExit {
+        reason,
+        code: status.as_ref().map(|s| s.exit_code()),
+        description: match &status {
+            _ if kill_reason.is_some() => kill_reason.unwrap_or_default(),
+            Some(s) => s.to_string(),
+            None if killed => "Killed".to_string(),
+            None => "Exit status unavailable".to_string(),
+        },
+        tail: output.tail(EXIT_TAIL_LINES),
+        resume: None,
+    };
+
+    // Decide on a resume under the same lock that records the exit, so the
+    // retry budget can't be spent twice
+    let mut relaunch = None;
+    if let Ok(mut map) = sessions.lock() {
+        if let Some(session) = map.get_mut(sid) {
+            if let (ExitReason::Crashed, Some(launch), Some(claude_id)) =
+                (reason, &session.launch, &session.claude_session_id)
+            {
+                let policy = theassociate_home()
+                    .map(|dir| load_auto_resume_policy(&dir))
+                    .unwrap_or_default();
+                let now = chrono::Utc::now().timestamp_millis();
+                if let Some(attempt) = take_retry(&policy, &mut session.resume_attempts, now) {
+                    exit.resume = Some(ResumeAttempt {
+                        claude_session_id: claude_id.clone(),
+                        attempt,
+                        max_retries: policy.max_retries,
+                        delay_ms: policy.delay_ms,
+                    });
+                    let size = session.master.get_size().ok();
+                    relaunch = Some(SpawnRequest {
+                        session_id: sid.to_string(),
+                        kind: SessionKind::Claude,
+                        cwd: session.cwd.clone(),
+                        rows: size.map(|s| s.rows).unwrap_or(24),
+                        cols: size.map(|s| s.cols).unwrap_or(80),
+                        record: launch.record,
+                        profile_id: launch.launch_profile_id.clone(),
+                        claude_session_id: Some(claude_id.clone()),
+                        launch: Some(launch.clone()),
+                        resume_attempts: session.resume_attempts.clone(),
+                    });
+                }
             }
+            session.exit = Some(exit.clone());
         }
-        return Some("Interactive selection prompt".to_string());
     }
+    if exit.reason == ExitReason::Crashed {
+        eprintln!("[pty] {} ended: {}", sid, exit.description);
+    }
+    let _ = app.emit(&format!("pty-exit-{}", sid), &exit);
 
-    None
-}
-
-#[derive(Clone, serde::Serialize)]
-struct QuestionPayload {
-    tab_id: String,
-    question: String,
+    let (Some(req), Some(resume)) = (relaunch, exit.resume) else {
+        clear_queue(app, sid);
+        return;
+    };
+    std::thread::sleep(Duration::from_millis(resume.delay_ms));
+    // The tab may have been closed during the delay
+    let still_exited = sessions
+        .lock()
+        .map(|map| map.get(sid).is_some_and(|s| s.exit.is_some()))
+        .unwrap_or(false);
+    if !still_exited {
+        return;
+    }
+    let result = req
+        .launch
+        .as_ref()
+        .ok_or_else(|| "Missing launch options".to_string())
+        .and_then(|launch| claude_command(launch, &req.cwd, Some(&resume.claude_session_id), false))
+        .and_then(|cmd| spawn_session(req, cmd, app, &app.state::<PtyState>()));
+    match result {
+        Ok(()) => {
+            let _ = app.emit(&format!("pty-resumed-{}", sid), &resume);
+        }
+        Err(e) => {
+            eprintln!("[pty] auto-resume of {} failed: {}", sid, e);
+            clear_queue(app, sid);
+            let _ = app.emit(&format!("pty-resume-failed-{}", sid), e);
+        }
+    }
 }
 
-/// Scan a PTY output chunk for a ~/.claude/plans/*.md file reference.
-/// Returns just the filename (e.g. "enchanted-herding-koala.md") when found.
-fn find_plan_filename(data: &str) -> Option<String> {
-    for marker in [".claude\\plans\\", ".claude/plans/"] {
-        if let Some(pos) = data.find(marker) {
-            let rest = &data[pos + marker.len()..];
-            let end = rest
-                .find(|c: char| c.is_ascii_control() || c == ' ' || c == '\'' || c == '"')
-                .unwrap_or(rest.len());
-            let filename = &rest[..end];
-            if filename.ends_with(".md") && filename.len() > 3 {
-                return Some(filename.to_string());
+/// Carry out the actions of a trigger rule that matched in session `sid`.
+fn run_trigger(firing: &Firing, sid: &str, app: &AppHandle, sessions: &Mutex<HashMap<String, PtySession>>) {
+    for action in &firing.actions {
+        match action {
+            FiredAction::Emit { event, payload } => {
+                let mut body = serde_json::Map::new();
+                body.insert("tab_id".to_string(), sid.into());
+                for (key, value) in payload {
+                    body.insert(key.clone(), value.as_str().into());
+                }
+                let _ = app.emit(event, serde_json::Value::Object(body));
+                if event == QUESTION_EVENT {
+                    let question = payload.get("question").map(String::as_str).unwrap_or("");
+                    crate::commands::broadcast::on_session_question(app, sid, question);
+                }
+            }
+            FiredAction::Notify { title, body } => {
+                emit_alert(app, sid, &firing.rule_id, title, body);
+            }
+            FiredAction::LinkFile { path } => {
+                let _ = app.emit("file-linked", TriggerPayload {
+                    tab_id: sid.to_string(),
+                    rule_id: firing.rule_id.clone(),
+                    title: None,
+                    text: path.clone(),
+                });
+            }
+            FiredAction::Reply { text } => {
+                let _ = write_to_session(sessions, sid, text);
             }
         }
     }
-    None
+}
+
+/// In-app + desktop notification about a tab (`terminal-alert`).
+pub(crate) fn emit_alert(app: &AppHandle, tab_id: &str, rule_id: &str, title: &str, text: &str) {
+    let _ = app.emit("terminal-alert", TriggerPayload {
+        tab_id: tab_id.to_string(),
+        rule_id: rule_id.to_string(),
+        title: Some(title.to_string()),
+        text: text.to_string(),
+    });
+}
+
+#[derive(Clone, Serialize)]
+struct TriggerPayload {
+    tab_id: String,
+    rule_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+    text: String,
 }
@@ -0,0 +1,207 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use tauri::ipc::{Channel, InvokeResponseBody};
+use tauri::{AppHandle, Emitter, State};
+
+use crate::paths::theassociate_home;
+use crate::data::path_encoding::encode_project_path;
+use crate::pty::recording::{list_recordings, read_cast, RecordingInfo};
+use crate::utils::strip_ansi;
+
+/// Cancel flags for in-flight playbacks, keyed by playback id.
+pub struct PlaybackState(pub Arc<Mutex<HashMap<String, Arc<AtomicBool>>>>);
+
+impl PlaybackState {
+    pub fn new() -> Self {
+        Self(Arc::new(Mutex::new(HashMap::new())))
+    }
+}
+
+/// `~/.claude/theassociate/projects/{encoded}/recordings/`
+fn recordings_dir(project_path: &str) -> Result<PathBuf, String> {
+    let encoded = encode_project_path(&PathBuf::from(project_path));
+    Ok(theassociate_home()?
+        .join("projects")
+        .join(encoded)
+        .join("recordings"))
+}
+
+/// Path for a new recording of `session_id` started now.
+pub(crate) fn new_recording_path(project_path: &str, session_id: &str) -> Result<PathBuf, String> {
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+    let safe_id: String = session_id
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '-' })
+        .collect();
+    Ok(recordings_dir(project_path)?.join(format!("{}-{}.cast", stamp, safe_id)))
+}
+
+/// Resolve a recording id (its file name) inside the project's recordings dir.
+fn recording_path(project_path: &str, recording_id: &str) -> Result<PathBuf, String> {
+    if recording_id.contains(['/', '\\']) || recording_id.contains("..") || !recording_id.ends_with(".cast") {
+        return Err("Invalid recording id".to_string());
+    }
+    let path = recordings_dir(project_path)?.join(recording_id);
+    if !path.exists() {
+        return Err(format!("Recording not found: {}", recording_id));
+    }
+    Ok(path)
+}
+
+#[tauri::command]
+pub async fn cmd_list_recordings(project_path: String) -> Result<Vec<RecordingInfo>, String> {
+    list_recordings(&recordings_dir(&project_path)?).map_err(|e| e.to_string())
+}
+
+/// How often a waiting playback checks whether it was stopped.
+const CANCEL_POLL: Duration = Duration::from_millis(50);
+
+/// Sleep for `delay`, waking every `CANCEL_POLL` to check `cancel`, so a long
+/// recorded pause can still be stopped. Returns `false` once cancelled.
+fn wait_unless_cancelled(delay: Duration, cancel: &AtomicBool) -> bool {
+    let deadline = Instant::now() + delay;
+    loop {
+        if cancel.load(Ordering::Relaxed) {
+            return false;
+        }
+        let left = deadline.saturating_duration_since(Instant::now());
+        if left.is_zero() {
+            return true;
+        }
+        std::thread::sleep(left.min(CANCEL_POLL));
+    }
+}
+
+/// Replay a recording into `output` as raw byte batches, the way `pty_attach`
+/// delivers live output. Size changes (starting with the recorded size) go out
+/// as `playback-resize-{playback_id}` events and the end as `playback-exit-{playback_id}`.
+/// `speed` multiplies playback rate; `idle_limit` caps pauses between events, in seconds.
+#[allow(clippy::too_many_arguments)]
+#[tauri::command]
+pub async fn cmd_play_recording(
+    project_path: String,
+    recording_id: String,
+    playback_id: String,
+    speed: Option<f64>,
+    idle_limit: Option<f64>,
+    output: Channel<InvokeResponseBody>,
+    app_handle: AppHandle,
+    state: State<'_, PlaybackState>,
+) -> Result<(), String> {
+    let path = recording_path(&project_path, &recording_id)?;
+    let (header, events) = read_cast(&path).map_err(|e| e.to_string())?;
+    let speed = speed.filter(|s| *s > 0.0).unwrap_or(1.0);
+
+    let cancel = Arc::new(AtomicBool::new(false));
+    {
+        let mut playbacks = state.0.lock().map_err(|e| e.to_string())?;
+        if let Some(prev) = playbacks.insert(playback_id.clone(), cancel.clone()) {
+            prev.store(true, Ordering::Relaxed);
+        }
+    }
+
+    let playbacks = state.0.clone();
+    std::thread::spawn(move || {
+        let resize = |cols: u16, rows: u16| {
+            let _ = app_handle.emit(
+                &format!("playback-resize-{}", playback_id),
+                serde_json::json!({ "cols": cols, "rows": rows }),
+            );
+        };
+        resize(header.width, header.height);
+
+        let mut last = 0.0_f64;
+        for event in events {
+            let mut delay = (event.time - last).max(0.0);
+            if let Some(limit) = idle_limit {
+                delay = delay.min(limit);
+            }
+            last = event.time;
+            if !wait_unless_cancelled(Duration::from_secs_f64(delay / speed), &cancel) {
+                return;
+            }
+            match event.code.as_str() {
+                // The player is gone; nothing left to play to
+                "o" if output.send(InvokeResponseBody::Raw(event.data.as_bytes().to_vec())).is_err() => {
+                    cancel.store(true, Ordering::Relaxed);
+                    break;
+                }
+                "r" => {
+                    if let Some((cols, rows)) = event.data.split_once('x') {
+                        if let (Ok(cols), Ok(rows)) = (cols.parse::<u16>(), rows.parse::<u16>()) {
+                            resize(cols, rows);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        if !cancel.load(Ordering::Relaxed) {
+            let _ = app_handle.emit(&format!("playback-exit-{}", playback_id), ());
+        }
+        if let Ok(mut map) = playbacks.lock() {
+            if map.get(&playback_id).map(|c| Arc::ptr_eq(c, &cancel)).unwrap_or(false) {
+                map.remove(&playback_id);
+            }
+        }
+    });
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn cmd_stop_playback(
+    playback_id: String,
+    state: State<'_, PlaybackState>,
+) -> Result<(), String> {
+    let mut playbacks = state.0.lock().map_err(|e| e.to_string())?;
+    if let Some(cancel) = playbacks.remove(&playback_id) {
+        cancel.store(true, Ordering::Relaxed);
+    }
+    Ok(())
+}
+
+/// Export a recording to `dest`: `"cast"` copies the asciicast file as-is,
+/// `"txt"` writes the output stream with ANSI sequences stripped.
+#[tauri::command]
+pub async fn cmd_export_recording(
+    project_path: String,
+    recording_id: String,
+    dest: String,
+    format: Option<String>,
+) -> Result<(), String> {
+    let src = recording_path(&project_path, &recording_id)?;
+    let dest_path = PathBuf::from(&dest);
+    // Reject paths containing .. components to prevent path traversal
+    if dest_path
+        .components()
+        .any(|c| matches!(c, std::path::Component::ParentDir))
+    {
+        return Err("Invalid path: '..' components are not allowed".to_string());
+    }
+    if let Some(parent) = dest_path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+
+    match format.as_deref().unwrap_or("cast") {
+        "cast" => {
+            std::fs::copy(&src, &dest_path).map_err(|e| e.to_string())?;
+        }
+        "txt" => {
+            let (_, events) = read_cast(&src).map_err(|e| e.to_string())?;
+            let output: String = events
+                .iter()
+                .filter(|e| e.code == "o")
+                .map(|e| e.data.as_str())
+                .collect();
+            let text = strip_ansi(&output).replace("\r\n", "\n");
+            std::fs::write(&dest_path, text).map_err(|e| e.to_string())?;
+        }
+        other => return Err(format!("Unknown export format: {}", other)),
+    }
+    Ok(())
+}
@@ -0,0 +1,13 @@
+use crate::paths::theassociate_home;
+use crate::data::resource_limits;
+use crate::models::resource_limits::ResourceLimits;
+
+#[tauri::command]
+pub async fn cmd_get_resource_limits() -> Result<ResourceLimits, String> {
+    Ok(resource_limits::load_resource_limits(&theassociate_home()?))
+}
+
+#[tauri::command]
+pub async fn cmd_save_resource_limits(limits: ResourceLimits) -> Result<(), String> {
+    resource_limits::save_resource_limits(&theassociate_home()?, &limits).map_err(|e| e.to_string())
+}
@@ -3,22 +3,13 @@ use crate::data::sessions::{load_sessions, load_subagent_sessions};
 use crate::data::transcripts::TranscriptReader;
 use crate::models::session::{SessionEntry, SessionIndex, SubagentSessionEntry};
 use crate::models::transcript::TranscriptItem;
+use crate::paths::claude_home;
 use serde::Serialize;
 use std::path::PathBuf;
 
-fn get_claude_home() -> Result<PathBuf, String> {
-    let home = std::env::var("USERPROFILE")
-        .or_else(|_| std::env::var("HOME"))
-        .map_err(|_| "Neither USERPROFILE nor HOME environment variable is set".to_string())?;
-    if home.is_empty() {
-        return Err("Home directory environment variable is empty".to_string());
-    }
-    Ok(PathBuf::from(home).join(".claude"))
-}
-
 #[tauri::command]
 pub async fn cmd_load_sessions(project_dir: String) -> Result<Vec<SessionEntry>, String> {
-    let claude_home = get_claude_home()?;
+    let claude_home = claude_home()?;
     let encoded = encode_project_path(&PathBuf::from(&project_dir));
     let project_sessions_dir = claude_home.join("projects").join(&encoded);
     load_sessions(&project_sessions_dir).map_err(|e| e.to_string())
@@ -29,7 +20,7 @@ pub async fn cmd_delete_session(
     project_dir: String,
     session_id: String,
 ) -> Result<(), String> {
-    let claude_home = get_claude_home()?;
+    let claude_home = claude_home()?;
     let encoded = encode_project_path(&PathBuf::from(&project_dir));
     let project_sessions_dir = claude_home.join("projects").join(&encoded);
 
@@ -57,7 +48,7 @@ pub async fn cmd_load_subagent_sessions(
     project_dir: String,
     session_id: String,
 ) -> Result<Vec<SubagentSessionEntry>, String> {
-    let claude_home = get_claude_home()?;
+    let claude_home = claude_home()?;
     let encoded = encode_project_path(&PathBuf::from(&project_dir));
     let project_sessions_dir = claude_home.join("projects").join(&encoded);
     Ok(load_subagent_sessions(&project_sessions_dir, &session_id))
@@ -1,4 +1,4 @@
-use crate::commands::projects::get_theassociate_home;
+use crate::paths::theassociate_home;
 use crate::data::summaries::load_summaries_for_session;
 use crate::models::summary::SummaryFile;
 
@@ -9,7 +9,7 @@ pub async fn cmd_load_summaries(
     project_dir: String,
     session_id: String,
 ) -> Result<Vec<SummaryFile>, String> {
-    let dir = get_theassociate_home()?
+    let dir = theassociate_home()?
         .join("projects")
         .join(&project_dir)
         .join("summaries");
@@ -23,7 +23,7 @@ pub async fn cmd_read_summary(
     project_dir: String,
     filename: String,
 ) -> Result<String, String> {
-    let summaries_dir = get_theassociate_home()?
+    let summaries_dir = theassociate_home()?
         .join("projects")
         .join(&project_dir)
         .join("summaries");
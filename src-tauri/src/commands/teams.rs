@@ -1,17 +1,8 @@
 use crate::data::teams::load_teams;
 use crate::models::team::Team;
+use crate::paths::claude_home;
 use std::path::{Path, PathBuf};
 
-fn get_claude_home() -> Result<PathBuf, String> {
-    let home = std::env::var("USERPROFILE")
-        .or_else(|_| std::env::var("HOME"))
-        .map_err(|_| "Neither USERPROFILE nor HOME environment variable is set".to_string())?;
-    if home.is_empty() {
-        return Err("Home directory environment variable is empty".to_string());
-    }
-    Ok(PathBuf::from(home).join(".claude"))
-}
-
 /// Clear read-only attributes on all files in a directory tree so
 /// `remove_dir_all` doesn't fail on Windows due to read-only files.
 ///
@@ -94,14 +85,14 @@ fn validate_within(child: &Path, parent: &Path) -> Result<PathBuf, String> {
 
 #[tauri::command]
 pub async fn cmd_load_teams(project_cwd: Option<String>) -> Result<Vec<Team>, String> {
-    let claude_home = get_claude_home()?;
+    let claude_home = claude_home()?;
     let cwd_path = project_cwd.map(PathBuf::from);
     load_teams(&claude_home, cwd_path.as_deref()).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
 pub async fn cmd_delete_team(team_name: String) -> Result<(), String> {
-    let claude_home = get_claude_home()?;
+    let claude_home = claude_home()?;
 
     // Delete team directory
     let teams_base = claude_home.join("teams");
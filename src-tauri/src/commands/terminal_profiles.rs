@@ -0,0 +1,24 @@
+use crate::paths::theassociate_home;
+use crate::data::terminal_profiles;
+use crate::models::terminal_profile::TerminalProfile;
+
+#[tauri::command]
+pub async fn cmd_list_terminal_profiles() -> Result<Vec<TerminalProfile>, String> {
+    Ok(terminal_profiles::load_terminal_profiles(&theassociate_home()?))
+}
+
+/// Save the user-defined profiles. Built-in entries in `profiles` are ignored.
+#[tauri::command]
+pub async fn cmd_save_terminal_profiles(profiles: Vec<TerminalProfile>) -> Result<(), String> {
+    let mut seen = std::collections::HashSet::new();
+    for profile in profiles.iter().filter(|p| !p.builtin) {
+        if profile.id.trim().is_empty() || profile.command.trim().is_empty() {
+            return Err("Terminal profiles need an id and a command".to_string());
+        }
+        if !seen.insert(profile.id.as_str()) {
+            return Err(format!("Duplicate terminal profile id: {}", profile.id));
+        }
+    }
+    terminal_profiles::save_terminal_profiles(&theassociate_home()?, &profiles)
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,26 @@
+use crate::paths::theassociate_home;
+use crate::data::trigger_rules;
+use crate::models::trigger_rule::TriggerRule;
+use crate::pty::triggers::validate_rule;
+
+/// Built-in rules (with any saved overrides) followed by user rules, in evaluation order.
+#[tauri::command]
+pub async fn cmd_list_trigger_rules() -> Result<Vec<TriggerRule>, String> {
+    Ok(trigger_rules::load_trigger_rules(&theassociate_home()?))
+}
+
+/// Save the full rule list. Applies to terminals started afterwards.
+#[tauri::command]
+pub async fn cmd_save_trigger_rules(rules: Vec<TriggerRule>) -> Result<(), String> {
+    let mut seen = std::collections::HashSet::new();
+    for rule in &rules {
+        if rule.id.trim().is_empty() {
+            return Err("Trigger rules need an id".to_string());
+        }
+        if !seen.insert(rule.id.as_str()) {
+            return Err(format!("Duplicate trigger rule id: {}", rule.id));
+        }
+        validate_rule(rule)?;
+    }
+    trigger_rules::save_trigger_rules(&theassociate_home()?, &rules).map_err(|e| e.to_string())
+}
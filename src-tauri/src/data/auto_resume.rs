@@ -0,0 +1,22 @@
+use std::path::Path;
+
+use anyhow::Result;
+
+use crate::models::auto_resume::AutoResumePolicy;
+
+const POLICY_FILE: &str = "auto-resume.json";
+
+/// The saved policy, or the default (disabled) when none is saved.
+pub fn load_auto_resume_policy(theassociate_dir: &Path) -> AutoResumePolicy {
+    std::fs::read_to_string(theassociate_dir.join(POLICY_FILE))
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_auto_resume_policy(theassociate_dir: &Path, policy: &AutoResumePolicy) -> Result<()> {
+    std::fs::create_dir_all(theassociate_dir)?;
+    let json = serde_json::to_string_pretty(policy)?;
+    std::fs::write(theassociate_dir.join(POLICY_FILE), json)?;
+    Ok(())
+}
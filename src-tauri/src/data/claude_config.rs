@@ -447,16 +447,9 @@ fn level_label(level: &ConfigLevel) -> &'static str {
 }
 
 /// Discover all extensions (MCP servers, skills, agents, allowed tools) across
-/// user-level and project-level config locations.
-pub fn discover_extensions(project_dir: &str) -> Vec<ClaudeExtension> {
-    let home = std::env::var("USERPROFILE")
-        .or_else(|_| std::env::var("HOME"))
-        .unwrap_or_default();
-    if home.is_empty() {
-        return vec![];
-    }
-    let home_path = PathBuf::from(&home);
-    let claude_home = home_path.join(".claude");
+/// user-level and project-level config locations. `claude_json` is Claude's
+/// global state file, which holds the user-level MCP servers.
+pub fn discover_extensions(claude_home: &Path, claude_json: &Path, project_dir: &str) -> Vec<ClaudeExtension> {
     let project_path = PathBuf::from(project_dir);
     let project_claude = project_path.join(".claude");
 
@@ -484,13 +477,12 @@ pub fn discover_extensions(project_dir: &str) -> Vec<ClaudeExtension> {
     }
 
     // User MCP servers from ~/.claude.json
-    let user_claude_json = home_path.join(".claude.json");
-    if user_claude_json.exists() {
-        if let Ok(content) = std::fs::read_to_string(&user_claude_json) {
+    if claude_json.exists() {
+        if let Ok(content) = std::fs::read_to_string(claude_json) {
             if let Ok(json) = serde_json::from_str::<Value>(&content) {
                 extract_mcp_servers(
                     &json,
-                    &user_claude_json,
+                    claude_json,
                     &ConfigLevel::User,
                     ".claude.json",
                     &mut all,
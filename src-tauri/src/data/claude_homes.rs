@@ -0,0 +1,22 @@
+use std::path::Path;
+
+use anyhow::Result;
+
+use crate::models::claude_home::ClaudeHomes;
+
+const HOMES_FILE: &str = "claude-homes.json";
+
+/// The registered Claude roots, or none when nothing is saved.
+pub fn load_claude_homes(theassociate_dir: &Path) -> ClaudeHomes {
+    std::fs::read_to_string(theassociate_dir.join(HOMES_FILE))
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_claude_homes(theassociate_dir: &Path, homes: &ClaudeHomes) -> Result<()> {
+    std::fs::create_dir_all(theassociate_dir)?;
+    let json = serde_json::to_string_pretty(homes)?;
+    std::fs::write(theassociate_dir.join(HOMES_FILE), json)?;
+    Ok(())
+}
@@ -0,0 +1,151 @@
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+use serde_json::Value;
+
+use crate::data::git::parse_diff_output;
+use crate::models::git::DiffLine;
+
+/// Number of timestamped backups kept per settings file.
+const MAX_BACKUPS: usize = 5;
+
+/// Read a Claude settings file (`settings.json`, `settings.local.json`).
+///
+/// A missing or blank file reads as an empty object. A file that exists but
+/// does not parse as a JSON object is an error — callers must never fall back
+/// to an empty object and write it back, or the user's config is wiped.
+pub fn read_settings(path: &Path) -> Result<Value> {
+    if !path.exists() {
+        return Ok(Value::Object(serde_json::Map::new()));
+    }
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    if content.trim().is_empty() {
+        return Ok(Value::Object(serde_json::Map::new()));
+    }
+    let value: Value = serde_json::from_str(&content).map_err(|e| {
+        anyhow!(
+            "{} is not valid JSON ({}); refusing to modify it",
+            path.display(),
+            e
+        )
+    })?;
+    if !value.is_object() {
+        return Err(anyhow!(
+            "{} is not a JSON object; refusing to modify it",
+            path.display()
+        ));
+    }
+    Ok(value)
+}
+
+/// Serialize settings the way Claude CLI writes them: two-space indent,
+/// original key order (serde_json `preserve_order`), trailing newline.
+pub fn render_settings(settings: &Value) -> Result<String> {
+    let mut out = serde_json::to_string_pretty(settings)?;
+    out.push('\n');
+    Ok(out)
+}
+
+/// True if `content` parses to the same JSON as `settings`, ignoring formatting.
+fn same_settings(content: &str, settings: &Value) -> bool {
+    serde_json::from_str::<Value>(content)
+        .map(|v| &v == settings)
+        .unwrap_or(false)
+}
+
+/// Write `settings` to `path` unless the file on disk already holds the same JSON.
+///
+/// The previous file is copied to a timestamped backup in `backup_dir` first
+/// (next to the file for `~/.claude/settings.json`; outside the client repo
+/// for a project's `settings.local.json`, where Claude's gitignore doesn't
+/// cover backups), then the new
+/// content is written to a temp file in the same directory and renamed over
+/// the original so a crash mid-write never leaves a truncated file behind.
+/// Returns `true` if the file was changed.
+pub fn write_settings(path: &Path, settings: &Value, backup_dir: &Path) -> Result<bool> {
+    let existing = std::fs::read_to_string(path).ok();
+    if existing.as_deref().map(|e| same_settings(e, settings)).unwrap_or(false) {
+        return Ok(false);
+    }
+    let rendered = render_settings(settings)?;
+
+    let dir = path
+        .parent()
+        .ok_or_else(|| anyhow!("Invalid settings path: {}", path.display()))?;
+    std::fs::create_dir_all(dir)?;
+
+    if existing.is_some() {
+        backup_settings(path, backup_dir)?;
+    }
+
+    let file_name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("settings.json");
+    let tmp_path = dir.join(format!(".{}.{}.tmp", file_name, std::process::id()));
+    {
+        let mut tmp = std::fs::File::create(&tmp_path)
+            .with_context(|| format!("Failed to create {}", tmp_path.display()))?;
+        tmp.write_all(rendered.as_bytes())?;
+        tmp.sync_all()?;
+    }
+    if let Err(e) = std::fs::rename(&tmp_path, path) {
+        std::fs::remove_file(&tmp_path).ok();
+        return Err(anyhow!("Failed to replace {}: {}", path.display(), e));
+    }
+    Ok(true)
+}
+
+/// Copy `path` to `<name>.<timestamp>.bak` in `dir` and prune all but the
+/// newest `MAX_BACKUPS` backups of that file there.
+fn backup_settings(path: &Path, dir: &Path) -> Result<PathBuf> {
+    std::fs::create_dir_all(dir)
+        .with_context(|| format!("Failed to create {}", dir.display()))?;
+    let file_name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("settings.json");
+    let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S%.3f");
+    let backup = dir.join(format!("{}.{}.bak", file_name, stamp));
+    std::fs::copy(path, &backup)
+        .with_context(|| format!("Failed to back up {}", path.display()))?;
+
+    let prefix = format!("{}.", file_name);
+    let mut backups: Vec<PathBuf> = std::fs::read_dir(dir)?
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .map(|n| n.starts_with(&prefix) && n.ends_with(".bak"))
+                .unwrap_or(false)
+        })
+        .collect();
+    // Timestamps sort lexically, so newest ends up last
+    backups.sort();
+    if backups.len() > MAX_BACKUPS {
+        for old in &backups[..backups.len() - MAX_BACKUPS] {
+            std::fs::remove_file(old).ok();
+        }
+    }
+    Ok(backup)
+}
+
+/// Unified diff between the settings file on disk and `settings`, parsed into
+/// the same `DiffLine`s the git diff viewer renders. Empty when nothing changes.
+pub fn diff_settings(path: &Path, settings: &Value) -> Result<Vec<DiffLine>> {
+    let before = std::fs::read_to_string(path).unwrap_or_default();
+    if same_settings(&before, settings) {
+        return Ok(vec![]);
+    }
+    let after = render_settings(settings)?;
+    let name = path.to_string_lossy().replace('\\', "/");
+    let diff = similar::TextDiff::from_lines(&before, &after)
+        .unified_diff()
+        .context_radius(3)
+        .header(&name, &name)
+        .to_string();
+    Ok(parse_diff_output(&diff))
+}
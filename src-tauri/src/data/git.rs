@@ -1,152 +1,1020 @@
-use std::path::Path;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
+use git2::build::CheckoutBuilder;
+use git2::{
+    ApplyLocation, BranchType, Delta, Diff, DiffFindOptions, DiffFormat, DiffOptions, ErrorCode, IndexConflict, Oid, Repository,
+    RepositoryState, Status, StatusOptions, SubmoduleIgnore, SubmoduleStatus as SmStatus,
+};
 
-use crate::models::git::{DiffLine, DiffLineKind, GitFileEntry, GitFileSection, GitStatus};
-use crate::utils::silent_command;
+use crate::models::git::{
+    BlameLine, ConflictKind, DiffLine, DiffLineId, DiffLineKind, DiffSelection, GitFileEntry, GitFileSection, GitOperation, GitStatus,
+    SubmoduleStatus,
+};
 
-/// Load git status by running `git status --porcelain` in the given directory.
-pub fn load_git_status(cwd: &Path) -> Result<GitStatus> {
-    let output = match silent_command("git")
-        .args(["status", "--porcelain"])
-        .current_dir(cwd)
-        .output()
-    {
-        Ok(o) => o,
-        Err(_) => return Ok(GitStatus::default()),
-    };
+/// Open the repository containing `cwd` (a subdirectory or linked worktree is fine).
+pub fn open_repo(cwd: &Path) -> Result<Repository> {
+    Repository::discover(cwd).map_err(|e| {
+        if e.code() == ErrorCode::NotFound {
+            anyhow!("Not a git repository: {}", cwd.display())
+        } else {
+            anyhow!("Failed to open repository at {}: {}", cwd.display(), e.message())
+        }
+    })
+}
 
-    if !output.status.success() {
-        return Ok(GitStatus::default());
+/// Load the working tree status: staged, unstaged, untracked and conflicted
+/// paths with renames detected, plus branch tracking info and submodules.
+pub fn load_git_status(cwd: &Path) -> Result<GitStatus> {
+    let repo = open_repo(cwd)?;
+    if repo.is_bare() {
+        return Err(anyhow!("{} is a bare repository", cwd.display()));
     }
-
-    let stdout = String::from_utf8_lossy(&output.stdout);
     let mut status = GitStatus::default();
 
-    for line in stdout.lines() {
-        if line.len() < 3 {
-            continue;
-        }
+    let mut opts = StatusOptions::new();
+    opts.include_untracked(true)
+        .recurse_untracked_dirs(false)
+        .include_ignored(false)
+        .renames_head_to_index(true)
+        .renames_index_to_workdir(true);
+    let conflicts = conflict_kinds(&repo)?;
 
-        let bytes = line.as_bytes();
-        let index_char = bytes[0] as char;
-        let worktree_char = bytes[1] as char;
-        let path_str = &line[3..];
-        let path = if index_char == 'R' || index_char == 'C' {
-            path_str
-                .split(" -> ")
-                .last()
-                .unwrap_or(path_str)
-                .to_string()
-        } else {
-            path_str.to_string()
+    for entry in repo.statuses(Some(&mut opts))?.iter() {
+        let flags = entry.status();
+        let Some(path) = entry.path().map(str::to_string) else {
+            continue;
         };
 
-        if index_char == '?' && worktree_char == '?' {
+        if flags.is_conflicted() {
+            status.conflicted.push(GitFileEntry {
+                conflict: conflicts.get(&path).copied(),
+                path,
+                section: GitFileSection::Conflicted,
+                status_char: 'U',
+                old_path: None,
+            });
+            continue;
+        }
+        if flags.is_wt_new() {
             status.untracked.push(GitFileEntry {
                 path,
                 section: GitFileSection::Untracked,
                 status_char: '?',
+                old_path: None,
+                conflict: None,
             });
             continue;
         }
 
-        if index_char != ' ' && index_char != '?' {
+        if let Some(status_char) = index_char(flags) {
+            let delta = entry.head_to_index();
             status.staged.push(GitFileEntry {
-                path: path.clone(),
+                path: delta_new_path(delta.as_ref()).unwrap_or_else(|| path.clone()),
                 section: GitFileSection::Staged,
-                status_char: index_char,
+                status_char,
+                old_path: renamed_from(delta.as_ref()),
+                conflict: None,
             });
         }
-
-        if worktree_char != ' ' && worktree_char != '?' {
+        if let Some(status_char) = worktree_char(flags) {
+            let delta = entry.index_to_workdir();
             status.unstaged.push(GitFileEntry {
-                path,
+                path: delta_new_path(delta.as_ref()).unwrap_or(path),
                 section: GitFileSection::Unstaged,
-                status_char: worktree_char,
+                status_char,
+                old_path: renamed_from(delta.as_ref()),
+                conflict: None,
             });
         }
     }
 
+    status.branch = current_branch(&repo)?;
+    if let Some((upstream, ahead, behind)) = tracking(&repo) {
+        status.upstream = Some(upstream);
+        status.ahead = ahead;
+        status.behind = behind;
+    }
+    status.submodules = submodules(&repo)?;
+    status.operation = current_operation(&repo);
     Ok(status)
 }
 
-/// Load diff for a specific file.
+fn index_char(flags: Status) -> Option<char> {
+    if flags.is_index_new() {
+        Some('A')
+    } else if flags.is_index_modified() {
+        Some('M')
+    } else if flags.is_index_deleted() {
+        Some('D')
+    } else if flags.is_index_renamed() {
+        Some('R')
+    } else if flags.is_index_typechange() {
+        Some('T')
+    } else {
+        None
+    }
+}
+
+fn worktree_char(flags: Status) -> Option<char> {
+    if flags.is_wt_modified() {
+        Some('M')
+    } else if flags.is_wt_deleted() {
+        Some('D')
+    } else if flags.is_wt_renamed() {
+        Some('R')
+    } else if flags.is_wt_typechange() {
+        Some('T')
+    } else {
+        None
+    }
+}
+
+fn delta_new_path(delta: Option<&git2::DiffDelta>) -> Option<String> {
+    delta?.new_file().path().map(|p| p.to_string_lossy().replace('\\', "/"))
+}
+
+fn renamed_from(delta: Option<&git2::DiffDelta>) -> Option<String> {
+    let delta = delta?;
+    if delta.status() != Delta::Renamed {
+        return None;
+    }
+    delta.old_file().path().map(|p| p.to_string_lossy().replace('\\', "/"))
+}
+
+/// Classify each conflicted index entry by which stages are present.
+fn conflict_kinds(repo: &Repository) -> Result<HashMap<String, ConflictKind>> {
+    let index = repo.index()?;
+    let mut kinds = HashMap::new();
+    if !index.has_conflicts() {
+        return Ok(kinds);
+    }
+    for conflict in index.conflicts()? {
+        let conflict = conflict?;
+        if let (Some(kind), Some(path)) = (conflict_kind(&conflict), conflict_path(&conflict)) {
+            kinds.insert(path, kind);
+        }
+    }
+    Ok(kinds)
+}
+
+/// Which of the base, ours and theirs stages a conflict has.
+pub(crate) fn conflict_kind(conflict: &IndexConflict) -> Option<ConflictKind> {
+    Some(match (
+        conflict.ancestor.is_some(),
+        conflict.our.is_some(),
+        conflict.their.is_some(),
+    ) {
+        (true, true, true) => ConflictKind::BothModified,
+        (false, true, true) => ConflictKind::BothAdded,
+        (true, false, false) => ConflictKind::BothDeleted,
+        (false, true, false) => ConflictKind::AddedByUs,
+        (false, false, true) => ConflictKind::AddedByThem,
+        (true, false, true) => ConflictKind::DeletedByUs,
+        (true, true, false) => ConflictKind::DeletedByThem,
+        (false, false, false) => return None,
+    })
+}
+
+pub(crate) fn conflict_path(conflict: &IndexConflict) -> Option<String> {
+    let entry = conflict.our.as_ref().or(conflict.their.as_ref()).or(conflict.ancestor.as_ref())?;
+    Some(String::from_utf8_lossy(&entry.path).into_owned())
+}
+
+/// The merge, rebase, cherry-pick or revert waiting to be continued, if any.
+pub(crate) fn current_operation(repo: &Repository) -> Option<GitOperation> {
+    match repo.state() {
+        RepositoryState::Merge => Some(GitOperation::Merge),
+        RepositoryState::Rebase
+        | RepositoryState::RebaseInteractive
+        | RepositoryState::RebaseMerge
+        | RepositoryState::ApplyMailboxOrRebase => Some(GitOperation::Rebase),
+        RepositoryState::CherryPick | RepositoryState::CherryPickSequence => Some(GitOperation::CherryPick),
+        RepositoryState::Revert | RepositoryState::RevertSequence => Some(GitOperation::Revert),
+        _ => None,
+    }
+}
+
+/// Short name of the checked-out branch (also before the first commit), or
+/// `HEAD` when detached.
+fn current_branch(repo: &Repository) -> Result<String> {
+    let head = repo.find_reference("HEAD")?;
+    Ok(match head.symbolic_target() {
+        Some(target) => target.strip_prefix("refs/heads/").unwrap_or(target).to_string(),
+        None => "HEAD".to_string(),
+    })
+}
+
+/// Upstream name and ahead/behind counts for the checked-out branch.
+fn tracking(repo: &Repository) -> Option<(String, usize, usize)> {
+    let head = repo.head().ok()?;
+    if !head.is_branch() {
+        return None;
+    }
+    let branch = git2::Branch::wrap(head);
+    let upstream = branch.upstream().ok()?;
+    let name = upstream.name().ok()??.to_string();
+    let local = branch.get().target()?;
+    let remote = upstream.get().target()?;
+    let (ahead, behind) = repo.graph_ahead_behind(local, remote).ok()?;
+    Some((name, ahead, behind))
+}
+
+fn submodules(repo: &Repository) -> Result<Vec<SubmoduleStatus>> {
+    let mut out = Vec::new();
+    for sm in repo.submodules()? {
+        let name = sm.name().unwrap_or_default().to_string();
+        let flags = repo.submodule_status(&name, SubmoduleIgnore::None)?;
+        out.push(SubmoduleStatus {
+            path: sm.path().to_string_lossy().replace('\\', "/"),
+            head_id: sm.head_id().map(|id| id.to_string()),
+            workdir_id: sm.workdir_id().map(|id| id.to_string()),
+            initialized: !flags.contains(SmStatus::WD_UNINITIALIZED),
+            commit_changed: flags.contains(SmStatus::WD_MODIFIED)
+                || flags.contains(SmStatus::INDEX_MODIFIED),
+            dirty: flags.intersects(
+                SmStatus::WD_INDEX_MODIFIED | SmStatus::WD_WD_MODIFIED | SmStatus::WD_UNTRACKED,
+            ),
+            name,
+        });
+    }
+    Ok(out)
+}
+
+/// Load the diff of one file: HEAD against the index when `staged`, else the
+/// index against the working tree.
 pub fn load_diff(cwd: &Path, file_path: &str, staged: bool) -> Result<Vec<DiffLine>> {
-    if staged {
-        load_git_diff(cwd, file_path, true)
+    let repo = open_repo(cwd)?;
+    let diff = if staged {
+        // Whole-index diff so a staged rename pairs up with its old path
+        let mut diff = repo.diff_tree_to_index(head_tree(&repo)?.as_ref(), None, None)?;
+        diff.find_similar(Some(DiffFindOptions::new().renames(true)))?;
+        diff
     } else {
-        load_git_diff(cwd, file_path, false)
+        let mut opts = DiffOptions::new();
+        opts.pathspec(file_path).disable_pathspec_match(true);
+        repo.diff_index_to_workdir(None, Some(&mut opts))?
+    };
+    Ok(parse_diff_output(&diff_text(&diff, Some(file_path))?))
+}
+
+/// HEAD's commit, or `None` before the first commit.
+pub(crate) fn head_commit(repo: &Repository) -> Result<Option<git2::Commit<'_>>> {
+    match repo.head() {
+        Ok(head) => Ok(Some(head.peel_to_commit()?)),
+        Err(e) if e.code() == ErrorCode::UnbornBranch || e.code() == ErrorCode::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
     }
 }
 
+/// HEAD's tree, or `None` before the first commit.
+pub(crate) fn head_tree(repo: &Repository) -> Result<Option<git2::Tree<'_>>> {
+    head_commit(repo)?.map(|c| c.tree()).transpose().map_err(Into::into)
+}
+
+/// The whole staged diff as `git diff --cached` prints it.
+pub fn staged_diff_text(cwd: &Path) -> Result<String> {
+    let repo = open_repo(cwd)?;
+    let mut diff = repo.diff_tree_to_index(head_tree(&repo)?.as_ref(), None, None)?;
+    diff.find_similar(Some(DiffFindOptions::new().renames(true)))?;
+    diff_text(&diff, None)
+}
+
+/// Render a diff as `git diff` prints it, optionally only the file whose old
+/// or new path is `only`.
+pub(crate) fn diff_text(diff: &Diff, only: Option<&str>) -> Result<String> {
+    let mut text = String::new();
+    diff.print(DiffFormat::Patch, |delta, _, line| {
+        if let Some(only) = only {
+            let is = |p: Option<&Path>| p.is_some_and(|p| p.to_string_lossy().replace('\\', "/") == only);
+            if !is(delta.new_file().path()) && !is(delta.old_file().path()) {
+                return true;
+            }
+        }
+        let content = String::from_utf8_lossy(line.content());
+        match line.origin() {
+            '+' | '-' | ' ' => {
+                text.push(line.origin());
+                text.push_str(&content);
+            }
+            _ => text.push_str(&content),
+        }
+        true
+    })?;
+    Ok(text)
+}
+
 /// Load current branch name.
 pub fn load_current_branch(cwd: &Path) -> Result<String> {
-    let output = silent_command("git")
-        .args(["rev-parse", "--abbrev-ref", "HEAD"])
-        .current_dir(cwd)
-        .output()?;
+    current_branch(&open_repo(cwd)?)
+}
 
-    if !output.status.success() {
-        return Ok(String::new());
+/// Load local branch names, sorted like `git branch`.
+pub fn load_branches(cwd: &Path) -> Result<Vec<String>> {
+    let repo = open_repo(cwd)?;
+    let mut names = Vec::new();
+    for branch in repo.branches(Some(BranchType::Local))? {
+        let (branch, _) = branch?;
+        if let Some(name) = branch.name()? {
+            names.push(name.to_string());
+        }
     }
+    names.sort();
+    Ok(names)
+}
 
-    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+/// Remote-tracking branches as `(remote, branch)` pairs, skipping `<remote>/HEAD`.
+pub fn load_remote_branches(cwd: &Path) -> Result<Vec<(String, String)>> {
+    let repo = open_repo(cwd)?;
+    let mut out = Vec::new();
+    for branch in repo.branches(Some(BranchType::Remote))? {
+        let (branch, _) = branch?;
+        let Some(name) = branch.name()? else {
+            continue;
+        };
+        if let Some((remote, rest)) = name.split_once('/') {
+            if rest != "HEAD" {
+                out.push((remote.to_string(), rest.to_string()));
+            }
+        }
+    }
+    out.sort();
+    Ok(out)
 }
 
-/// Load list of branches.
-pub fn load_branches(cwd: &Path) -> Result<Vec<String>> {
-    let output = silent_command("git")
-        .args(["branch", "--format=%(refname:short)"])
-        .current_dir(cwd)
-        .output()?;
+/// Ref decorations per commit, formatted like `git log --pretty=%D`.
+pub(crate) fn ref_labels(repo: &Repository) -> Result<HashMap<Oid, Vec<String>>> {
+    let head_branch = repo
+        .find_reference("HEAD")?
+        .symbolic_target()
+        .map(str::to_string);
+    let mut labels: HashMap<Oid, Vec<String>> = HashMap::new();
+    if head_branch.is_none() {
+        if let Some(oid) = repo.head().ok().and_then(|h| h.target()) {
+            labels.entry(oid).or_default().push("HEAD".to_string());
+        }
+    }
+    for reference in repo.references()? {
+        let reference = reference?;
+        let Some(full) = reference.name() else {
+            continue;
+        };
+        let Ok(target) = reference.peel_to_commit().map(|c| c.id()) else {
+            continue;
+        };
+        let label = if let Some(name) = full.strip_prefix("refs/heads/") {
+            if head_branch.as_deref() == Some(full) {
+                format!("HEAD -> {}", name)
+            } else {
+                name.to_string()
+            }
+        } else if let Some(name) = full.strip_prefix("refs/remotes/") {
+            name.to_string()
+        } else if let Some(name) = full.strip_prefix("refs/tags/") {
+            format!("tag: {}", name)
+        } else {
+            continue;
+        };
+        let entry = labels.entry(target).or_default();
+        // HEAD first, as git prints it
+        if label.starts_with("HEAD") {
+            entry.insert(0, label);
+        } else {
+            entry.push(label);
+        }
+    }
+    Ok(labels)
+}
+
+fn plural(n: i64, unit: &str) -> String {
+    if n == 1 {
+        format!("{} {}", n, unit)
+    } else {
+        format!("{} {}s", n, unit)
+    }
+}
 
-    if !output.status.success() {
-        return Ok(vec![]);
+/// `secs` ago in words, with git's `--date=relative` rounding.
+pub(crate) fn relative_date(secs: i64) -> String {
+    if secs < 0 {
+        return "in the future".to_string();
+    }
+    if secs < 90 {
+        return format!("{} ago", plural(secs, "second"));
+    }
+    let minutes = (secs + 30) / 60;
+    if minutes < 90 {
+        return format!("{} ago", plural(minutes, "minute"));
+    }
+    let hours = (minutes + 30) / 60;
+    if hours < 36 {
+        return format!("{} ago", plural(hours, "hour"));
+    }
+    let days = (hours + 12) / 24;
+    if days < 14 {
+        return format!("{} ago", plural(days, "day"));
+    }
+    if days < 70 {
+        return format!("{} ago", plural((days + 3) / 7, "week"));
+    }
+    if days < 365 {
+        return format!("{} ago", plural((days + 15) / 30, "month"));
+    }
+    if days < 1825 {
+        let total_months = (days * 12 * 2 + 365) / (365 * 2);
+        let (years, months) = (total_months / 12, total_months % 12);
+        return if months > 0 {
+            format!("{}, {} ago", plural(years, "year"), plural(months, "month"))
+        } else {
+            format!("{} ago", plural(years, "year"))
+        };
     }
+    format!("{} ago", plural((days + 183) / 365, "year"))
+}
+
+/// Name in the `Co-Authored-By` trailer the studio adds to agent commits.
+pub const AGENT_CO_AUTHOR: &str = "The Associate";
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    Ok(stdout.lines().map(|s| s.to_string()).collect())
+/// Whether a commit message carries the studio's co-author trailer.
+pub fn is_agent_commit(message: &str) -> bool {
+    let agent = AGENT_CO_AUTHOR.to_lowercase();
+    message.lines().any(|line| {
+        line.split_once(':').is_some_and(|(key, value)| {
+            key.trim().eq_ignore_ascii_case("co-authored-by")
+                && value.trim().to_lowercase().starts_with(&agent)
+        })
+    })
 }
 
-fn load_git_diff(cwd: &Path, file_path: &str, staged: bool) -> Result<Vec<DiffLine>> {
-    let mut args = vec!["diff"];
-    if staged {
-        args.push("--cached");
+/// `file_path` relative to `workdir`, whether given that way or absolute.
+pub(crate) fn repo_relative(workdir: &Path, file_path: &str) -> Result<PathBuf> {
+    let path = Path::new(file_path);
+    if !path.is_absolute() {
+        return Ok(path.to_path_buf());
     }
-    args.push("--");
-    args.push(file_path);
+    // Either side may go through a symlink (e.g. /tmp on macOS)
+    let canonical = |p: &Path| std::fs::canonicalize(p).unwrap_or_else(|_| p.to_path_buf());
+    path.strip_prefix(workdir)
+        .map(Path::to_path_buf)
+        .or_else(|_| canonical(path).strip_prefix(canonical(workdir)).map(Path::to_path_buf))
+        .map_err(|_| anyhow!("{} is outside the repository", file_path))
+}
 
-    let output = silent_command("git").args(&args).current_dir(cwd).output()?;
+/// Blame each line of the working-tree version of `file_path` (relative to
+/// the repository root, or absolute). Lines edited since HEAD have no commit.
+pub fn load_blame(cwd: &Path, file_path: &str) -> Result<Vec<BlameLine>> {
+    let repo = open_repo(cwd)?;
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| anyhow!("Cannot blame in a bare repository"))?;
+    let rel = repo_relative(workdir, file_path)?;
+    let rel = rel.as_path();
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    Ok(parse_diff_output(&stdout))
+    let blame = repo.blame_file(rel, None).map_err(|e| {
+        if e.code() == ErrorCode::NotFound {
+            anyhow!("{} is not tracked in HEAD", file_path)
+        } else {
+            e.into()
+        }
+    })?;
+    // Blame what is on disk, so local edits show up as uncommitted
+    let content = match std::fs::read(workdir.join(rel)) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            let tree = head_tree(&repo)?.ok_or_else(|| anyhow!("No commits yet"))?;
+            repo.find_blob(tree.get_path(rel)?.id())?.content().to_vec()
+        }
+        Err(e) => return Err(e.into()),
+    };
+    if content.contains(&0) {
+        return Err(anyhow!("Cannot blame a binary file: {}", file_path));
+    }
+    let blame = blame.blame_buffer(&content)?;
+    let text = String::from_utf8_lossy(&content);
+
+    let now = chrono::Utc::now().timestamp();
+    // Per-commit fields, looked up once however many lines a commit owns
+    let mut commits: HashMap<Oid, BlameLine> = HashMap::new();
+    let mut lines = Vec::new();
+    for (i, content) in text.lines().enumerate() {
+        let id = blame
+            .get_line(i + 1)
+            .map(|hunk| hunk.final_commit_id())
+            .filter(|id| !id.is_zero());
+        let Some(id) = id else {
+            lines.push(BlameLine {
+                line: i + 1,
+                content: content.to_string(),
+                hash: None,
+                short_hash: None,
+                author: "Not Committed Yet".to_string(),
+                email: String::new(),
+                timestamp: now,
+                date: relative_date(0),
+                summary: String::new(),
+                agent: false,
+            });
+            continue;
+        };
+        let meta = match commits.entry(id) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let commit = repo.find_commit(id)?;
+                let author = commit.author();
+                let timestamp = author.when().seconds();
+                entry.insert(BlameLine {
+                    line: 0,
+                    content: String::new(),
+                    hash: Some(id.to_string()),
+                    short_hash: commit.as_object().short_id()?.as_str().map(str::to_string),
+                    author: author.name().unwrap_or_default().to_string(),
+                    email: author.email().unwrap_or_default().to_string(),
+                    timestamp,
+                    date: relative_date(now - timestamp),
+                    summary: commit.summary().unwrap_or_default().to_string(),
+                    agent: is_agent_commit(commit.message().unwrap_or_default()),
+                })
+            }
+        };
+        lines.push(BlameLine {
+            line: i + 1,
+            content: content.to_string(),
+            ..meta.clone()
+        });
+    }
+    Ok(lines)
+}
+
+/// Extended header lines `git diff` prints before the first hunk of a file.
+const DIFF_HEADER_PREFIXES: &[&str] = &[
+    "diff ",
+    "index ",
+    "--- ",
+    "+++ ",
+    "similarity index ",
+    "rename from ",
+    "rename to ",
+    "new file mode ",
+    "deleted file mode ",
+    "old mode ",
+    "new mode ",
+];
+
+/// Parse `@@ -a,b +c,d @@` into `(a, b, c, d)`; a missing count means 1.
+fn parse_hunk_header(line: &str) -> Option<(u32, u32, u32, u32)> {
+    let mut parts = line.strip_prefix("@@ ")?.split(' ');
+    let range = |part: &str, sign: char| -> Option<(u32, u32)> {
+        let part = part.strip_prefix(sign)?;
+        match part.split_once(',') {
+            Some((start, count)) => Some((start.parse().ok()?, count.parse().ok()?)),
+            None => Some((part.parse().ok()?, 1)),
+        }
+    };
+    let (old_start, old_count) = range(parts.next()?, '-')?;
+    let (new_start, new_count) = range(parts.next()?, '+')?;
+    Some((old_start, old_count, new_start, new_count))
 }
 
-fn parse_diff_output(output: &str) -> Vec<DiffLine> {
-    output
-        .lines()
-        .map(|line| {
-            let kind = if line.starts_with("diff ")
-                || line.starts_with("index ")
-                || line.starts_with("--- ")
-                || line.starts_with("+++ ")
-            {
-                DiffLineKind::Header
-            } else if line.starts_with("@@") {
-                DiffLineKind::Hunk
-            } else if line.starts_with('+') {
+pub(crate) fn parse_diff_output(output: &str) -> Vec<DiffLine> {
+    let mut lines = Vec::new();
+    let mut hunk = None;
+    let mut hunks = 0;
+    // Next line number and lines still to come on each side of the current hunk
+    let (mut old_no, mut new_no, mut old_left, mut new_left) = (0, 0, 0u32, 0u32);
+
+    for line in output.lines() {
+        let (mut old_line, mut new_line) = (None, None);
+        let kind = if old_left > 0 || new_left > 0 {
+            // Inside a hunk body only the first column counts, so a removed
+            // "-- x" line is not mistaken for a "--- " header
+            if line.starts_with('+') {
+                new_line = Some(new_no);
+                new_no += 1;
+                new_left = new_left.saturating_sub(1);
                 DiffLineKind::Add
             } else if line.starts_with('-') {
+                old_line = Some(old_no);
+                old_no += 1;
+                old_left = old_left.saturating_sub(1);
                 DiffLineKind::Remove
             } else {
+                if !line.starts_with('\\') {
+                    old_line = Some(old_no);
+                    new_line = Some(new_no);
+                    old_no += 1;
+                    new_no += 1;
+                    old_left = old_left.saturating_sub(1);
+                    new_left = new_left.saturating_sub(1);
+                }
                 DiffLineKind::Context
+            }
+        } else if DIFF_HEADER_PREFIXES.iter().any(|p| line.starts_with(p)) {
+            if line.starts_with("diff ") {
+                hunk = None;
+            }
+            DiffLineKind::Header
+        } else if line.starts_with("@@") {
+            if let Some((old_start, old_count, new_start, new_count)) = parse_hunk_header(line) {
+                (old_no, new_no, old_left, new_left) = (old_start, new_start, old_count, new_count);
+            }
+            hunk = Some(hunks);
+            hunks += 1;
+            DiffLineKind::Hunk
+        } else if line.starts_with('+') {
+            DiffLineKind::Add
+        } else if line.starts_with('-') {
+            DiffLineKind::Remove
+        } else {
+            DiffLineKind::Context
+        };
+
+        lines.push(DiffLine {
+            kind,
+            text: line.to_string(),
+            hunk,
+            old_line,
+            new_line,
+        });
+    }
+    lines
+}
+
+/// What to do with a selection from a file's diff.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SelectionAction {
+    /// Copy the selected working-tree changes into the index
+    Stage,
+    /// Take the selected staged changes back out of the index
+    Unstage,
+    /// Revert the selected unstaged changes in the working tree
+    Discard,
+}
+
+/// Stage, unstage or discard part of one file's changes. The selection refers
+/// to the lines of `load_diff` for the same file (staged for `Unstage`).
+pub fn apply_selection(
+    cwd: &Path,
+    file_path: &str,
+    selection: &DiffSelection,
+    action: SelectionAction,
+) -> Result<()> {
+    let repo = open_repo(cwd)?;
+    let lines = load_diff(cwd, file_path, action == SelectionAction::Unstage)?;
+    let patch = selection_patch(file_path, &lines, selection, action != SelectionAction::Stage)
+        .ok_or_else(|| anyhow!("No changes selected in {}", file_path))?;
+    let location = match action {
+        SelectionAction::Stage | SelectionAction::Unstage => ApplyLocation::Index,
+        SelectionAction::Discard => ApplyLocation::WorkDir,
+    };
+    repo.apply(&Diff::from_buffer(patch.as_bytes())?, location, None)
+        .map_err(|e| anyhow!("Could not apply selection to {}: {}", file_path, e.message()))
+}
+
+/// Build a patch holding only the selected changes of a parsed single-file
+/// diff. Forward patches apply to the old side: unselected additions are
+/// dropped and unselected removals kept as context. With `reverse` the patch
+/// undoes the selection on the new side instead. `None` if nothing is selected.
+fn selection_patch(
+    path: &str,
+    lines: &[DiffLine],
+    selection: &DiffSelection,
+    reverse: bool,
+) -> Option<String> {
+    let selected = |l: &DiffLine| {
+        l.hunk.is_some_and(|h| selection.hunks.contains(&h))
+            || selection.lines.contains(&DiffLineId {
+                old_line: l.old_line,
+                new_line: l.new_line,
+            })
+    };
+    let mut patch = format!("diff --git a/{0} b/{0}\n--- a/{0}\n+++ b/{0}\n", path);
+    let mut any = false;
+    // Lines added minus lines removed by the hunks emitted so far
+    let mut offset = 0i64;
+
+    for hunk in lines.chunk_by(|a, b| a.hunk == b.hunk) {
+        let Some(header) = hunk.first().filter(|l| l.kind == DiffLineKind::Hunk) else {
+            continue;
+        };
+        let Some((old_start, old_count, new_start, new_count)) = parse_hunk_header(&header.text)
+        else {
+            continue;
+        };
+        let (base_start, base_count) = if reverse {
+            (new_start, new_count)
+        } else {
+            (old_start, old_count)
+        };
+
+        let mut body: Vec<PatchLine> = Vec::new();
+        let (mut old_n, mut new_n) = (0i64, 0i64);
+        let mut changed = false;
+        // Whether the previous line made it into the patch, for "\ No newline"
+        let mut kept = true;
+        for line in &hunk[1..] {
+            let rest = line.text.get(1..).unwrap_or("");
+            let (emit, old, new) = match (line.kind.clone(), line.text.starts_with('\\')) {
+                (DiffLineKind::Context, true) => {
+                    if let Some(last) = body.last_mut().filter(|_| kept) {
+                        last.old_eof = last.prefix != '+';
+                        last.new_eof = last.prefix != '-';
+                    }
+                    continue;
+                }
+                (DiffLineKind::Context, false) => (Some(' '), 1, 1),
+                (DiffLineKind::Add, _) => match (selected(line), reverse) {
+                    (true, false) => (Some('+'), 0, 1),
+                    (true, true) => (Some('-'), 1, 0),
+                    (false, false) => (None, 0, 0),
+                    (false, true) => (Some(' '), 1, 1),
+                },
+                (DiffLineKind::Remove, _) => match (selected(line), reverse) {
+                    (true, false) => (Some('-'), 1, 0),
+                    (true, true) => (Some('+'), 0, 1),
+                    (false, false) => (Some(' '), 1, 1),
+                    (false, true) => (None, 0, 0),
+                },
+                _ => continue,
             };
+            kept = emit.is_some();
+            if let Some(prefix) = emit {
+                changed |= prefix != ' ';
+                body.push(PatchLine {
+                    prefix,
+                    text: rest,
+                    old_eof: false,
+                    new_eof: false,
+                });
+                old_n += old;
+                new_n += new;
+            }
+        }
+        if !changed {
+            continue;
+        }
+
+        // 0-based position of the hunk in the base file, then in the file as
+        // the earlier emitted hunks leave it
+        let pos = i64::from(if base_count == 0 { base_start } else { base_start.saturating_sub(1) });
+        let old_at = pos + i64::from(old_n > 0);
+        let new_at = pos + offset + i64::from(new_n > 0);
+        patch.push_str(&format!("@@ -{},{} +{},{} @@\n", old_at, old_n, new_at, new_n));
+        push_patch_body(&mut patch, &mut body);
+        offset += new_n - old_n;
+        any = true;
+    }
+    any.then_some(patch)
+}
 
-            DiffLine {
-                kind,
-                text: line.to_string(),
+/// One line of a hunk built by `selection_patch`. `old_eof` / `new_eof` mark
+/// the last line of a side that has no trailing newline.
+struct PatchLine<'a> {
+    prefix: char,
+    text: &'a str,
+    old_eof: bool,
+    new_eof: bool,
+}
+
+/// Append a hunk body, placing "\ No newline at end of file" after the line
+/// that really ends each side. A line that lacked the newline in the diff but
+/// is followed by kept lines on the patch's new side gets one there: as
+/// context it becomes a `-` line without and a `+` line with the newline.
+fn push_patch_body(patch: &mut String, body: &mut [PatchLine]) {
+    const NO_NEWLINE: &str = "\\ No newline at end of file\n";
+    let last_old = body.iter().rposition(|l| l.prefix != '+');
+    let last_new = body.iter().rposition(|l| l.prefix != '-');
+    for (i, line) in body.iter_mut().enumerate() {
+        line.old_eof &= Some(i) == last_old;
+        line.new_eof &= Some(i) == last_new;
+    }
+    for line in body.iter() {
+        if line.prefix == ' ' && line.old_eof != line.new_eof {
+            for (prefix, eof) in [('-', line.old_eof), ('+', line.new_eof)] {
+                patch.push(prefix);
+                patch.push_str(line.text);
+                patch.push('\n');
+                if eof {
+                    patch.push_str(NO_NEWLINE);
+                }
             }
-        })
-        .collect()
+            continue;
+        }
+        patch.push(line.prefix);
+        patch.push_str(line.text);
+        patch.push('\n');
+        if line.old_eof || line.new_eof {
+            patch.push_str(NO_NEWLINE);
+        }
+    }
+}
+
+/// Unstage a whole file, putting back its HEAD version in the index (or
+/// removing it there before the first commit).
+pub fn unstage_file(cwd: &Path, file_path: &str) -> Result<()> {
+    let repo = open_repo(cwd)?;
+    let head = head_commit(&repo)?.map(|c| c.into_object());
+    repo.reset_default(head.as_ref(), [file_path])?;
+    Ok(())
+}
+
+/// Throw away a file's unstaged changes by checking out its index version.
+pub fn discard_file(cwd: &Path, file_path: &str) -> Result<()> {
+    let repo = open_repo(cwd)?;
+    let mut checkout = CheckoutBuilder::new();
+    checkout.force().disable_pathspec_match(true).path(file_path);
+    repo.checkout_index(None, Some(&mut checkout))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::Patch;
+
+    /// Parsed diff of `old` -> `new` for one file, as `load_diff` returns it.
+    fn diff_of(old: &str, new: &str) -> Vec<DiffLine> {
+        let path = Path::new("f.txt");
+        let mut patch = Patch::from_buffers(old.as_bytes(), Some(path), new.as_bytes(), Some(path), None).unwrap();
+        parse_diff_output(patch.to_buf().unwrap().as_str().unwrap())
+    }
+
+    /// Apply `patch` with libgit2 to a file holding `base` and return the result.
+    fn apply(base: &str, patch: &str) -> String {
+        let dir = std::env::temp_dir().join(format!(
+            "theassociate-git-test-{}-{}",
+            std::process::id(),
+            std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_nanos()
+        ));
+        let repo = Repository::init(&dir).unwrap();
+        std::fs::write(dir.join("f.txt"), base).unwrap();
+        let diff = Diff::from_buffer(patch.as_bytes()).unwrap();
+        let result = repo.apply(&diff, ApplyLocation::WorkDir, None);
+        let text = std::fs::read_to_string(dir.join("f.txt")).unwrap();
+        std::fs::remove_dir_all(&dir).ok();
+        result.unwrap_or_else(|e| panic!("patch rejected: {}\n{}", e.message(), patch));
+        text
+    }
+
+    /// Select single lines by `(old_line, new_line)`.
+    fn select_lines(ids: &[(Option<u32>, Option<u32>)]) -> DiffSelection {
+        DiffSelection {
+            hunks: vec![],
+            lines: ids
+                .iter()
+                .map(|&(old_line, new_line)| DiffLineId { old_line, new_line })
+                .collect(),
+        }
+    }
+
+    fn numbered(n: u32) -> String {
+        (1..=n).map(|i| format!("line {}\n", i)).collect()
+    }
+
+    #[test]
+    fn test_parse_line_numbers_across_hunks() {
+        let old = numbered(20);
+        let new = old.replace("line 2\n", "line 2\nnew a\n").replace("line 18\n", "");
+        let lines = diff_of(&old, &new);
+        let added = lines.iter().find(|l| l.text == "+new a").unwrap();
+        assert_eq!((added.old_line, added.new_line, added.hunk), (None, Some(3), Some(0)));
+        let removed = lines.iter().find(|l| l.text == "-line 18").unwrap();
+        assert_eq!((removed.old_line, removed.new_line, removed.hunk), (Some(18), None, Some(1)));
+        // Context after the insert is one line further down on the new side
+        let context = lines.iter().find(|l| l.text == " line 19").unwrap();
+        assert_eq!((context.old_line, context.new_line), (Some(19), Some(19)));
+        assert_eq!(lines.iter().filter(|l| l.kind == DiffLineKind::Hunk).count(), 2);
+    }
+
+    #[test]
+    fn test_parse_removed_line_that_looks_like_a_header() {
+        let lines = diff_of("a\n-- x\nb\n", "a\nb\n");
+        let removed = lines.iter().find(|l| l.text == "--- x").unwrap();
+        assert_eq!(removed.kind, DiffLineKind::Remove);
+        assert_eq!(removed.old_line, Some(2));
+    }
+
+    #[test]
+    fn test_parse_no_newline_marker_has_no_numbers() {
+        let lines = diff_of("a\nb", "a\nc");
+        let marker: Vec<_> = lines.iter().filter(|l| l.text.starts_with('\\')).collect();
+        assert_eq!(marker.len(), 2);
+        assert!(marker.iter().all(|l| l.old_line.is_none() && l.new_line.is_none()));
+        let added = lines.iter().find(|l| l.text == "+c").unwrap();
+        assert_eq!(added.new_line, Some(2));
+    }
+
+    #[test]
+    fn test_parse_new_file() {
+        let lines = diff_of("", "a\nb\n");
+        let numbers: Vec<_> = lines
+            .iter()
+            .filter(|l| l.kind == DiffLineKind::Add)
+            .map(|l| l.new_line)
+            .collect();
+        assert_eq!(numbers, vec![Some(1), Some(2)]);
+    }
+
+    #[test]
+    fn test_selection_nothing_selected() {
+        let lines = diff_of("a\n", "a\nb\n");
+        assert!(selection_patch("f.txt", &lines, &DiffSelection::default(), false).is_none());
+    }
+
+    #[test]
+    fn test_selection_forward_single_lines() {
+        let old = "a\nb\nc\n";
+        let new = "a\nx\nc\ny\n";
+        let lines = diff_of(old, new);
+        // Only the removal of "b"
+        let patch = selection_patch("f.txt", &lines, &select_lines(&[(Some(2), None)]), false).unwrap();
+        assert_eq!(apply(old, &patch), "a\nc\n");
+        // Only the added "y"
+        let patch = selection_patch("f.txt", &lines, &select_lines(&[(None, Some(4))]), false).unwrap();
+        assert_eq!(apply(old, &patch), "a\nb\nc\ny\n");
+    }
+
+    #[test]
+    fn test_selection_forward_multi_hunk_offsets() {
+        let old = numbered(20);
+        let new = old
+            .replace("line 2\n", "line 2\nnew a\nnew b\n")
+            .replace("line 18\n", "line 18\nnew c\n");
+        let lines = diff_of(&old, &new);
+        // All of the first hunk shifts the second one down by two lines
+        let selection = DiffSelection {
+            hunks: vec![0],
+            lines: vec![DiffLineId {
+                old_line: None,
+                new_line: Some(21),
+            }],
+        };
+        let patch = selection_patch("f.txt", &lines, &selection, false).unwrap();
+        assert_eq!(apply(&old, &patch), new);
+        // Only one line of the first hunk, then the second hunk
+        let selection = DiffSelection {
+            hunks: vec![1],
+            lines: vec![DiffLineId {
+                old_line: None,
+                new_line: Some(4),
+            }],
+        };
+        let patch = selection_patch("f.txt", &lines, &selection, false).unwrap();
+        assert_eq!(apply(&old, &patch), new.replace("new a\n", ""));
+    }
+
+    #[test]
+    fn test_selection_reverse_undoes_on_new_side() {
+        let old = numbered(20);
+        let new = old
+            .replace("line 2\n", "line 2\nnew a\nnew b\n")
+            .replace("line 18\n", "");
+        let lines = diff_of(&old, &new);
+        // Discard "new b" and the removal of line 18, keeping "new a"
+        let selection = select_lines(&[(None, Some(4)), (Some(18), None)]);
+        let patch = selection_patch("f.txt", &lines, &selection, true).unwrap();
+        assert_eq!(apply(&new, &patch), old.replace("line 2\n", "line 2\nnew a\n"));
+    }
+
+    #[test]
+    fn test_selection_new_file() {
+        let new = "a\nb\nc\n";
+        let lines = diff_of("", new);
+        // Forward from the empty file: a pure insert at the start
+        let patch = selection_patch("f.txt", &lines, &select_lines(&[(None, Some(2))]), false).unwrap();
+        assert_eq!(apply("", &patch), "b\n");
+        // Reverse: unstage one line of a newly added file
+        let patch = selection_patch("f.txt", &lines, &select_lines(&[(None, Some(1))]), true).unwrap();
+        assert_eq!(apply(new, &patch), "b\nc\n");
+    }
+
+    #[test]
+    fn test_selection_after_line_without_newline() {
+        let old = "a\nlast";
+        let new = "a\nLAST\nx\ny";
+        let lines = diff_of(old, new);
+        // The unselected "-last" stays as context and gains a newline
+        let patch = selection_patch("f.txt", &lines, &select_lines(&[(None, Some(3))]), false).unwrap();
+        assert_eq!(apply(old, &patch), "a\nlast\nx\n");
+        // Selecting the new last line keeps it without a newline
+        let patch = selection_patch("f.txt", &lines, &select_lines(&[(None, Some(4))]), false).unwrap();
+        assert_eq!(apply(old, &patch), "a\nlast\ny");
+        // Only the removal: "last" goes, the file ends after "a"
+        let patch = selection_patch("f.txt", &lines, &select_lines(&[(Some(2), None)]), false).unwrap();
+        assert_eq!(apply(old, &patch), "a\n");
+        // Everything at once reproduces the new side
+        let all = DiffSelection {
+            hunks: vec![0],
+            lines: vec![],
+        };
+        let patch = selection_patch("f.txt", &lines, &all, false).unwrap();
+        assert_eq!(apply(old, &patch), new);
+        // Discarding "x" in the working tree keeps the missing newline after "y"
+        let patch = selection_patch("f.txt", &lines, &select_lines(&[(None, Some(3))]), true).unwrap();
+        assert_eq!(apply(new, &patch), "a\nLAST\ny");
+        // Discarding "y" makes "x" the last line, and it keeps its newline
+        let patch = selection_patch("f.txt", &lines, &select_lines(&[(None, Some(4))]), true).unwrap();
+        assert_eq!(apply(new, &patch), "a\nLAST\nx\n");
+    }
 }
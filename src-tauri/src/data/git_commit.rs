@@ -0,0 +1,403 @@
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+
+use anyhow::{anyhow, bail, Result};
+use git2::{Commit, Config, Oid, Repository, RepositoryState, Signature};
+
+use crate::data::git::{head_commit, open_repo};
+use crate::models::git::{CommitOptions, CreatedCommit};
+use crate::utils::silent_command;
+
+/// Commit the index like `git commit` (hooks are not run). Signs the commit
+/// when `commit.gpgsign` is set, using `gpg.format` and `user.signingkey`.
+pub fn create_commit(cwd: &Path, opts: &CommitOptions) -> Result<CreatedCommit> {
+    let mut repo = open_repo(cwd)?;
+    let merging = !opts.amend && repo.state() == RepositoryState::Merge;
+    let mut merge_heads = Vec::new();
+    if merging {
+        repo.mergehead_foreach(|id| {
+            merge_heads.push(*id);
+            true
+        })?;
+    }
+    let mut index = repo.index()?;
+    if index.has_conflicts() {
+        bail!("Resolve merge conflicts before committing");
+    }
+    let head = head_commit(&repo)?;
+    if opts.amend && head.is_none() {
+        bail!("Nothing to amend: there are no commits yet");
+    }
+    let tree = repo.find_tree(index.write_tree()?)?;
+
+    let mut parents: Vec<Commit> = match (&head, opts.amend) {
+        (Some(head), true) => head.parents().collect(),
+        (Some(head), false) => vec![head.clone()],
+        (None, _) => Vec::new(),
+    };
+    for id in merge_heads {
+        parents.push(repo.find_commit(id)?);
+    }
+    if !opts.amend && !merging {
+        let unchanged = match parents.first() {
+            Some(parent) => parent.tree_id() == tree.id(),
+            None => tree.is_empty(),
+        };
+        if unchanged {
+            bail!("Nothing to commit: no staged changes");
+        }
+    }
+
+    let committer = repo
+        .signature()
+        .map_err(|_| anyhow!("Set user.name and user.email in git config to commit"))?;
+    let mut message = opts.message.clone();
+    if message.trim().is_empty() {
+        match (&head, opts.amend) {
+            (Some(head), true) => message = head.message().unwrap_or_default().to_string(),
+            // Like `git commit` during a merge, default to .git/MERGE_MSG
+            // without its comment lines
+            _ if merging => {
+                let merge_msg = repo.message().unwrap_or_default();
+                message = git2::message_prettify(&merge_msg, Some(b'#'))?;
+            }
+            _ => {}
+        }
+    }
+    if message.trim().is_empty() {
+        bail!("Commit message is empty");
+    }
+    if opts.sign_off {
+        message = add_sign_off(&message, &committer);
+    }
+    let message = git2::message_prettify(&message, None)?;
+    let author = match (&opts.author, &head) {
+        (Some(author), _) => Signature::now(&author.name, &author.email)?,
+        (None, Some(head)) if opts.amend => head.author().to_owned(),
+        (None, _) => committer.clone(),
+    };
+
+    let config = repo.config()?.snapshot()?;
+    let signed = config.get_bool("commit.gpgsign").unwrap_or(false);
+    let parent_refs: Vec<&Commit> = parents.iter().collect();
+    let id = if signed {
+        let buffer = repo.commit_create_buffer(&author, &committer, &message, &tree, &parent_refs)?;
+        let content = buffer
+            .as_str()
+            .ok_or_else(|| anyhow!("Commit contents are not valid UTF-8"))?;
+        let signature = sign_buffer(&config, &committer, content.as_bytes())?;
+        repo.commit_signed(content, &signature, None)?
+    } else {
+        repo.commit(None, &author, &committer, &message, &tree, &parent_refs)?
+    };
+
+    let summary = message.lines().next().unwrap_or_default().to_string();
+    let kind = if opts.amend {
+        "commit (amend)"
+    } else if head.is_none() {
+        "commit (initial)"
+    } else if merging {
+        "commit (merge)"
+    } else {
+        "commit"
+    };
+    move_head(&repo, id, &format!("{}: {}", kind, summary))?;
+    if merging {
+        repo.cleanup_state()?;
+    }
+
+    let short_hash = repo
+        .find_object(id, None)?
+        .short_id()?
+        .as_str()
+        .unwrap_or_default()
+        .to_string();
+    Ok(CreatedCommit {
+        hash: id.to_string(),
+        short_hash,
+        summary,
+        signed,
+    })
+}
+
+/// Point the current branch (or a detached HEAD) at `id`.
+fn move_head(repo: &Repository, id: Oid, log_message: &str) -> Result<()> {
+    let head = repo.find_reference("HEAD")?;
+    match head.symbolic_target() {
+        Some(branch) => {
+            repo.reference(branch, id, true, log_message)?;
+        }
+        None => repo.set_head_detached(id)?,
+    }
+    Ok(())
+}
+
+/// Append a `Signed-off-by` trailer for `who`, joining an existing trailer
+/// block the way `git commit -s` does.
+fn add_sign_off(message: &str, who: &Signature) -> String {
+    let line = format!(
+        "Signed-off-by: {} <{}>",
+        who.name().unwrap_or_default(),
+        who.email().unwrap_or_default()
+    );
+    let message = message.trim_end();
+    if message.lines().last() == Some(line.as_str()) {
+        return message.to_string();
+    }
+    let is_trailer = |l: &str| {
+        l.split_once(": ").is_some_and(|(key, _)| {
+            !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        })
+    };
+    // The subject is never a trailer block, even if it looks like one
+    let in_trailers = message
+        .rsplit_once("\n\n")
+        .is_some_and(|(_, last)| last.lines().all(is_trailer));
+    format!("{}{}{}", message, if in_trailers { "\n" } else { "\n\n" }, line)
+}
+
+/// Produce a detached signature of a commit buffer like `git commit -S`.
+fn sign_buffer(config: &Config, committer: &Signature, content: &[u8]) -> Result<String> {
+    let format = config
+        .get_string("gpg.format")
+        .unwrap_or_else(|_| "openpgp".to_string());
+    let key = config.get_string("user.signingkey").ok();
+    match format.as_str() {
+        "openpgp" | "x509" => {
+            let program = config
+                .get_string(&format!("gpg.{}.program", format))
+                .or_else(|e| {
+                    if format == "openpgp" {
+                        config.get_string("gpg.program")
+                    } else {
+                        Err(e)
+                    }
+                })
+                .unwrap_or_else(|_| if format == "x509" { "gpgsm" } else { "gpg" }.to_string());
+            // Without a configured key git signs as the committer
+            let key = key.unwrap_or_else(|| {
+                format!(
+                    "{} <{}>",
+                    committer.name().unwrap_or_default(),
+                    committer.email().unwrap_or_default()
+                )
+            });
+            let mut cmd = silent_command(&program);
+            cmd.args(["--status-fd=2", "-bsau", &key]);
+            let output = run_with_stdin(cmd, content)
+                .map_err(|e| anyhow!("Failed to run {}: {}", program, e))?;
+            signature_output(&program, output)
+        }
+        "ssh" => sign_ssh(config, key, content),
+        other => bail!("Unsupported gpg.format: {}", other),
+    }
+}
+
+/// `ssh-keygen -Y sign` works on files, so the buffer (and a literal public
+/// key, which signs through the agent) go through temp files.
+fn sign_ssh(config: &Config, key: Option<String>, content: &[u8]) -> Result<String> {
+    let program = config
+        .get_string("gpg.ssh.program")
+        .unwrap_or_else(|_| "ssh-keygen".to_string());
+    let key = key.ok_or_else(|| anyhow!("Set user.signingkey to sign commits with SSH"))?;
+
+    let stamp = format!(
+        "{}-{}",
+        std::process::id(),
+        chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+    );
+    let dir = std::env::temp_dir();
+    let buffer_file = dir.join(format!("theassociate-commit-{}", stamp));
+    let sig_file = PathBuf::from(format!("{}.sig", buffer_file.display()));
+    let mut temp_files = vec![buffer_file.clone(), sig_file.clone()];
+
+    let result = (|| {
+        fs::write(&buffer_file, content)?;
+        let mut cmd = silent_command(&program);
+        cmd.args(["-Y", "sign", "-n", "git", "-f"]);
+        let literal = key
+            .strip_prefix("key::")
+            .or_else(|| key.starts_with("ssh-").then_some(key.as_str()));
+        match literal {
+            Some(public_key) => {
+                let key_file = dir.join(format!("theassociate-signing-key-{}.pub", stamp));
+                temp_files.push(key_file.clone());
+                fs::write(&key_file, public_key)?;
+                cmd.arg(&key_file).arg("-U");
+            }
+            None => {
+                cmd.arg(expand_home(&key));
+            }
+        }
+        cmd.arg(&buffer_file);
+        let output = cmd
+            .output()
+            .map_err(|e| anyhow!("Failed to run {}: {}", program, e))?;
+        if !output.status.success() {
+            bail!(
+                "{} failed to sign the commit: {}",
+                program,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Ok(fs::read_to_string(&sig_file)?)
+    })();
+
+    for file in temp_files {
+        let _ = fs::remove_file(file);
+    }
+    result
+}
+
+fn run_with_stdin(mut cmd: Command, input: &[u8]) -> std::io::Result<Output> {
+    let mut child = cmd
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(input)?;
+    }
+    child.wait_with_output()
+}
+
+fn signature_output(program: &str, output: Output) -> Result<String> {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    // gpg reports success on the status fd; the exit code alone isn't enough
+    if !output.status.success() || !stderr.contains("[GNUPG:] SIG_CREATED ") {
+        bail!("{} failed to sign the commit: {}", program, stderr.trim());
+    }
+    String::from_utf8(output.stdout).map_err(|_| anyhow!("{} returned a non-UTF-8 signature", program))
+}
+
+/// Expand a leading `~/` as git does for `user.signingkey` paths.
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), crate::paths::home_dir()) {
+        (Some(rest), Ok(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Temp repo with a committer and one commit on the default branch, plus a
+    /// `feature` branch one commit ahead of it.
+    fn repo_with_feature() -> (PathBuf, Repository) {
+        let dir = std::env::temp_dir().join(format!(
+            "theassociate-commit-test-{}-{}",
+            std::process::id(),
+            std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_nanos()
+        ));
+        let repo = Repository::init(&dir).unwrap();
+        let mut config = repo.config().unwrap();
+        config.set_str("user.name", "Ada Lovelace").unwrap();
+        config.set_str("user.email", "ada@example.com").unwrap();
+        config.set_bool("commit.gpgsign", false).unwrap();
+        {
+            let sig = repo.signature().unwrap();
+            let mut index = repo.index().unwrap();
+            fs::write(dir.join("base.txt"), "base").unwrap();
+            index.add_path(Path::new("base.txt")).unwrap();
+            let base_tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+            let base = repo.commit(Some("HEAD"), &sig, &sig, "Base", &base_tree, &[]).unwrap();
+            let base = repo.find_commit(base).unwrap();
+            repo.branch("feature", &base, false).unwrap();
+            // Commit to the branch without checking it out
+            fs::write(dir.join("feature.txt"), "feature").unwrap();
+            index.add_path(Path::new("feature.txt")).unwrap();
+            let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+            repo.commit(Some("refs/heads/feature"), &sig, &sig, "Feature", &tree, &[&base]).unwrap();
+            fs::remove_file(dir.join("feature.txt")).unwrap();
+            index.read_tree(&base_tree).unwrap();
+            index.write().unwrap();
+        }
+        (dir, repo)
+    }
+
+    #[test]
+    fn test_merge_commit_defaults_to_merge_msg() {
+        let (dir, repo) = repo_with_feature();
+        let feature = repo.find_reference("refs/heads/feature").unwrap();
+        let feature = repo.reference_to_annotated_commit(&feature).unwrap();
+        repo.merge(&[&feature], None, None).unwrap();
+        fs::write(dir.join(".git/MERGE_MSG"), "Merge branch 'feature'\n\n# Conflicts:\n#\tnone\n").unwrap();
+
+        let result = create_commit(&dir, &CommitOptions::default());
+        let head = head_commit(&repo).unwrap().unwrap();
+        let state = repo.state();
+        fs::remove_dir_all(&dir).ok();
+
+        assert_eq!(result.unwrap().summary, "Merge branch 'feature'");
+        assert_eq!(head.message(), Some("Merge branch 'feature'\n"));
+        assert_eq!(head.parent_count(), 2);
+        assert_eq!(state, RepositoryState::Clean);
+    }
+
+    #[test]
+    fn test_empty_message_fails_outside_merge() {
+        let (dir, repo) = repo_with_feature();
+        fs::write(dir.join("new.txt"), "new").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("new.txt")).unwrap();
+        index.write().unwrap();
+        let result = create_commit(&dir, &CommitOptions::default());
+        fs::remove_dir_all(&dir).ok();
+        assert_eq!(result.unwrap_err().to_string(), "Commit message is empty");
+    }
+
+    fn signed_off(message: &str) -> String {
+        add_sign_off(message, &Signature::now("Ada Lovelace", "ada@example.com").unwrap())
+    }
+
+    #[test]
+    fn test_sign_off_starts_trailer_block() {
+        assert_eq!(
+            signed_off("Fix the parser\n\nIt dropped the last line.\n"),
+            "Fix the parser\n\nIt dropped the last line.\n\nSigned-off-by: Ada Lovelace <ada@example.com>"
+        );
+    }
+
+    #[test]
+    fn test_sign_off_joins_existing_trailers() {
+        assert_eq!(
+            signed_off("Fix the parser\n\nIt dropped the last line.\n\nCo-authored-by: Bob <bob@example.com>\nFixes: #12"),
+            "Fix the parser\n\nIt dropped the last line.\n\nCo-authored-by: Bob <bob@example.com>\nFixes: #12\n\
+             Signed-off-by: Ada Lovelace <ada@example.com>"
+        );
+    }
+
+    #[test]
+    fn test_sign_off_not_duplicated() {
+        let message = "Fix the parser\n\nSigned-off-by: Ada Lovelace <ada@example.com>";
+        assert_eq!(signed_off(message), message);
+        assert_eq!(signed_off(&format!("{}\n\n", message)), message);
+    }
+
+    #[test]
+    fn test_sign_off_after_other_sign_off() {
+        assert_eq!(
+            signed_off("Fix the parser\n\nSigned-off-by: Bob <bob@example.com>"),
+            "Fix the parser\n\nSigned-off-by: Bob <bob@example.com>\nSigned-off-by: Ada Lovelace <ada@example.com>"
+        );
+    }
+
+    #[test]
+    fn test_sign_off_subject_like_trailer() {
+        assert_eq!(
+            signed_off("Fixes: crash on empty repos"),
+            "Fixes: crash on empty repos\n\nSigned-off-by: Ada Lovelace <ada@example.com>"
+        );
+    }
+
+    #[test]
+    fn test_sign_off_body_not_trailers() {
+        assert_eq!(
+            signed_off("Fix the parser\n\nNote: the lexer\nis next"),
+            "Fix the parser\n\nNote: the lexer\nis next\n\nSigned-off-by: Ada Lovelace <ada@example.com>"
+        );
+    }
+}
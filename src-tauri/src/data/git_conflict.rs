@@ -0,0 +1,282 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{anyhow, bail, Result};
+use git2::{Oid, Repository};
+
+use crate::data::git::{conflict_kind, conflict_path, current_operation, open_repo};
+use crate::models::git::{ConflictFile, ConflictResolution, ConflictState, GitOperation};
+use crate::utils::silent_command;
+
+/// The `git` subcommand that drives an operation.
+fn subcommand(op: GitOperation) -> &'static str {
+    match op {
+        GitOperation::Merge => "merge",
+        GitOperation::Rebase => "rebase",
+        GitOperation::CherryPick => "cherry-pick",
+        GitOperation::Revert => "revert",
+    }
+}
+
+/// Trimmed contents of a file in the git dir, if present.
+fn read_git_file(repo: &Repository, name: &str) -> Option<String> {
+    fs::read_to_string(repo.path().join(name))
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// Blob contents as text, or `None` when the blob is binary.
+fn blob_text(repo: &Repository, id: Oid) -> Result<Option<String>> {
+    let blob = repo.find_blob(id)?;
+    Ok((!blob.is_binary()).then(|| String::from_utf8_lossy(blob.content()).into_owned()))
+}
+
+/// The operation in progress and every conflicted file with its base, ours,
+/// theirs and working-tree contents.
+pub fn load_conflict_state(cwd: &Path) -> Result<ConflictState> {
+    let repo = open_repo(cwd)?;
+    let operation = current_operation(&repo);
+    let mut state = ConflictState {
+        operation,
+        ..Default::default()
+    };
+
+    let incoming = match operation {
+        Some(GitOperation::Merge) => read_git_file(&repo, "MERGE_HEAD")
+            .and_then(|heads| heads.lines().next().map(str::to_string)),
+        Some(GitOperation::Rebase) => read_git_file(&repo, "REBASE_HEAD")
+            .or_else(|| read_git_file(&repo, "rebase-merge/stopped-sha")),
+        Some(GitOperation::CherryPick) => read_git_file(&repo, "CHERRY_PICK_HEAD"),
+        Some(GitOperation::Revert) => read_git_file(&repo, "REVERT_HEAD"),
+        None => None,
+    };
+    if let Some(commit) = incoming.and_then(|h| repo.revparse_single(&h).ok()?.peel_to_commit().ok()) {
+        state.incoming_hash = Some(commit.id().to_string());
+        state.incoming_summary = commit.summary().map(str::to_string);
+    }
+
+    if operation == Some(GitOperation::Rebase) {
+        // `rebase-merge` for the default backend, `rebase-apply` for the old one
+        let (dir, step, total) = if repo.path().join("rebase-merge").is_dir() {
+            ("rebase-merge", "msgnum", "end")
+        } else {
+            ("rebase-apply", "next", "last")
+        };
+        let number = |name: &str| read_git_file(&repo, &format!("{}/{}", dir, name))?.parse().ok();
+        state.step = number(step);
+        state.total_steps = number(total);
+        state.rebase_branch = read_git_file(&repo, &format!("{}/head-name", dir))
+            .map(|h| h.strip_prefix("refs/heads/").unwrap_or(&h).to_string());
+        state.rebase_onto = read_git_file(&repo, &format!("{}/onto", dir))
+            .and_then(|sha| repo.revparse_single(&sha).ok())
+            .and_then(|obj| obj.short_id().ok()?.as_str().map(str::to_string));
+    }
+
+    let index = repo.index()?;
+    if !index.has_conflicts() {
+        return Ok(state);
+    }
+    let workdir = repo.workdir().ok_or_else(|| anyhow!("Cannot resolve conflicts in a bare repository"))?;
+    for conflict in index.conflicts()? {
+        let conflict = conflict?;
+        let (Some(kind), Some(path)) = (conflict_kind(&conflict), conflict_path(&conflict)) else {
+            continue;
+        };
+        let mut binary = false;
+        let mut stage = |entry: &Option<git2::IndexEntry>| -> Result<Option<String>> {
+            let Some(entry) = entry else {
+                return Ok(None);
+            };
+            let text = blob_text(&repo, entry.id)?;
+            binary |= text.is_none();
+            Ok(text)
+        };
+        let base = stage(&conflict.ancestor)?;
+        let ours = stage(&conflict.our)?;
+        let theirs = stage(&conflict.their)?;
+        let working = fs::read(workdir.join(&path))
+            .ok()
+            .filter(|bytes| !bytes.contains(&0))
+            .map(|bytes| String::from_utf8_lossy(&bytes).into_owned());
+        state.conflicts.push(ConflictFile {
+            path,
+            kind,
+            binary,
+            base: if binary { None } else { base },
+            ours: if binary { None } else { ours },
+            theirs: if binary { None } else { theirs },
+            working: if binary { None } else { working },
+        });
+    }
+    state.conflicts.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(state)
+}
+
+/// Whether `text` still has a line opening or closing a conflict block.
+fn has_conflict_markers(text: &str) -> bool {
+    text.lines()
+        .any(|line| line.starts_with("<<<<<<< ") || line.starts_with(">>>>>>> "))
+}
+
+/// Settle one conflicted file: write the chosen version to the working tree
+/// and stage it, which clears the conflict.
+pub fn resolve_conflict(cwd: &Path, file_path: &str, resolution: &ConflictResolution) -> Result<()> {
+    let repo = open_repo(cwd)?;
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| anyhow!("Cannot resolve conflicts in a bare repository"))?
+        .to_path_buf();
+    let mut index = repo.index()?;
+    let conflict = index
+        .conflicts()?
+        .filter_map(|c| c.ok())
+        .find(|c| conflict_path(c).as_deref() == Some(file_path))
+        .ok_or_else(|| anyhow!("{} is not conflicted", file_path))?;
+
+    let target = workdir.join(file_path);
+    let side = match resolution {
+        ConflictResolution::Ours => Some(&conflict.our),
+        ConflictResolution::Theirs => Some(&conflict.their),
+        ConflictResolution::Content { content } => {
+            if has_conflict_markers(content) {
+                bail!("{} still has conflict markers", file_path);
+            }
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&target, content)?;
+            None
+        }
+    };
+    match side {
+        // That side deleted the file
+        Some(None) => {
+            if target.exists() {
+                fs::remove_file(&target)?;
+            }
+            index.remove_path(Path::new(file_path))?;
+        }
+        Some(Some(entry)) => {
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&target, repo.find_blob(entry.id)?.content())?;
+            index.add_path(Path::new(file_path))?;
+        }
+        None => index.add_path(Path::new(file_path))?,
+    }
+    index.write()?;
+    Ok(())
+}
+
+/// Stage files as they are in the working tree, as `git add` does after a
+/// manual edit. Refuses text files that still have conflict markers.
+pub fn mark_resolved(cwd: &Path, paths: &[String]) -> Result<()> {
+    let repo = open_repo(cwd)?;
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| anyhow!("Cannot resolve conflicts in a bare repository"))?
+        .to_path_buf();
+    let mut index = repo.index()?;
+    for path in paths {
+        let target = workdir.join(path);
+        match fs::read(&target) {
+            Ok(bytes) => {
+                if !bytes.contains(&0) && has_conflict_markers(&String::from_utf8_lossy(&bytes)) {
+                    bail!("{} still has conflict markers", path);
+                }
+                index.add_path(Path::new(path))?;
+            }
+            // Deleted in the working tree: resolve as deleted
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => index.remove_path(Path::new(path))?,
+            Err(e) => return Err(e.into()),
+        }
+    }
+    index.write()?;
+    Ok(())
+}
+
+/// The step to take with the operation in progress.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConflictStep {
+    Continue,
+    Skip,
+    Abort,
+}
+
+/// Continue, skip or abort the operation in progress with the `git` CLI so
+/// sequencer state and hooks behave as on the command line. Stopping on the
+/// next commit's conflicts is not an error; the new state is returned.
+pub fn run_conflict_step(cwd: &Path, step: ConflictStep) -> Result<ConflictState> {
+    let repo = open_repo(cwd)?;
+    let op = current_operation(&repo).ok_or_else(|| anyhow!("No merge, rebase, cherry-pick or revert in progress"))?;
+    let flag = match step {
+        ConflictStep::Continue => {
+            let index = repo.index()?;
+            if index.has_conflicts() {
+                let count = index.conflicts()?.count();
+                bail!(
+                    "Resolve {} conflicted file{} first",
+                    count,
+                    if count == 1 { "" } else { "s" }
+                );
+            }
+            "--continue"
+        }
+        ConflictStep::Skip if op == GitOperation::Merge => bail!("A merge can't skip; continue or abort it"),
+        ConflictStep::Skip => "--skip",
+        ConflictStep::Abort => "--abort",
+    };
+
+    let output = silent_command("git")
+        .arg("-C")
+        .arg(cwd)
+        .args([subcommand(op), flag])
+        // Keep the prepared message instead of opening an editor
+        .env("GIT_EDITOR", "true")
+        .output()
+        .map_err(|e| anyhow!("Failed to run git: {}", e))?;
+    let state = load_conflict_state(cwd)?;
+    if !output.status.success() && state.conflicts.is_empty() {
+        let text = format!(
+            "{}{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+        bail!("git {} {} failed: {}", subcommand(op), flag, text.trim());
+    }
+    Ok(state)
+}
+
+/// Everything an agent needs to merge one file, for `claude -p` on stdin.
+pub fn conflict_prompt_input(cwd: &Path, file_path: &str) -> Result<String> {
+    let state = load_conflict_state(cwd)?;
+    let file = state
+        .conflicts
+        .iter()
+        .find(|c| c.path == file_path)
+        .ok_or_else(|| anyhow!("{} is not conflicted", file_path))?;
+    if file.binary {
+        bail!("{} is binary; pick ours or theirs instead", file_path);
+    }
+    let (ours_label, theirs_label) = if state.operation == Some(GitOperation::Rebase) {
+        ("OURS (the branch being rebased onto)", "THEIRS (the commit being replayed)")
+    } else {
+        ("OURS (the checked-out branch)", "THEIRS (the incoming changes)")
+    };
+    let section = |title: &str, text: &Option<String>| match text {
+        Some(text) => format!("===== {} =====\n{}\n", title, text),
+        None => format!("===== {} =====\n(file does not exist on this side)\n", title),
+    };
+    let mut input = format!("File: {}\n", file.path);
+    if let Some(summary) = &state.incoming_summary {
+        input.push_str(&format!("Incoming commit: {}\n", summary));
+    }
+    input.push('\n');
+    input.push_str(&section("BASE (common ancestor)", &file.base));
+    input.push_str(&section(ours_label, &file.ours));
+    input.push_str(&section(theirs_label, &file.theirs));
+    input.push_str(&section("WORKING TREE (with conflict markers)", &file.working));
+    Ok(input)
+}
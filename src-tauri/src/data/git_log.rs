@@ -0,0 +1,445 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail, Result};
+use git2::{Commit, DiffFindOptions, ErrorCode, Oid, Patch, Repository, Sort};
+
+use crate::data::git::{is_agent_commit, open_repo, parse_diff_output, ref_labels, relative_date, repo_relative};
+use crate::models::git::{CommitDetail, CommitFileChange, CommitInfo, GraphRow, LogPage, LogQuery};
+
+const DEFAULT_PAGE_SIZE: usize = 100;
+
+/// Lays out commits in columns as they come out of a topological walk. Each
+/// lane holds the commit the line in that column is heading for.
+#[derive(Default)]
+struct LaneLayout {
+    lanes: Vec<Option<Oid>>,
+}
+
+impl LaneLayout {
+    fn free_lane(&mut self) -> usize {
+        match self.lanes.iter().position(Option::is_none) {
+            Some(i) => i,
+            None => {
+                self.lanes.push(None);
+                self.lanes.len() - 1
+            }
+        }
+    }
+
+    fn place(&mut self, id: Oid, parents: &[Oid]) -> GraphRow {
+        let up: Vec<usize> = (0..self.lanes.len())
+            .filter(|&i| self.lanes[i] == Some(id))
+            .collect();
+        // A branch tip nothing points at yet starts a new lane
+        let lane = match up.first() {
+            Some(&i) => i,
+            None => self.free_lane(),
+        };
+        for &i in &up {
+            self.lanes[i] = None;
+        }
+        let through: Vec<usize> = (0..self.lanes.len())
+            .filter(|&i| self.lanes[i].is_some())
+            .collect();
+
+        let mut down = Vec::new();
+        for (n, parent) in parents.iter().enumerate() {
+            // Join the line already heading for this parent, if any
+            let target = match self.lanes.iter().position(|l| *l == Some(*parent)) {
+                Some(i) => i,
+                None if n == 0 => lane,
+                None => self.free_lane(),
+            };
+            self.lanes[target] = Some(*parent);
+            if !down.contains(&target) {
+                down.push(target);
+            }
+        }
+
+        while self.lanes.last() == Some(&None) {
+            self.lanes.pop();
+        }
+        let width = up
+            .iter()
+            .chain(&down)
+            .map(|&i| i + 1)
+            .chain([lane + 1, self.lanes.len()])
+            .max()
+            .unwrap_or(1);
+        GraphRow {
+            lane,
+            up,
+            down,
+            through,
+            width,
+        }
+    }
+}
+
+/// Case-insensitive filters from a `LogQuery`, with blanks dropped.
+struct LogFilter {
+    author: Option<String>,
+    message: Option<String>,
+    path: Option<PathBuf>,
+}
+
+impl LogFilter {
+    fn new(repo: &Repository, query: &LogQuery) -> Result<Self> {
+        let text = |s: &Option<String>| {
+            s.as_deref()
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(str::to_lowercase)
+        };
+        let path = match query.path.as_deref().map(str::trim).filter(|p| !p.is_empty()) {
+            Some(path) => {
+                let workdir = repo
+                    .workdir()
+                    .ok_or_else(|| anyhow!("Cannot filter by path in a bare repository"))?;
+                let rel = repo_relative(workdir, path.trim_start_matches("./").trim_end_matches('/'))?;
+                // The repository root itself filters nothing
+                (!rel.as_os_str().is_empty()).then_some(rel)
+            }
+            None => None,
+        };
+        Ok(Self {
+            author: text(&query.author),
+            message: text(&query.message),
+            path,
+        })
+    }
+
+    fn is_empty(&self) -> bool {
+        self.author.is_none() && self.message.is_none() && self.path.is_none()
+    }
+
+    fn matches(&self, commit: &Commit) -> Result<bool> {
+        if let Some(author) = &self.author {
+            let sig = commit.author();
+            let name = sig.name().unwrap_or_default().to_lowercase();
+            let email = sig.email().unwrap_or_default().to_lowercase();
+            if !name.contains(author.as_str()) && !email.contains(author.as_str()) {
+                return Ok(false);
+            }
+        }
+        if let Some(message) = &self.message {
+            if !commit.message().unwrap_or_default().to_lowercase().contains(message.as_str()) {
+                return Ok(false);
+            }
+        }
+        match &self.path {
+            Some(path) => changes_path(commit, path),
+            None => Ok(true),
+        }
+    }
+}
+
+/// Whether `commit` changes `path`. Like `git log -- <path>`, a merge that
+/// keeps the path as one of its parents had it is left out.
+fn changes_path(commit: &Commit, path: &Path) -> Result<bool> {
+    let entry_id = |tree: git2::Tree| tree.get_path(path).ok().map(|e| e.id());
+    let own = entry_id(commit.tree()?);
+    if commit.parent_count() == 0 {
+        return Ok(own.is_some());
+    }
+    for parent in commit.parents() {
+        if entry_id(parent.tree()?) == own {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+fn short_hash(commit: &Commit) -> Result<String> {
+    Ok(commit.as_object().short_id()?.as_str().unwrap_or_default().to_string())
+}
+
+/// One page of the history reachable from HEAD, in `git log --graph` order.
+/// The graph is laid out from HEAD every time, so the walk restarts at the
+/// top and skips to `cursor` (the hash of the page's first commit).
+pub fn load_log(cwd: &Path, query: &LogQuery) -> Result<LogPage> {
+    let repo = open_repo(cwd)?;
+    let mut walk = repo.revwalk()?;
+    walk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+    match walk.push_head() {
+        Ok(()) => {}
+        // No commits yet
+        Err(e) if e.code() == ErrorCode::UnbornBranch || e.code() == ErrorCode::NotFound => {
+            return Ok(LogPage {
+                commits: vec![],
+                next_cursor: None,
+            })
+        }
+        Err(e) => return Err(e.into()),
+    }
+    let limit = query.limit.unwrap_or(DEFAULT_PAGE_SIZE).max(1);
+    let filter = LogFilter::new(&repo, query)?;
+    let mut layout = filter.is_empty().then(LaneLayout::default);
+    let labels = ref_labels(&repo)?;
+    let now = chrono::Utc::now().timestamp();
+
+    let mut started = query.cursor.is_none();
+    let mut commits = Vec::new();
+    for oid in walk {
+        let oid = oid?;
+        let commit = repo.find_commit(oid)?;
+        let parents: Vec<Oid> = commit.parent_ids().collect();
+        let graph = layout.as_mut().map(|l| l.place(oid, &parents));
+        if !started {
+            started = query.cursor.as_deref() == Some(oid.to_string().as_str());
+            if !started {
+                continue;
+            }
+        }
+        if !filter.matches(&commit)? {
+            continue;
+        }
+        if commits.len() == limit {
+            return Ok(LogPage {
+                commits,
+                next_cursor: Some(oid.to_string()),
+            });
+        }
+        let author = commit.author();
+        commits.push(CommitInfo {
+            hash: oid.to_string(),
+            short_hash: short_hash(&commit)?,
+            parents: parents.iter().map(Oid::to_string).collect(),
+            message: commit.summary().unwrap_or_default().to_string(),
+            author: author.name().unwrap_or_default().to_string(),
+            email: author.email().unwrap_or_default().to_string(),
+            timestamp: author.when().seconds(),
+            date: relative_date(now - author.when().seconds()),
+            refs: labels.get(&oid).cloned().unwrap_or_default(),
+            graph,
+        });
+    }
+    if !started {
+        bail!(
+            "Commit {} is no longer in the history; reload the log",
+            query.cursor.as_deref().unwrap_or_default()
+        );
+    }
+    Ok(LogPage {
+        commits,
+        next_cursor: None,
+    })
+}
+
+/// Full message and per-file changes of one commit (any revision `git show`
+/// accepts). Merges are compared with their first parent.
+pub fn load_commit_detail(cwd: &Path, rev: &str) -> Result<CommitDetail> {
+    let repo = open_repo(cwd)?;
+    let commit = repo
+        .revparse_single(rev)
+        .and_then(|o| o.peel_to_commit())
+        .map_err(|e| {
+            if e.code() == ErrorCode::NotFound {
+                anyhow!("No commit {}", rev)
+            } else {
+                e.into()
+            }
+        })?;
+    let base = match commit.parent(0) {
+        Ok(parent) => Some(parent.tree()?),
+        Err(_) => None,
+    };
+    let mut diff = repo.diff_tree_to_tree(base.as_ref(), Some(&commit.tree()?), None)?;
+    diff.find_similar(Some(DiffFindOptions::new().renames(true)))?;
+
+    let mut files = Vec::new();
+    for (i, delta) in diff.deltas().enumerate() {
+        let path_of = |f: git2::DiffFile| f.path().map(|p| p.to_string_lossy().replace('\\', "/"));
+        let new_path = path_of(delta.new_file());
+        let old_path = path_of(delta.old_file());
+        let status_char = match delta.status() {
+            git2::Delta::Added => 'A',
+            git2::Delta::Deleted => 'D',
+            git2::Delta::Renamed => 'R',
+            git2::Delta::Copied => 'C',
+            git2::Delta::Typechange => 'T',
+            _ => 'M',
+        };
+        let patch = Patch::from_diff(&diff, i)?;
+        let (additions, deletions) = match &patch {
+            Some(patch) => {
+                let (_, additions, deletions) = patch.line_stats()?;
+                (additions, deletions)
+            }
+            None => (0, 0),
+        };
+        let diff_lines = match patch {
+            Some(mut patch) => parse_diff_output(&String::from_utf8_lossy(&patch.to_buf()?)),
+            None => Vec::new(),
+        };
+        files.push(CommitFileChange {
+            path: new_path.clone().or_else(|| old_path.clone()).unwrap_or_default(),
+            old_path: matches!(status_char, 'R' | 'C').then_some(old_path).flatten(),
+            status_char,
+            additions,
+            deletions,
+            binary: delta.flags().is_binary(),
+            diff: diff_lines,
+        });
+    }
+
+    let author = commit.author();
+    let committer = commit.committer();
+    let message = commit.message().unwrap_or_default().to_string();
+    Ok(CommitDetail {
+        hash: commit.id().to_string(),
+        short_hash: short_hash(&commit)?,
+        parents: commit.parent_ids().map(|id| id.to_string()).collect(),
+        author: author.name().unwrap_or_default().to_string(),
+        email: author.email().unwrap_or_default().to_string(),
+        timestamp: author.when().seconds(),
+        date: relative_date(chrono::Utc::now().timestamp() - author.when().seconds()),
+        committer: committer.name().unwrap_or_default().to_string(),
+        committer_email: committer.email().unwrap_or_default().to_string(),
+        commit_timestamp: committer.when().seconds(),
+        refs: ref_labels(&repo)?.remove(&commit.id()).unwrap_or_default(),
+        agent: is_agent_commit(&message),
+        message,
+        files,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::{Signature, Time};
+
+    fn oid(n: u8) -> Oid {
+        Oid::from_bytes(&[n; 20]).unwrap()
+    }
+
+    fn row(lane: usize, up: &[usize], down: &[usize], through: &[usize], width: usize) -> GraphRow {
+        GraphRow {
+            lane,
+            up: up.to_vec(),
+            down: down.to_vec(),
+            through: through.to_vec(),
+            width,
+        }
+    }
+
+    /// Lay out `(commit, parents)` in walk order.
+    fn layout(commits: &[(u8, &[u8])]) -> Vec<GraphRow> {
+        let mut layout = LaneLayout::default();
+        commits
+            .iter()
+            .map(|(id, parents)| {
+                let parents: Vec<Oid> = parents.iter().map(|&p| oid(p)).collect();
+                layout.place(oid(*id), &parents)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_lanes_linear() {
+        assert_eq!(
+            layout(&[(3, &[2]), (2, &[1]), (1, &[])]),
+            vec![row(0, &[], &[0], &[], 1), row(0, &[0], &[0], &[], 1), row(0, &[0], &[], &[], 1)]
+        );
+    }
+
+    #[test]
+    fn test_lanes_branch_and_merge() {
+        // 4 merges 3 into 2; both branched off 1
+        assert_eq!(
+            layout(&[(4, &[2, 3]), (3, &[1]), (2, &[1]), (1, &[])]),
+            vec![
+                row(0, &[], &[0, 1], &[], 2),
+                row(1, &[1], &[1], &[0], 2),
+                // 2's line joins the one already heading for 1
+                row(0, &[0], &[1], &[1], 2),
+                row(1, &[1], &[], &[], 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lanes_octopus_merge() {
+        assert_eq!(
+            layout(&[(4, &[1, 2, 3]), (1, &[]), (2, &[]), (3, &[])]),
+            vec![
+                row(0, &[], &[0, 1, 2], &[], 3),
+                row(0, &[0], &[], &[1, 2], 3),
+                row(1, &[1], &[], &[2], 3),
+                row(2, &[2], &[], &[], 3),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lanes_unrelated_tips() {
+        // 4 -> 2 and 3 -> 1 share no history
+        assert_eq!(
+            layout(&[(4, &[2]), (3, &[1]), (2, &[]), (1, &[])]),
+            vec![
+                row(0, &[], &[0], &[], 1),
+                row(1, &[], &[1], &[0], 2),
+                row(0, &[0], &[], &[1], 2),
+                row(1, &[1], &[], &[], 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_load_log_pages_continue_graph() {
+        let dir = std::env::temp_dir().join(format!(
+            "theassociate-log-test-{}-{}",
+            std::process::id(),
+            std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_nanos()
+        ));
+        let repo = Repository::init(&dir).unwrap();
+        {
+            let tree = repo.find_tree(repo.index().unwrap().write_tree().unwrap()).unwrap();
+            let mut seconds = 1_700_000_000;
+            let mut commit = |update_ref: Option<&str>, message: &str, parents: &[Oid]| {
+                seconds += 60;
+                let sig = Signature::new("Ada", "ada@example.com", &Time::new(seconds, 0)).unwrap();
+                let parents: Vec<Commit> = parents.iter().map(|&p| repo.find_commit(p).unwrap()).collect();
+                let parents: Vec<&Commit> = parents.iter().collect();
+                repo.commit(update_ref, &sig, &sig, message, &tree, &parents).unwrap()
+            };
+            let root = commit(None, "root", &[]);
+            let a1 = commit(None, "a1", &[root]);
+            let b1 = commit(None, "b1", &[a1]);
+            let a2 = commit(None, "a2", &[a1]);
+            let b2 = commit(None, "b2", &[b1]);
+            let merge = commit(None, "merge", &[a2, b2]);
+            commit(Some("HEAD"), "tip", &[merge]);
+        }
+
+        let full = load_log(&dir, &LogQuery::default()).unwrap();
+        let mut paged = Vec::new();
+        let mut cursor = None;
+        loop {
+            let query = LogQuery {
+                cursor: cursor.clone(),
+                limit: Some(3),
+                ..LogQuery::default()
+            };
+            let page = load_log(&dir, &query).unwrap();
+            if let Some(cursor) = &cursor {
+                assert_eq!(&page.commits[0].hash, cursor);
+            }
+            paged.extend(page.commits);
+            cursor = page.next_cursor;
+            if cursor.is_none() {
+                break;
+            }
+        }
+        std::fs::remove_dir_all(&dir).ok();
+
+        assert_eq!(full.commits.len(), 7);
+        assert_eq!(full.next_cursor, None);
+        let rows = |commits: &[CommitInfo]| -> Vec<(String, Option<GraphRow>)> {
+            commits.iter().map(|c| (c.hash.clone(), c.graph.clone())).collect()
+        };
+        assert_eq!(rows(&paged), rows(&full.commits));
+        let merge = full.commits.iter().find(|c| c.message == "merge").unwrap();
+        assert_eq!(merge.graph.as_ref().unwrap().down.len(), 2);
+    }
+}
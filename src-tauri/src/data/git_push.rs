@@ -0,0 +1,331 @@
+use std::io::Read;
+use std::path::Path;
+use std::process::Stdio;
+use std::sync::OnceLock;
+
+use anyhow::{anyhow, bail, Result};
+use git2::{ErrorCode, Repository};
+use regex::Regex;
+
+use crate::data::git::open_repo;
+use crate::models::git::{PushOptions, PushRefStatus, PushRefUpdate, PushRejection, PushResult};
+use crate::utils::silent_command;
+
+/// Where the checked-out branch goes when pushed.
+struct PushTarget {
+    remote: String,
+    branch: String,
+    remote_ref: String,
+    set_upstream: bool,
+}
+
+fn push_target(repo: &Repository) -> Result<PushTarget> {
+    let head = match repo.head() {
+        Ok(head) => head,
+        Err(e) if e.code() == ErrorCode::UnbornBranch => bail!("Nothing to push: there are no commits yet"),
+        Err(e) => return Err(e.into()),
+    };
+    if !head.is_branch() {
+        bail!("Cannot push a detached HEAD; check out a branch first");
+    }
+    let branch = head.shorthand().unwrap_or_default().to_string();
+    let config = repo.config()?.snapshot()?;
+    let upstream_remote = config.get_string(&format!("branch.{}.remote", branch)).ok();
+    let upstream_ref = config.get_string(&format!("branch.{}.merge", branch)).ok();
+
+    if let (Some(remote), Some(remote_ref)) = (upstream_remote, upstream_ref) {
+        if remote != "." {
+            return Ok(PushTarget {
+                remote,
+                branch,
+                remote_ref,
+                set_upstream: false,
+            });
+        }
+    }
+
+    let remote = config
+        .get_string(&format!("branch.{}.pushRemote", branch))
+        .or_else(|_| config.get_string("remote.pushDefault"))
+        .ok()
+        .or_else(|| default_remote(repo))
+        .ok_or_else(|| anyhow!("No remote to push to; add one with `git remote add`"))?;
+    Ok(PushTarget {
+        remote,
+        remote_ref: format!("refs/heads/{}", branch),
+        branch,
+        set_upstream: true,
+    })
+}
+
+/// `origin`, or the only remote there is.
+fn default_remote(repo: &Repository) -> Option<String> {
+    let remotes = repo.remotes().ok()?;
+    let names: Vec<&str> = remotes.iter().flatten().collect();
+    if names.contains(&"origin") {
+        Some("origin".to_string())
+    } else if names.len() == 1 {
+        Some(names[0].to_string())
+    } else {
+        None
+    }
+}
+
+/// Push the checked-out branch, setting its upstream when it has none.
+/// `on_progress` gets `(stage, percent, line)` for each progress update.
+pub fn push_branch(
+    cwd: &Path,
+    opts: &PushOptions,
+    mut on_progress: impl FnMut(&str, Option<u8>, &str),
+) -> Result<PushResult> {
+    let target = push_target(&open_repo(cwd)?)?;
+
+    let mut cmd = silent_command("git");
+    cmd.arg("-C")
+        .arg(cwd)
+        .args(["push", "--porcelain", "--progress"]);
+    if opts.force_with_lease {
+        cmd.arg("--force-with-lease");
+    }
+    if target.set_upstream {
+        cmd.arg("--set-upstream");
+    }
+    cmd.arg(&target.remote)
+        .arg(format!("refs/heads/{}:{}", target.branch, target.remote_ref))
+        // No terminal to prompt on; fail instead of hanging on credentials
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    let mut child = cmd
+        .spawn()
+        .map_err(|e| anyhow!("Failed to run git push: {}", e))?;
+    let mut stdout = child.stdout.take().ok_or_else(|| anyhow!("git push has no stdout"))?;
+    let stdout_reader = std::thread::spawn(move || {
+        let mut out = String::new();
+        let _ = stdout.read_to_string(&mut out);
+        out
+    });
+
+    // Progress is redrawn with '\r', so split on both line endings
+    let mut output = Vec::new();
+    if let Some(mut stderr) = child.stderr.take() {
+        let mut pending = Vec::new();
+        let mut buf = [0u8; 4096];
+        loop {
+            let n = match stderr.read(&mut buf) {
+                Ok(0) | Err(_) => break,
+                Ok(n) => n,
+            };
+            for &b in &buf[..n] {
+                if b == b'\r' || b == b'\n' {
+                    let line = String::from_utf8_lossy(&pending).trim_end().to_string();
+                    pending.clear();
+                    handle_stderr_line(line, &mut output, &mut on_progress);
+                } else {
+                    pending.push(b);
+                }
+            }
+        }
+        let line = String::from_utf8_lossy(&pending).trim_end().to_string();
+        handle_stderr_line(line, &mut output, &mut on_progress);
+    }
+
+    let status = child.wait()?;
+    let stdout = stdout_reader.join().unwrap_or_default();
+    let mut refs: Vec<PushRefUpdate> = stdout.lines().filter_map(parse_porcelain_line).collect();
+    let output = output.join("\n");
+    // Hosts that decline through a hook name the protection only in `remote:` lines
+    if output.to_lowercase().contains("protected branch") {
+        for r in &mut refs {
+            if r.rejection == Some(PushRejection::RemoteRejected) {
+                r.rejection = Some(PushRejection::ProtectedBranch);
+            }
+        }
+    }
+    if !status.success() && refs.is_empty() {
+        bail!(if output.is_empty() {
+            format!("git push exited with {}", status)
+        } else {
+            output
+        });
+    }
+
+    let rejected = refs.iter().any(|r| r.status == PushRefStatus::Rejected);
+    Ok(PushResult {
+        remote: target.remote,
+        branch: target.branch,
+        upstream_set: target.set_upstream && !rejected && status.success(),
+        refs,
+        rejected,
+        output,
+    })
+}
+
+fn handle_stderr_line(
+    line: String,
+    output: &mut Vec<String>,
+    on_progress: &mut impl FnMut(&str, Option<u8>, &str),
+) {
+    if line.is_empty() {
+        return;
+    }
+    match parse_progress(&line) {
+        Some((stage, percent)) => on_progress(&stage, percent, &line),
+        None => output.push(line),
+    }
+}
+
+/// "Writing objects:  45% (9/20)" or "remote: Resolving deltas: 100% (1/1)"
+/// into the stage name and percentage.
+fn parse_progress(line: &str) -> Option<(String, Option<u8>)> {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    let re = RE.get_or_init(|| {
+        Regex::new(r"^(?:remote: )?([A-Z][A-Za-z ]+):\s+(?:(\d{1,3})% \(\d+/\d+\)|\d+)").unwrap()
+    });
+    let caps = re.captures(line)?;
+    let percent = caps.get(2).and_then(|m| m.as_str().parse().ok());
+    Some((caps[1].to_string(), percent))
+}
+
+/// One `git push --porcelain` ref line: "<flag>\t<from>:<to>\t<summary>".
+fn parse_porcelain_line(line: &str) -> Option<PushRefUpdate> {
+    let mut parts = line.splitn(3, '\t');
+    let flag = parts.next()?;
+    let (local_ref, remote_ref) = parts.next()?.split_once(':')?;
+    let summary = parts.next().unwrap_or_default().to_string();
+    let status = match flag {
+        " " => PushRefStatus::FastForward,
+        "+" => PushRefStatus::Forced,
+        "*" => PushRefStatus::New,
+        "-" => PushRefStatus::Deleted,
+        "=" => PushRefStatus::UpToDate,
+        "!" => PushRefStatus::Rejected,
+        _ => return None,
+    };
+    let rejection = (status == PushRefStatus::Rejected).then(|| rejection_reason(&summary));
+    Some(PushRefUpdate {
+        local_ref: local_ref.to_string(),
+        remote_ref: remote_ref.to_string(),
+        status,
+        rejection,
+        summary,
+    })
+}
+
+fn rejection_reason(summary: &str) -> PushRejection {
+    let lower = summary.to_lowercase();
+    if lower.contains("protected") {
+        PushRejection::ProtectedBranch
+    } else if lower.contains("stale info") {
+        PushRejection::StaleLease
+    } else if lower.contains("non-fast-forward") {
+        PushRejection::NonFastForward
+    } else if lower.contains("fetch first") {
+        PushRejection::FetchFirst
+    } else if lower.contains("[remote rejected]") {
+        PushRejection::RemoteRejected
+    } else {
+        PushRejection::Other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status_of(line: &str) -> (PushRefStatus, Option<PushRejection>) {
+        let update = parse_porcelain_line(line).expect("ref line");
+        (update.status, update.rejection)
+    }
+
+    #[test]
+    fn test_porcelain_non_fast_forward() {
+        let update = parse_porcelain_line("!\trefs/heads/main:refs/heads/main\t[rejected] (non-fast-forward)").unwrap();
+        assert_eq!(update.local_ref, "refs/heads/main");
+        assert_eq!(update.remote_ref, "refs/heads/main");
+        assert_eq!(update.status, PushRefStatus::Rejected);
+        assert_eq!(update.rejection, Some(PushRejection::NonFastForward));
+        assert_eq!(update.summary, "[rejected] (non-fast-forward)");
+        assert_eq!(
+            status_of("!\trefs/heads/main:refs/heads/main\t[rejected] (fetch first)"),
+            (PushRefStatus::Rejected, Some(PushRejection::FetchFirst))
+        );
+    }
+
+    #[test]
+    fn test_porcelain_remote_rejected() {
+        assert_eq!(
+            status_of("!\trefs/heads/main:refs/heads/main\t[remote rejected] (protected branch hook declined)"),
+            (PushRefStatus::Rejected, Some(PushRejection::ProtectedBranch))
+        );
+        assert_eq!(
+            status_of("!\trefs/heads/main:refs/heads/main\t[remote rejected] (pre-receive hook declined)"),
+            (PushRefStatus::Rejected, Some(PushRejection::RemoteRejected))
+        );
+    }
+
+    #[test]
+    fn test_porcelain_stale_lease() {
+        assert_eq!(
+            status_of("!\trefs/heads/topic:refs/heads/topic\t[rejected] (stale info)"),
+            (PushRefStatus::Rejected, Some(PushRejection::StaleLease))
+        );
+    }
+
+    #[test]
+    fn test_porcelain_accepted_updates() {
+        let update = parse_porcelain_line("*\trefs/heads/topic:refs/heads/topic\t[new branch]").unwrap();
+        assert_eq!((update.status, update.rejection), (PushRefStatus::New, None));
+        assert_eq!(update.summary, "[new branch]");
+        assert_eq!(
+            status_of("=\trefs/heads/main:refs/heads/main\t[up to date]"),
+            (PushRefStatus::UpToDate, None)
+        );
+        assert_eq!(
+            status_of(" \trefs/heads/main:refs/heads/main\t1a2b3c4..5d6e7f8"),
+            (PushRefStatus::FastForward, None)
+        );
+        assert_eq!(
+            status_of("+\trefs/heads/main:refs/heads/main\t1a2b3c4...5d6e7f8 (forced update)"),
+            (PushRefStatus::Forced, None)
+        );
+        let deleted = parse_porcelain_line("-\t:refs/heads/old\t[deleted]").unwrap();
+        assert_eq!((deleted.status, deleted.local_ref.as_str()), (PushRefStatus::Deleted, ""));
+    }
+
+    #[test]
+    fn test_porcelain_other_lines() {
+        assert!(parse_porcelain_line("To github.com:acme/widgets.git").is_none());
+        assert!(parse_porcelain_line("Done").is_none());
+    }
+
+    #[test]
+    fn test_progress() {
+        assert_eq!(
+            parse_progress("Writing objects:  45% (9/20)"),
+            Some(("Writing objects".to_string(), Some(45)))
+        );
+        assert_eq!(
+            parse_progress("Writing objects: 100% (20/20), 2.31 KiB | 2.31 MiB/s, done."),
+            Some(("Writing objects".to_string(), Some(100)))
+        );
+        assert_eq!(
+            parse_progress("Enumerating objects: 5, done."),
+            Some(("Enumerating objects".to_string(), None))
+        );
+        assert_eq!(
+            parse_progress("remote: Resolving deltas: 100% (1/1), completed with 1 local object."),
+            Some(("Resolving deltas".to_string(), Some(100)))
+        );
+    }
+
+    #[test]
+    fn test_progress_other_lines() {
+        assert_eq!(parse_progress("remote: Create a pull request for 'topic' on GitHub by visiting:"), None);
+        assert_eq!(parse_progress("remote:"), None);
+        assert_eq!(parse_progress("error: failed to push some refs to 'github.com:acme/widgets.git'"), None);
+        assert_eq!(parse_progress("To github.com:acme/widgets.git"), None);
+    }
+}
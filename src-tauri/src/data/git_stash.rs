@@ -0,0 +1,189 @@
+use std::path::Path;
+
+use anyhow::{anyhow, bail, Result};
+use git2::{DiffFindOptions, ErrorCode, Oid, Repository, StashFlags};
+
+use crate::data::git::{diff_text, open_repo, parse_diff_output, relative_date};
+use crate::models::git::{DiffLine, StashEntry, StashOptions};
+
+/// Split a stash message into its branch and the rest. libgit2 and git write
+/// "On <branch>: <message>", or "WIP on <branch>: <short id> <summary>"
+/// when no message was given.
+fn parse_stash_message(raw: &str) -> (Option<String>, String) {
+    if let Some(rest) = raw.strip_prefix("WIP on ") {
+        // The summary may contain ": " too; the branch ends where the short id starts
+        let split = rest.match_indices(": ").find(|&(i, sep)| {
+            let after = &rest[i + sep.len()..];
+            let hex = after.bytes().take_while(u8::is_ascii_hexdigit).count();
+            hex >= 4 && after[hex..].starts_with(' ')
+        });
+        if let Some((i, sep)) = split {
+            return (Some(rest[..i].to_string()), rest[i + sep.len()..].to_string());
+        }
+    }
+    // Ref names can't contain ':', so otherwise the first ": " ends the branch
+    let rest = raw
+        .strip_prefix("WIP on ")
+        .or_else(|| raw.strip_prefix("On "));
+    match rest.and_then(|r| r.split_once(": ")) {
+        Some((branch, message)) => (Some(branch.to_string()), message.to_string()),
+        None => (None, raw.to_string()),
+    }
+}
+
+fn stash_ids(repo: &mut Repository) -> Result<Vec<(String, Oid)>> {
+    let mut ids = Vec::new();
+    repo.stash_foreach(|_, message, id| {
+        ids.push((message.to_string(), *id));
+        true
+    })?;
+    Ok(ids)
+}
+
+pub fn list_stashes(cwd: &Path) -> Result<Vec<StashEntry>> {
+    let mut repo = open_repo(cwd)?;
+    let now = chrono::Utc::now().timestamp();
+    let mut entries = Vec::new();
+    for (index, (raw, id)) in stash_ids(&mut repo)?.into_iter().enumerate() {
+        let commit = repo.find_commit(id)?;
+        let (branch, message) = parse_stash_message(&raw);
+        let timestamp = commit.time().seconds();
+        entries.push(StashEntry {
+            index,
+            hash: id.to_string(),
+            message,
+            branch,
+            timestamp,
+            date: relative_date(now - timestamp),
+            // Parents: HEAD, the index, then untracked files if stashed
+            includes_untracked: commit.parent_count() > 2,
+        });
+    }
+    Ok(entries)
+}
+
+/// Position of the stash with commit `hash`, looked up fresh because indices
+/// shift whenever a stash is added or dropped.
+fn stash_index(repo: &mut Repository, hash: &str) -> Result<usize> {
+    stash_ids(repo)?
+        .iter()
+        .position(|(_, id)| id.to_string() == hash)
+        .ok_or_else(|| anyhow!("No stash with id {}", hash))
+}
+
+pub fn save_stash(cwd: &Path, opts: &StashOptions) -> Result<StashEntry> {
+    let mut repo = open_repo(cwd)?;
+    let stasher = repo
+        .signature()
+        .map_err(|_| anyhow!("Set user.name and user.email in git config to stash"))?;
+    let mut flags = StashFlags::DEFAULT;
+    if opts.include_untracked {
+        flags |= StashFlags::INCLUDE_UNTRACKED;
+    }
+    if opts.keep_index {
+        flags |= StashFlags::KEEP_INDEX;
+    }
+    let message = opts.message.as_deref().map(str::trim).filter(|m| !m.is_empty());
+    let id = match repo.stash_save2(&stasher, message, Some(flags)) {
+        Ok(id) => id,
+        Err(e) if e.code() == ErrorCode::NotFound => bail!("No local changes to stash"),
+        Err(e) => return Err(e.into()),
+    };
+    list_stashes(cwd)?
+        .into_iter()
+        .find(|s| s.hash == id.to_string())
+        .ok_or_else(|| anyhow!("Stash {} was not recorded", id))
+}
+
+/// The stash's changes as `git stash show -p --include-untracked` prints them.
+pub fn show_stash(cwd: &Path, hash: &str) -> Result<Vec<DiffLine>> {
+    let repo = open_repo(cwd)?;
+    let commit = repo.find_commit(Oid::from_str(hash)?)?;
+    let base = commit.parent(0)?.tree()?;
+    let mut diff = repo.diff_tree_to_tree(Some(&base), Some(&commit.tree()?), None)?;
+    diff.find_similar(Some(DiffFindOptions::new().renames(true)))?;
+    let mut text = diff_text(&diff, None)?;
+    if let Ok(untracked) = commit.parent(2) {
+        let added = repo.diff_tree_to_tree(None, Some(&untracked.tree()?), None)?;
+        text.push_str(&diff_text(&added, None)?);
+    }
+    Ok(parse_diff_output(&text))
+}
+
+fn apply_error(e: git2::Error) -> anyhow::Error {
+    if e.code() == ErrorCode::Conflict || e.code() == ErrorCode::MergeConflict {
+        anyhow!("The stash conflicts with local changes; commit or stash them first")
+    } else {
+        e.into()
+    }
+}
+
+/// Re-apply a stash to the working tree and keep it in the list.
+pub fn apply_stash(cwd: &Path, hash: &str) -> Result<()> {
+    let mut repo = open_repo(cwd)?;
+    let index = stash_index(&mut repo, hash)?;
+    repo.stash_apply(index, None).map_err(apply_error)
+}
+
+/// Apply a stash and drop it if that succeeded.
+pub fn pop_stash(cwd: &Path, hash: &str) -> Result<()> {
+    let mut repo = open_repo(cwd)?;
+    let index = stash_index(&mut repo, hash)?;
+    repo.stash_pop(index, None).map_err(apply_error)
+}
+
+pub fn drop_stash(cwd: &Path, hash: &str) -> Result<()> {
+    let mut repo = open_repo(cwd)?;
+    let index = stash_index(&mut repo, hash)?;
+    repo.stash_drop(index)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split(branch: Option<&str>, message: &str) -> (Option<String>, String) {
+        (branch.map(str::to_string), message.to_string())
+    }
+
+    #[test]
+    fn test_stash_message_with_message() {
+        assert_eq!(parse_stash_message("On main: half-done refactor"), split(Some("main"), "half-done refactor"));
+        assert_eq!(
+            parse_stash_message("On feature/login: Fix: keep the session"),
+            split(Some("feature/login"), "Fix: keep the session")
+        );
+    }
+
+    #[test]
+    fn test_stash_message_wip() {
+        assert_eq!(
+            parse_stash_message("WIP on main: 1a2b3c4 Add the parser"),
+            split(Some("main"), "1a2b3c4 Add the parser")
+        );
+        assert_eq!(
+            parse_stash_message("WIP on main: 1a2b3c4 fix: keep the session"),
+            split(Some("main"), "1a2b3c4 fix: keep the session")
+        );
+        assert_eq!(
+            parse_stash_message("WIP on (no branch): 1a2b3c4 Add the parser"),
+            split(Some("(no branch)"), "1a2b3c4 Add the parser")
+        );
+    }
+
+    #[test]
+    fn test_stash_message_wip_branch_with_separator() {
+        assert_eq!(
+            parse_stash_message("WIP on release: v2: 1a2b3c4 Bump: version"),
+            split(Some("release: v2"), "1a2b3c4 Bump: version")
+        );
+    }
+
+    #[test]
+    fn test_stash_message_without_prefix() {
+        assert_eq!(parse_stash_message("autostash"), split(None, "autostash"));
+        assert_eq!(parse_stash_message("Fix: keep the session"), split(None, "Fix: keep the session"));
+        assert_eq!(parse_stash_message("On main"), split(None, "On main"));
+    }
+}
@@ -0,0 +1,426 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail, Result};
+use git2::{BranchType, Oid, Repository, StatusOptions};
+
+use crate::data::git::open_repo;
+use crate::data::path_encoding::encode_project_path;
+use crate::models::git::{RemoveWorktreeOptions, WorktreeInfo, WorktreeSource};
+use crate::utils::silent_command;
+
+/// Run `git -C <cwd> <args>` and return its stdout, or its stderr as the error.
+fn git(cwd: &Path, args: &[&str]) -> Result<String> {
+    let output = silent_command("git")
+        .arg("-C")
+        .arg(cwd)
+        .args(args)
+        .output()
+        .map_err(|e| anyhow!("Failed to run git: {}", e))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("git {} failed: {}", args[..args.len().min(2)].join(" "), stderr.trim());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Parses `git worktree list --porcelain` stdout. `is_merged` is left false.
+fn parse_worktrees(output: &str) -> Vec<WorktreeInfo> {
+    let mut worktrees = Vec::new();
+    let mut is_first = true;
+
+    // Blocks are separated by blank lines
+    for block in output.split("\n\n") {
+        let block = block.trim();
+        if block.is_empty() {
+            continue;
+        }
+
+        let mut path = String::new();
+        let mut head = String::new();
+        let mut branch = String::new();
+        let mut is_prunable = false;
+        let mut lock = None;
+
+        for line in block.lines() {
+            if let Some(v) = line.strip_prefix("worktree ") {
+                path = v.trim().to_string();
+            } else if let Some(v) = line.strip_prefix("HEAD ") {
+                let sha = v.trim();
+                head = if sha.len() >= 8 {
+                    sha[..8].to_string()
+                } else {
+                    sha.to_string()
+                };
+            } else if let Some(v) = line.strip_prefix("branch ") {
+                let b = v.trim();
+                branch = b.strip_prefix("refs/heads/").unwrap_or(b).to_string();
+            } else if line.starts_with("prunable") {
+                is_prunable = true;
+            } else if line == "locked" {
+                lock = Some(None);
+            } else if let Some(reason) = line.strip_prefix("locked ") {
+                lock = Some(Some(reason.trim().to_string()));
+            } else if line == "detached" {
+                branch = "(detached)".to_string();
+            }
+        }
+
+        if path.is_empty() {
+            continue;
+        }
+
+        worktrees.push(WorktreeInfo {
+            path,
+            head,
+            branch,
+            is_main: is_first,
+            is_prunable,
+            is_locked: lock.is_some(),
+            lock_reason: lock.flatten(),
+            is_merged: false,
+        });
+        is_first = false;
+    }
+
+    worktrees
+}
+
+/// Whether local `branch` has been merged into `base`: it has commits of its
+/// own and its tip is behind `base`'s. A branch still at the commit it was
+/// created from (nothing done yet) is not, even once `base` moves on.
+fn is_merged(repo: &Repository, branch: &str, base: &str) -> bool {
+    let tip = |name: &str| {
+        repo.find_branch(name, BranchType::Local)
+            .ok()?
+            .get()
+            .peel_to_commit()
+            .ok()
+            .map(|c| c.id())
+    };
+    let (Some(branch_tip), Some(base_tip)) = (tip(branch), tip(base)) else {
+        return false;
+    };
+    // Without a reflog there's no telling a fresh branch from a merged one
+    let Some(created_at) = creation_point(repo, branch) else {
+        return false;
+    };
+    branch_tip != base_tip
+        && branch_tip != created_at
+        && repo.graph_descendant_of(base_tip, branch_tip).unwrap_or(false)
+}
+
+/// Commit local `branch` was created at: the oldest entry of its reflog.
+fn creation_point(repo: &Repository, branch: &str) -> Option<Oid> {
+    let reflog = repo.reflog(&format!("refs/heads/{}", branch)).ok()?;
+    let oldest = reflog.get(reflog.len().checked_sub(1)?)?;
+    Some(oldest.id_new())
+}
+
+/// Every worktree of the repository containing `project_path`, main first.
+pub fn list_worktrees(project_path: &Path) -> Result<Vec<WorktreeInfo>> {
+    let mut worktrees = parse_worktrees(&git(project_path, &["worktree", "list", "--porcelain"])?);
+    let repo = open_repo(project_path)?;
+    let base = worktrees
+        .first()
+        .map(|w| w.branch.clone())
+        .filter(|b| !b.is_empty() && b != "(detached)");
+    if let Some(base) = base {
+        for wt in worktrees.iter_mut().skip(1) {
+            if !wt.branch.is_empty() && wt.branch != base {
+                wt.is_merged = is_merged(&repo, &wt.branch, &base);
+            }
+        }
+    }
+    Ok(worktrees)
+}
+
+/// The path of the main worktree root.
+pub fn main_worktree_root(project_path: &Path) -> Result<PathBuf> {
+    let output = git(project_path, &["worktree", "list", "--porcelain"])?;
+    output
+        .lines()
+        .find_map(|line| line.strip_prefix("worktree "))
+        .map(|path| PathBuf::from(path.trim()))
+        .ok_or_else(|| anyhow!("Could not find main worktree"))
+}
+
+/// Reads `.worktree_copy` from `project_path` as a JSON array of strings.
+pub fn read_worktree_copy(project_path: &Path) -> Result<Vec<String>> {
+    let copy_file = project_path.join(".worktree_copy");
+    if !copy_file.exists() {
+        return Ok(vec![]);
+    }
+    let content = fs::read_to_string(&copy_file)
+        .map_err(|e| anyhow!("Failed to read .worktree_copy: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| anyhow!("Failed to parse .worktree_copy: {}", e))
+}
+
+pub fn write_worktree_copy(project_path: &Path, entries: &[String]) -> Result<()> {
+    let copy_file = project_path.join(".worktree_copy");
+    let content = serde_json::to_string_pretty(entries)
+        .map_err(|e| anyhow!("Failed to serialize entries: {}", e))?;
+    fs::write(&copy_file, content).map_err(|e| anyhow!("Failed to write .worktree_copy: {}", e))
+}
+
+/// Recursively copies `src` directory into `dst`.
+fn copy_dir_recursive(src: &Path, dst: &Path) -> std::io::Result<()> {
+    if !dst.exists() {
+        fs::create_dir_all(dst)?;
+    }
+    for entry in fs::read_dir(src)? {
+        let entry = entry?;
+        let src_path = entry.path();
+        let dst_path = dst.join(entry.file_name());
+        if src_path.is_dir() {
+            copy_dir_recursive(&src_path, &dst_path)?;
+        } else {
+            fs::copy(&src_path, &dst_path)?;
+        }
+    }
+    Ok(())
+}
+
+/// Create a worktree next to the project, named after the branch, and copy
+/// in the files listed in `.worktree_copy`. Returns the new path, followed
+/// by a line of warnings if some files could not be copied.
+pub fn create_worktree(project_path: &Path, branch_name: &str, source: &WorktreeSource) -> Result<String> {
+    // Sanitize branch_name for use as a directory name: / and spaces → -, lowercase
+    let dir_name = branch_name.replace(['/', ' '], "-").to_lowercase();
+
+    // Compute parent dir of project
+    let parent = project_path
+        .parent()
+        .ok_or_else(|| anyhow!("Cannot determine parent directory of {}", project_path.display()))?;
+
+    // worktree_path = parent / sanitized_name
+    let worktree_path = parent.join(&dir_name);
+    let worktree_path_str = worktree_path
+        .to_str()
+        .ok_or_else(|| anyhow!("Invalid path encoding"))?
+        .to_string();
+
+    match source {
+        WorktreeSource::NewBranch => {
+            git(project_path, &["worktree", "add", &worktree_path_str, "-b", branch_name])?;
+        }
+        WorktreeSource::Local => {
+            git(project_path, &["worktree", "add", &worktree_path_str, branch_name])?;
+        }
+        WorktreeSource::Remote { branch } => {
+            git(
+                project_path,
+                &["worktree", "add", "--track", "-b", branch_name, &worktree_path_str, branch],
+            )?;
+        }
+        WorktreeSource::PullRequest { number, remote } => {
+            let remote = remote.as_deref().unwrap_or("origin");
+            // Fetched straight into the new branch, which must not exist yet
+            let refspec = format!("pull/{}/head:refs/heads/{}", number, branch_name);
+            git(project_path, &["fetch", remote, &refspec])?;
+            git(project_path, &["worktree", "add", &worktree_path_str, branch_name])?;
+        }
+    }
+
+    // Post-creation: copy files listed in .worktree_copy from base project
+    let mut copy_warnings: Vec<String> = Vec::new();
+    if let Ok(entries) = read_worktree_copy(project_path) {
+        for entry in entries {
+            let src = project_path.join(&entry);
+            let dst = worktree_path.join(&entry);
+            if src.is_file() {
+                if let Some(parent) = dst.parent() {
+                    if let Err(e) = fs::create_dir_all(parent) {
+                        copy_warnings.push(format!("mkdir {}: {}", parent.display(), e));
+                        continue;
+                    }
+                }
+                if let Err(e) = fs::copy(&src, &dst) {
+                    copy_warnings.push(format!("copy {}: {}", entry, e));
+                }
+            } else if src.is_dir() {
+                if let Err(e) = copy_dir_recursive(&src, &dst) {
+                    copy_warnings.push(format!("copy dir {}: {}", entry, e));
+                }
+            }
+        }
+    }
+
+    if copy_warnings.is_empty() {
+        Ok(worktree_path_str)
+    } else {
+        Ok(format!(
+            "{}\nWarnings copying files: {}",
+            worktree_path_str,
+            copy_warnings.join("; ")
+        ))
+    }
+}
+
+/// The listed worktree at `path`, compared after resolving symlinks since
+/// git prints canonical paths.
+fn find_worktree(project_path: &Path, path: &str) -> Result<WorktreeInfo> {
+    let canonical = |p: &Path| fs::canonicalize(p).unwrap_or_else(|_| p.to_path_buf());
+    let wanted = canonical(Path::new(path));
+    list_worktrees(project_path)?
+        .into_iter()
+        .find(|wt| canonical(Path::new(&wt.path)) == wanted || wt.path == path.replace('\\', "/"))
+        .ok_or_else(|| anyhow!("Not a worktree of this repository: {}", path))
+}
+
+/// Number of modified, staged or untracked (not ignored) files in a worktree.
+fn dirty_count(path: &Path) -> Result<usize> {
+    let repo = Repository::open(path)?;
+    let mut opts = StatusOptions::new();
+    opts.include_untracked(true).include_ignored(false);
+    let count = repo.statuses(Some(&mut opts))?.len();
+    Ok(count)
+}
+
+/// Remove a linked worktree. Refuses the main worktree, locked worktrees and,
+/// unless forced, worktrees with changes. The branch is kept.
+pub fn remove_worktree(project_path: &Path, path: &str, opts: &RemoveWorktreeOptions) -> Result<()> {
+    let wt = find_worktree(project_path, path)?;
+    if wt.is_main {
+        bail!("Cannot remove the main worktree");
+    }
+    if wt.is_locked {
+        bail!(
+            "Worktree is locked{}; unlock it first",
+            wt.lock_reason.map(|r| format!(" ({})", r)).unwrap_or_default()
+        );
+    }
+    let dir = Path::new(&wt.path);
+    let exists = dir.is_dir();
+    if exists && !opts.force {
+        let dirty = dirty_count(dir)?;
+        if dirty > 0 {
+            bail!(
+                "{} has {} uncommitted change{}; force removal to discard {}",
+                wt.path,
+                dirty,
+                if dirty == 1 { "" } else { "s" },
+                if dirty == 1 { "it" } else { "them" }
+            );
+        }
+    }
+
+    let root = main_worktree_root(project_path)?;
+    // A worktree whose directory is already gone only needs its entry dropped
+    if exists && !opts.force {
+        git(&root, &["worktree", "remove", &wt.path])?;
+    } else {
+        git(&root, &["worktree", "remove", "--force", &wt.path])?;
+    }
+
+    if opts.clean_claude_data {
+        let projects = crate::paths::claude_home()
+            .map_err(|e| anyhow!(e))?
+            .join("projects");
+        // Sessions are filed under the path Claude was started in, which may
+        // be the one given here rather than git's canonical one
+        for dir in [path, wt.path.as_str()] {
+            let data = projects.join(encode_project_path(Path::new(dir)));
+            if data.is_dir() {
+                fs::remove_dir_all(&data).map_err(|e| {
+                    anyhow!("Removed the worktree but not its Claude data at {}: {}", data.display(), e)
+                })?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Drop the entries of worktrees whose directories are gone, as
+/// `git worktree prune` does. Locked entries are kept. Returns their paths.
+pub fn prune_worktrees(project_path: &Path) -> Result<Vec<String>> {
+    let before: Vec<String> = list_worktrees(project_path)?
+        .into_iter()
+        .filter(|wt| wt.is_prunable)
+        .map(|wt| wt.path)
+        .collect();
+    if before.is_empty() {
+        return Ok(vec![]);
+    }
+    git(project_path, &["worktree", "prune"])?;
+    let after = list_worktrees(project_path)?;
+    Ok(before
+        .into_iter()
+        .filter(|path| !after.iter().any(|wt| &wt.path == path))
+        .collect())
+}
+
+/// Lock a worktree so it can't be removed or pruned, e.g. while it lives on
+/// removable storage.
+pub fn lock_worktree(project_path: &Path, path: &str, reason: Option<&str>) -> Result<()> {
+    let wt = find_worktree(project_path, path)?;
+    match reason.map(str::trim).filter(|r| !r.is_empty()) {
+        Some(reason) => git(project_path, &["worktree", "lock", "--reason", reason, &wt.path])?,
+        None => git(project_path, &["worktree", "lock", &wt.path])?,
+    };
+    Ok(())
+}
+
+pub fn unlock_worktree(project_path: &Path, path: &str) -> Result<()> {
+    let wt = find_worktree(project_path, path)?;
+    git(project_path, &["worktree", "unlock", &wt.path])?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::Signature;
+
+    /// Commit an empty change on top of `refname` (created if missing).
+    fn commit_on(repo: &Repository, refname: &str, parent: Oid, message: &str) -> Oid {
+        let sig = Signature::now("Ada", "ada@example.com").unwrap();
+        let parent = repo.find_commit(parent).unwrap();
+        let tree = parent.tree().unwrap();
+        repo.commit(Some(refname), &sig, &sig, message, &tree, &[&parent]).unwrap()
+    }
+
+    fn temp_repo() -> (PathBuf, Repository, Oid) {
+        let dir = std::env::temp_dir().join(format!(
+            "theassociate-worktree-test-{}-{}",
+            std::process::id(),
+            std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_nanos()
+        ));
+        let repo = Repository::init(&dir).unwrap();
+        let root = {
+            let sig = Signature::now("Ada", "ada@example.com").unwrap();
+            let tree = repo.find_tree(repo.index().unwrap().write_tree().unwrap()).unwrap();
+            repo.commit(Some("refs/heads/main"), &sig, &sig, "root", &tree, &[]).unwrap()
+        };
+        (dir, repo, root)
+    }
+
+    #[test]
+    fn test_fresh_branch_not_merged_after_base_moves() {
+        let (dir, repo, root) = temp_repo();
+        repo.branch("feature", &repo.find_commit(root).unwrap(), false).unwrap();
+        let fresh = is_merged(&repo, "feature", "main");
+        commit_on(&repo, "refs/heads/main", root, "main moves on");
+        let after_base_moved = is_merged(&repo, "feature", "main");
+        std::fs::remove_dir_all(&dir).ok();
+        assert!(!fresh);
+        assert!(!after_base_moved);
+    }
+
+    #[test]
+    fn test_branch_with_commits_merged_into_base() {
+        let (dir, repo, root) = temp_repo();
+        repo.branch("feature", &repo.find_commit(root).unwrap(), false).unwrap();
+        let work = commit_on(&repo, "refs/heads/feature", root, "feature work");
+        let unmerged = is_merged(&repo, "feature", "main");
+        // Fast-forward main to the feature, then move it on
+        repo.reference("refs/heads/main", work, true, "merge feature: Fast-forward").unwrap();
+        let at_same_commit = is_merged(&repo, "feature", "main");
+        commit_on(&repo, "refs/heads/main", work, "after the merge");
+        let merged = is_merged(&repo, "feature", "main");
+        std::fs::remove_dir_all(&dir).ok();
+        assert!(!unmerged);
+        assert!(!at_same_commit);
+        assert!(merged);
+    }
+}
@@ -0,0 +1,90 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+use crate::models::launch_profile::{LaunchProfile, LaunchProfileScope};
+
+const PROFILES_FILE: &str = "launch-profiles.json";
+
+/// File holding the profiles for `scope`. Project profiles live in the
+/// project's `.claude/` directory so they can be committed with the repo.
+pub fn profiles_path(
+    theassociate_dir: &Path,
+    project_path: Option<&str>,
+    scope: LaunchProfileScope,
+) -> Option<PathBuf> {
+    match scope {
+        LaunchProfileScope::Global => Some(theassociate_dir.join(PROFILES_FILE)),
+        LaunchProfileScope::Project => {
+            project_path.map(|p| PathBuf::from(p).join(".claude").join(PROFILES_FILE))
+        }
+    }
+}
+
+fn load_file(path: &Path, scope: LaunchProfileScope) -> Vec<LaunchProfile> {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|s| serde_json::from_str::<Vec<LaunchProfile>>(&s).ok())
+        .unwrap_or_default()
+        .into_iter()
+        .map(|mut p| {
+            p.scope = scope;
+            p
+        })
+        .collect()
+}
+
+/// Global profiles followed by the project's. A project profile with the same
+/// id as a global one replaces it in place.
+pub fn load_launch_profiles(theassociate_dir: &Path, project_path: Option<&str>) -> Vec<LaunchProfile> {
+    let mut profiles = load_file(&theassociate_dir.join(PROFILES_FILE), LaunchProfileScope::Global);
+    if let Some(path) = profiles_path(theassociate_dir, project_path, LaunchProfileScope::Project) {
+        for project in load_file(&path, LaunchProfileScope::Project) {
+            match profiles.iter_mut().find(|p| p.id == project.id) {
+                Some(existing) => *existing = project,
+                None => profiles.push(project),
+            }
+        }
+    }
+    profiles
+}
+
+pub fn save_launch_profiles(path: &Path, profiles: &[LaunchProfile]) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let json = serde_json::to_string_pretty(profiles)?;
+    std::fs::write(path, json + "\n")?;
+    Ok(())
+}
+
+fn non_empty(value: &Option<String>) -> Option<&str> {
+    value.as_deref().map(str::trim).filter(|v| !v.is_empty())
+}
+
+/// `claude` CLI arguments for a profile, in a stable order.
+pub fn launch_args(profile: &LaunchProfile) -> Vec<String> {
+    let mut args = Vec::new();
+    if let Some(model) = non_empty(&profile.model) {
+        args.extend(["--model".to_string(), model.to_string()]);
+    }
+    if let Some(mode) = non_empty(&profile.permission_mode) {
+        args.extend(["--permission-mode".to_string(), mode.to_string()]);
+    }
+    for dir in profile.add_dirs.iter().filter(|d| !d.trim().is_empty()) {
+        args.extend(["--add-dir".to_string(), dir.clone()]);
+    }
+    if let Some(config) = non_empty(&profile.mcp_config) {
+        args.extend(["--mcp-config".to_string(), config.to_string()]);
+    }
+    if let Some(prompt) = non_empty(&profile.append_system_prompt) {
+        args.extend(["--append-system-prompt".to_string(), prompt.to_string()]);
+    }
+    if !profile.allowed_tools.is_empty() {
+        args.extend(["--allowedTools".to_string(), profile.allowed_tools.join(",")]);
+    }
+    if !profile.disallowed_tools.is_empty() {
+        args.extend(["--disallowedTools".to_string(), profile.disallowed_tools.join(",")]);
+    }
+    args
+}
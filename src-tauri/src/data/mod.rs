@@ -1,15 +1,28 @@
+pub mod auto_resume;
 pub mod claude_config;
+pub mod claude_homes;
+pub mod claude_settings;
 pub mod git;
+pub mod git_commit;
+pub mod git_conflict;
+pub mod git_log;
+pub mod git_push;
+pub mod git_stash;
+pub mod git_worktree;
 pub mod hook_state;
 pub mod inboxes;
+pub mod launch_profiles;
 pub mod notes;
 pub mod path_encoding;
 pub mod plans;
 pub mod projects;
+pub mod resource_limits;
 pub mod sessions;
 pub mod summaries;
 pub mod tasks;
 pub mod teams;
+pub mod terminal_profiles;
 pub mod todos;
 pub mod transcripts;
+pub mod trigger_rules;
 pub mod watcher_state;
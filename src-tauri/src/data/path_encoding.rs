@@ -1,29 +1,126 @@
 use std::path::Path;
 
-/// Encode an absolute Windows path to Claude's project directory name.
-///
-/// Rules:
-/// - Replace `:\` with `--`
-/// - Replace remaining `\` and `/` with `-`
-/// - Replace `.` and `_` with `-`
+/// Path conventions of the OS a path came from: Windows paths use `\` (or `/`)
+/// with a drive letter and compare case-insensitively, as do macOS paths on
+/// the default APFS volume; Linux paths are case-sensitive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Platform {
+    Windows,
+    MacOs,
+    Linux,
+}
+
+impl Platform {
+    pub fn current() -> Self {
+        if cfg!(windows) {
+            Platform::Windows
+        } else if cfg!(target_os = "macos") {
+            Platform::MacOs
+        } else {
+            Platform::Linux
+        }
+    }
+
+    fn case_insensitive(self) -> bool {
+        self != Platform::Linux
+    }
+}
+
+/// Encode an absolute path to Claude's project directory name, the way the
+/// CLI does: every character that isn't an ASCII letter or digit becomes `-`
+/// (one per UTF-16 unit, as in JavaScript). Trailing separators are ignored.
 ///
-/// Example: `C:\dev\profile-server` -> `C--dev-profile-server`
 /// Example: `C:\dev\apex_3.11.0` -> `C--dev-apex-3-11-0`
+/// Example: `/home/me/my.app` -> `-home-me-my-app`
 pub fn encode_project_path(path: &Path) -> String {
-    let s = path.to_string_lossy().to_string();
-    let s = s.replace('/', "\\");
+    let s = path.to_string_lossy();
+    let s = s.trim_end_matches(['/', '\\']);
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        if c.is_ascii_alphanumeric() {
+            out.push(c);
+        } else {
+            out.extend(std::iter::repeat_n('-', c.len_utf16()));
+        }
+    }
+    out
+}
+
+/// The encoding used before `encode_project_path` matched the CLI: only `:\`,
+/// separators, `.` and `_` were replaced, so spaces and other characters were
+/// kept. Only needed to find data dirs written under the old names.
+///
+/// Example: `C:\dev\my app_2` -> `C--dev-my app-2`
+pub fn legacy_encode_project_path(path: &Path) -> String {
+    let s = path.to_string_lossy().replace('/', "\\");
     let s = s.replace(":\\", "--");
-    let s = s.replace('\\', "-");
-    let s = s.replace('.', "-");
-    let s = s.replace('_', "-");
+    let s = s.replace(['\\', '.', '_'], "-");
     s.trim_end_matches('-').to_string()
 }
 
+/// Best-effort decode of an encoded project dir name back to a path, with
+/// forward slashes. Lossy: `-` in the original name, `.` and `_` all come back
+/// as separators, except that `--` after the start is read as `/.` (hidden
+/// directories such as `.worktrees`).
+///
+/// Example: `C--dev-ide` -> `C:/dev/ide`
+/// Example: `-home-me-app--worktrees-x` -> `/home/me/app/.worktrees/x`
+pub fn decode_dir_name(dir_name: &str) -> String {
+    let is_drive = |s: &str| {
+        let bytes = s.as_bytes();
+        bytes.len() >= 3 && bytes[0].is_ascii_alphabetic() && &bytes[1..3] == b"--"
+    };
+    let (prefix, rest) = if is_drive(dir_name) {
+        (format!("{}:/", &dir_name[..1]), &dir_name[3..])
+    } else if let Some(rest) = dir_name.strip_prefix('-') {
+        ("/".to_string(), rest)
+    } else {
+        (String::new(), dir_name)
+    };
+    format!("{}{}", prefix, rest.replace("--", "/.").replace('-', "/"))
+}
+
+/// Normalize a path for comparison on `platform`: forward slashes on Windows,
+/// no trailing separator, and lowercased where the file system ignores case.
+pub fn normalize_path_for(path: &str, platform: Platform) -> String {
+    let mut s = if platform == Platform::Windows {
+        path.replace('\\', "/")
+    } else {
+        path.to_string()
+    };
+    while s.len() > 1 && s.ends_with('/') && !(platform == Platform::Windows && s.ends_with(":/")) {
+        s.pop();
+    }
+    if platform.case_insensitive() {
+        s = s.to_lowercase();
+    }
+    s
+}
+
+/// `path` is `parent` itself or somewhere below it.
+pub fn is_within_for(path: &str, parent: &str, platform: Platform) -> bool {
+    let path = normalize_path_for(path, platform);
+    let parent = normalize_path_for(parent, platform);
+    path == parent
+        || path
+            .strip_prefix(&parent)
+            .is_some_and(|rest| rest.starts_with('/') || parent.ends_with('/'))
+}
+
+/// `is_within_for` on the platform we're running on.
+pub fn is_within(path: &str, parent: &str) -> bool {
+    is_within_for(path, parent, Platform::current())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::path::PathBuf;
 
+    fn same_path_for(a: &str, b: &str, platform: Platform) -> bool {
+        normalize_path_for(a, platform) == normalize_path_for(b, platform)
+    }
+
     #[test]
     fn test_simple_path() {
         let p = PathBuf::from(r"C:\dev\profile-server");
@@ -59,4 +156,93 @@ mod tests {
         let p = PathBuf::from(r"C:\dev\my_project\v1.2.3");
         assert_eq!(encode_project_path(&p), "C--dev-my-project-v1-2-3");
     }
+
+    #[test]
+    fn test_windows_spaces_and_forward_slashes() {
+        let p = PathBuf::from("C:/Users/Jane Doe/app/");
+        assert_eq!(encode_project_path(&p), "C--Users-Jane-Doe-app");
+    }
+
+    #[test]
+    fn test_linux_path() {
+        let p = PathBuf::from("/home/me/app");
+        assert_eq!(encode_project_path(&p), "-home-me-app");
+    }
+
+    #[test]
+    fn test_linux_hidden_dir_and_non_ascii() {
+        let p = PathBuf::from("/home/me/.config/café_app/");
+        assert_eq!(encode_project_path(&p), "-home-me--config-caf--app");
+    }
+
+    #[test]
+    fn test_macos_path() {
+        let p = PathBuf::from("/Users/keith/Code/my.app");
+        assert_eq!(encode_project_path(&p), "-Users-keith-Code-my-app");
+    }
+
+    #[test]
+    fn test_legacy_encoding_keeps_spaces() {
+        let p = PathBuf::from(r"C:\dev\my app_2\");
+        assert_eq!(legacy_encode_project_path(&p), "C--dev-my app-2");
+        assert_eq!(encode_project_path(&p), "C--dev-my-app-2");
+        let p = PathBuf::from("/home/me/app");
+        assert_eq!(legacy_encode_project_path(&p), encode_project_path(&p));
+    }
+
+    #[test]
+    fn test_decode_windows() {
+        assert_eq!(decode_dir_name("C--dev-ide"), "C:/dev/ide");
+        assert_eq!(
+            decode_dir_name("C--dev-app--worktrees-x"),
+            "C:/dev/app/.worktrees/x"
+        );
+    }
+
+    #[test]
+    fn test_decode_unix() {
+        assert_eq!(decode_dir_name("-home-me-app"), "/home/me/app");
+        assert_eq!(decode_dir_name("-Users-keith-Code"), "/Users/keith/Code");
+        assert_eq!(
+            decode_dir_name("-home-me-app--worktrees-x"),
+            "/home/me/app/.worktrees/x"
+        );
+    }
+
+    #[test]
+    fn test_decode_round_trips_simple_paths() {
+        for path in [r"C:\dev\ide", "/home/me/app", "/Users/keith/Code"] {
+            let decoded = decode_dir_name(&encode_project_path(&PathBuf::from(path)));
+            assert!(same_path_for(&decoded, path, Platform::Windows), "{}", path);
+        }
+    }
+
+    #[test]
+    fn test_compare_windows() {
+        let w = Platform::Windows;
+        assert!(same_path_for(r"C:\Dev\App", "c:/dev/app/", w));
+        assert!(is_within_for(r"C:\dev\app\src", "C:/dev/app", w));
+        assert!(is_within_for(r"C:\dev\app", r"C:\", w));
+        assert!(!is_within_for(r"C:\dev\app-2", r"C:\dev\app", w));
+    }
+
+    #[test]
+    fn test_compare_linux() {
+        let l = Platform::Linux;
+        assert!(same_path_for("/home/me/app/", "/home/me/app", l));
+        assert!(!same_path_for("/home/me/App", "/home/me/app", l));
+        // A backslash is an ordinary file name character on Unix
+        assert!(!same_path_for(r"/home/me/a\b", "/home/me/a/b", l));
+        assert!(is_within_for("/home/me/app/src", "/home/me/app", l));
+        assert!(is_within_for("/home/me", "/", l));
+        assert!(!is_within_for("/home/me/app2", "/home/me/app", l));
+    }
+
+    #[test]
+    fn test_compare_macos() {
+        let m = Platform::MacOs;
+        assert!(same_path_for("/Users/Keith/Code", "/users/keith/code", m));
+        assert!(is_within_for("/Users/keith/Code/app", "/Users/Keith/code", m));
+        assert!(!is_within_for("/Users/keith/Codex", "/Users/keith/Code", m));
+    }
 }
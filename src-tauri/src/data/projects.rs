@@ -1,8 +1,9 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 
+use crate::data::path_encoding::{decode_dir_name, encode_project_path, legacy_encode_project_path};
 use crate::data::sessions::{load_session_index, load_sessions};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -17,7 +18,7 @@ pub struct ProjectInfo {
 }
 
 /// Discover all projects from ~/.claude/projects/
-pub fn discover_projects(claude_home: &PathBuf) -> Result<Vec<ProjectInfo>> {
+pub fn discover_projects(claude_home: &Path) -> Result<Vec<ProjectInfo>> {
     let projects_dir = claude_home.join("projects");
     if !projects_dir.exists() {
         return Ok(vec![]);
@@ -104,7 +105,7 @@ pub fn discover_projects(claude_home: &PathBuf) -> Result<Vec<ProjectInfo>> {
 
 /// Discover orphaned projects — entries in ~/.claude/projects/ whose real
 /// code directory no longer exists on disk.
-pub fn discover_orphaned_projects(claude_home: &PathBuf) -> Result<Vec<ProjectInfo>> {
+pub fn discover_orphaned_projects(claude_home: &Path) -> Result<Vec<ProjectInfo>> {
     let projects_dir = claude_home.join("projects");
     if !projects_dir.exists() {
         return Ok(vec![]);
@@ -180,14 +181,46 @@ pub fn discover_orphaned_projects(claude_home: &PathBuf) -> Result<Vec<ProjectIn
     Ok(orphans)
 }
 
-/// Best-effort decode of an encoded project dir name back to a path.
-/// Example: C--dev-ide → C:/dev/ide
-fn decode_dir_name(dir_name: &str) -> String {
-    if let Some(pos) = dir_name.find("--") {
-        let drive = &dir_name[..pos];
-        let rest = dir_name[pos + 2..].replace('-', "/");
-        format!("{}:/{}", drive, rest)
-    } else {
-        dir_name.replace('-', "/")
+/// Move per-project studio data (`theassociate/projects/{encoded}`: settings,
+/// notes, recordings) from dirs named with the legacy encoding to the current
+/// name, for known projects whose paths encode differently (spaces and other
+/// special characters). Entries already present under the new name win; the
+/// old dir is removed once empty. Safe to run on every launch. Returns how
+/// many dirs were migrated.
+pub fn migrate_legacy_data_dirs(claude_home: &Path) -> Result<usize> {
+    let data_dir = claude_home.join("theassociate").join("projects");
+    if !data_dir.exists() {
+        return Ok(0);
     }
+    let mut migrated = 0;
+    for project in discover_projects(claude_home)? {
+        let path = Path::new(&project.path);
+        let legacy = legacy_encode_project_path(path);
+        let current = encode_project_path(path);
+        if legacy == current {
+            continue;
+        }
+        let from = data_dir.join(&legacy);
+        if !from.is_dir() {
+            continue;
+        }
+        let to = data_dir.join(&current);
+        if !to.exists() {
+            std::fs::rename(&from, &to)?;
+            migrated += 1;
+            continue;
+        }
+        for entry in std::fs::read_dir(&from)? {
+            let entry = entry?;
+            let target = to.join(entry.file_name());
+            if !target.exists() {
+                std::fs::rename(entry.path(), target)?;
+            }
+        }
+        // Fails, and keeps the leftovers, when both names had the same entry
+        if std::fs::remove_dir(&from).is_ok() {
+            migrated += 1;
+        }
+    }
+    Ok(migrated)
 }
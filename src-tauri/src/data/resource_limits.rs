@@ -0,0 +1,22 @@
+use std::path::Path;
+
+use anyhow::Result;
+
+use crate::models::resource_limits::ResourceLimits;
+
+const LIMITS_FILE: &str = "resource-limits.json";
+
+/// The saved limits, or the default (monitoring only, no limits).
+pub fn load_resource_limits(theassociate_dir: &Path) -> ResourceLimits {
+    std::fs::read_to_string(theassociate_dir.join(LIMITS_FILE))
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_resource_limits(theassociate_dir: &Path, limits: &ResourceLimits) -> Result<()> {
+    std::fs::create_dir_all(theassociate_dir)?;
+    let json = serde_json::to_string_pretty(limits)?;
+    std::fs::write(theassociate_dir.join(LIMITS_FILE), json)?;
+    Ok(())
+}
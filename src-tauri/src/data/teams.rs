@@ -2,6 +2,7 @@ use std::path::Path;
 
 use anyhow::Result;
 
+use crate::data::path_encoding::is_within;
 use crate::models::team::{Team, TeamConfig};
 
 /// Load all teams, optionally filtering by project CWD.
@@ -49,17 +50,11 @@ pub fn load_teams(claude_home: &Path, project_cwd: Option<&Path>) -> Result<Vec<
         };
 
         if let Some(cwd) = project_cwd {
-            let cwd_str = cwd.to_string_lossy().to_lowercase();
-            let matches = config.members.iter().any(|m| {
-                if let Some(ref member_cwd) = m.cwd {
-                    let member_path = member_cwd.to_lowercase().replace('/', "\\");
-                    let cwd_normalized = cwd_str.replace('/', "\\");
-                    member_path == cwd_normalized
-                        || member_path.starts_with(&format!("{}\\", cwd_normalized))
-                } else {
-                    false
-                }
-            });
+            let cwd_str = cwd.to_string_lossy();
+            let matches = config
+                .members
+                .iter()
+                .any(|m| m.cwd.as_deref().is_some_and(|member_cwd| is_within(member_cwd, &cwd_str)));
             if !matches {
                 continue;
             }
@@ -0,0 +1,90 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+
+use crate::models::terminal_profile::TerminalProfile;
+
+const PROFILES_FILE: &str = "terminal-profiles.json";
+
+/// Find an executable by name on `PATH`.
+fn find_on_path(program: &str) -> Option<PathBuf> {
+    let path = Path::new(program);
+    if path.is_absolute() {
+        return path.is_file().then(|| path.to_path_buf());
+    }
+    let exts: &[&str] = if cfg!(windows) { &[".exe", ".cmd", ".bat", ""] } else { &[""] };
+    std::env::split_paths(&std::env::var_os("PATH")?).find_map(|dir| {
+        exts.iter()
+            .map(|ext| dir.join(format!("{}{}", program, ext)))
+            .find(|candidate| candidate.is_file())
+    })
+}
+
+fn builtin(id: &str, name: &str, command: &str, args: &[&str]) -> TerminalProfile {
+    TerminalProfile {
+        id: id.to_string(),
+        name: name.to_string(),
+        command: command.to_string(),
+        args: args.iter().map(|a| a.to_string()).collect(),
+        env: HashMap::new(),
+        cwd: None,
+        builtin: true,
+    }
+}
+
+/// Shells detected on this machine, the platform default first.
+pub fn builtin_profiles() -> Vec<TerminalProfile> {
+    let mut candidates = Vec::new();
+    if cfg!(windows) {
+        candidates.push(builtin("pwsh", "PowerShell", "pwsh", &["-NoLogo"]));
+        candidates.push(builtin("powershell", "Windows PowerShell", "powershell", &["-NoLogo"]));
+        candidates.push(builtin("cmd", "Command Prompt", "cmd", &[]));
+        candidates.push(builtin("bash", "Bash", "bash", &["--login"]));
+    } else {
+        if let Ok(shell) = std::env::var("SHELL") {
+            if !shell.is_empty() {
+                candidates.push(builtin("default", "Default shell", &shell, &["-l"]));
+            }
+        }
+        candidates.push(builtin("bash", "Bash", "bash", &["-l"]));
+        candidates.push(builtin("zsh", "Zsh", "zsh", &["-l"]));
+        candidates.push(builtin("fish", "Fish", "fish", &["-l"]));
+        candidates.push(builtin("pwsh", "PowerShell", "pwsh", &["-NoLogo"]));
+    }
+    candidates
+        .into_iter()
+        .filter(|p| find_on_path(&p.command).is_some())
+        .collect()
+}
+
+/// User-defined profiles from `~/.claude/theassociate/terminal-profiles.json`.
+fn load_user_profiles(theassociate_dir: &Path) -> Vec<TerminalProfile> {
+    std::fs::read_to_string(theassociate_dir.join(PROFILES_FILE))
+        .ok()
+        .and_then(|s| serde_json::from_str::<Vec<TerminalProfile>>(&s).ok())
+        .unwrap_or_default()
+}
+
+/// Built-in profiles followed by user-defined ones. A user profile with the
+/// same id as a built-in replaces it in place.
+pub fn load_terminal_profiles(theassociate_dir: &Path) -> Vec<TerminalProfile> {
+    let mut profiles = builtin_profiles();
+    for mut user in load_user_profiles(theassociate_dir) {
+        user.builtin = false;
+        match profiles.iter_mut().find(|p| p.id == user.id) {
+            Some(existing) => *existing = user,
+            None => profiles.push(user),
+        }
+    }
+    profiles
+}
+
+/// Persist the user-defined profiles; built-ins are always re-detected.
+pub fn save_terminal_profiles(theassociate_dir: &Path, profiles: &[TerminalProfile]) -> Result<()> {
+    let user: Vec<&TerminalProfile> = profiles.iter().filter(|p| !p.builtin).collect();
+    std::fs::create_dir_all(theassociate_dir)?;
+    let json = serde_json::to_string_pretty(&user)?;
+    std::fs::write(theassociate_dir.join(PROFILES_FILE), json)?;
+    Ok(())
+}
@@ -0,0 +1,160 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use anyhow::Result;
+
+use crate::models::terminal_profile::SessionKind;
+use crate::models::trigger_rule::{TriggerAction, TriggerRule, TriggerSource};
+
+const RULES_FILE: &str = "trigger-rules.json";
+
+/// Event emitted by the built-in rules when Claude is waiting on an answer.
+pub const QUESTION_EVENT: &str = "claude-question";
+
+fn emit(event: &str, key: &str, value: &str) -> TriggerAction {
+    TriggerAction::Emit {
+        event: event.to_string(),
+        payload: BTreeMap::from([(key.to_string(), value.to_string())]),
+    }
+}
+
+fn rule(id: &str, name: &str, pattern: &str, actions: Vec<TriggerAction>) -> TriggerRule {
+    TriggerRule {
+        id: id.to_string(),
+        name: name.to_string(),
+        pattern: pattern.to_string(),
+        exclude: None,
+        case_insensitive: false,
+        whole_chunk: false,
+        source: TriggerSource::Output,
+        kinds: vec![SessionKind::Claude],
+        profiles: vec![],
+        actions,
+        dedupe_secs: Some(10),
+        stop: false,
+        enabled: true,
+        builtin: true,
+    }
+}
+
+/// Rules shipped with the app, in evaluation order. The first five reproduce
+/// the original plan-link and question detectors and keep their event payloads;
+/// the question rules read the rendered screen so redraws split across reads
+/// still match.
+pub fn builtin_rules() -> Vec<TriggerRule> {
+    let question = |value: &str| vec![emit(QUESTION_EVENT, "question", value)];
+    vec![
+        TriggerRule {
+            dedupe_secs: None,
+            ..rule(
+                "plan-link",
+                "Link plan files",
+                r#"\.claude[\\/]plans[\\/]([^\s'"\\/]+\.md)(?:[\s'"]|$)"#,
+                vec![emit("plan-linked", "filename", "$1")],
+            )
+        },
+        TriggerRule {
+            exclude: Some(r"^\s*\?\s+for shortcuts".to_string()),
+            source: TriggerSource::Screen,
+            stop: true,
+            ..rule("claude-question", "Claude question", r"^\s*\?\s+(.+?)\s*$", question("$1"))
+        },
+        TriggerRule {
+            source: TriggerSource::Screen,
+            stop: true,
+            ..rule(
+                "claude-confirm",
+                "Claude Y/N confirmation",
+                r"^\s*(.+?(?:\[Y/n\]|\[y/N\]|\(Y/n\)|\(y/N\)))\s*$",
+                question("$1"),
+            )
+        },
+        TriggerRule {
+            whole_chunk: true,
+            source: TriggerSource::Screen,
+            stop: true,
+            ..rule(
+                "claude-select",
+                "Claude selection prompt",
+                r"(?mR)^[ \t]*(\S.{3,}\?)[ \t]*$(?s:.*)Enter to select.*navigate",
+                question("$1"),
+            )
+        },
+        TriggerRule {
+            whole_chunk: true,
+            source: TriggerSource::Screen,
+            stop: true,
+            ..rule(
+                "claude-select-fallback",
+                "Claude selection prompt (no question text)",
+                r"Enter to select.*navigate",
+                question("Interactive selection prompt"),
+            )
+        },
+        TriggerRule {
+            case_insensitive: true,
+            dedupe_secs: Some(300),
+            ..rule(
+                "usage-limit",
+                "Usage limit reached",
+                r"(?:usage|rate) limit (?:reached|exceeded)",
+                vec![TriggerAction::Notify {
+                    title: "Claude usage limit reached".to_string(),
+                    body: "$0".to_string(),
+                }],
+            )
+        },
+        TriggerRule {
+            kinds: vec![],
+            enabled: false,
+            dedupe_secs: Some(30),
+            ..rule(
+                "tests-failed",
+                "Tests failed",
+                r"\b(?:\d+ (?:tests? )?failed|tests? failed|FAILED)\b",
+                vec![TriggerAction::Notify {
+                    title: "Tests failed".to_string(),
+                    body: "$0".to_string(),
+                }],
+            )
+        },
+    ]
+}
+
+/// Built-in rules with saved overrides applied, followed by user rules.
+pub fn load_trigger_rules(theassociate_dir: &Path) -> Vec<TriggerRule> {
+    let saved: Vec<TriggerRule> = std::fs::read_to_string(theassociate_dir.join(RULES_FILE))
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default();
+    let mut rules = builtin_rules();
+    for mut rule in saved {
+        match rules.iter_mut().find(|r| r.id == rule.id) {
+            Some(existing) => {
+                rule.builtin = true;
+                *existing = rule;
+            }
+            None => {
+                rule.builtin = false;
+                rules.push(rule);
+            }
+        }
+    }
+    rules
+}
+
+/// Persist user rules and any built-ins that differ from their defaults.
+pub fn save_trigger_rules(theassociate_dir: &Path, rules: &[TriggerRule]) -> Result<()> {
+    let defaults = builtin_rules();
+    let changed: Vec<&TriggerRule> = rules
+        .iter()
+        .filter(|r| match defaults.iter().find(|d| d.id == r.id) {
+            Some(default) => *r != default,
+            None => true,
+        })
+        .collect();
+    std::fs::create_dir_all(theassociate_dir)?;
+    let json = serde_json::to_string_pretty(&changed)?;
+    std::fs::write(theassociate_dir.join(RULES_FILE), json)?;
+    Ok(())
+}
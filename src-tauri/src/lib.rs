@@ -2,6 +2,8 @@ mod cleanup;
 mod commands;
 mod data;
 mod models;
+mod paths;
+mod pty;
 mod startup;
 mod utils;
 mod watcher;
@@ -29,16 +31,27 @@ pub fn run() {
             std::collections::HashMap::new(),
         ))))
         .manage(watcher::git_watcher::GitWatcherState::new())
+        .manage(watcher::claude_watcher::ClaudeWatcherState::new())
+        .manage(commands::recordings::PlaybackState::new())
+        .manage(commands::prompt_queue::PromptQueueState::new())
+        .manage(commands::broadcast::BroadcastState::new())
         .setup(|app| {
             // Create a Start Menu shortcut with AUMID so Windows toast notifications
             // appear as "The Associate Studio" rather than PowerShell.
             startup::ensure_start_menu_shortcut();
 
-            // Auto-install hooks on every launch (idempotent — skips if already present)
-            if let Err(e) = commands::hooks::cmd_setup_hooks() {
+            // Per-project data dirs named before the path encoding matched the CLI's.
+            // Runs before hook setup, which reads each project's hook scope.
+            if let Err(e) = commands::projects::migrate_project_data_dirs() {
+                eprintln!("[ide] project data migration failed: {}", e);
+            }
+            // Auto-install user-level hooks on every launch (idempotent — skips if already
+            // present, or if every project opened in the studio uses project-scoped hooks)
+            if let Err(e) = commands::hooks::auto_setup_hooks(app.handle()) {
                 eprintln!("[ide] hook setup failed: {}", e);
             }
             watcher::claude_watcher::start_claude_watcher(app.handle().clone());
+            pty::monitor::start_resource_monitor(app.handle().clone());
 
             // Kill all PTY sessions when the main window is destroyed
             let pty_state = app.state::<PtyState>().inner().0.clone();
@@ -73,28 +86,91 @@ pub fn run() {
             commands::plans::cmd_save_plan,
             commands::git::cmd_git_status,
             commands::git::cmd_git_diff,
+            commands::git::cmd_git_blame,
             commands::git::cmd_git_branches,
             commands::git::cmd_git_current_branch,
             commands::git::cmd_git_log,
+            commands::git::cmd_git_commit_detail,
             commands::git::cmd_git_remote_branches,
             commands::git::cmd_create_worktree,
             commands::git::cmd_list_worktrees,
             commands::git::cmd_get_worktree_copy,
             commands::git::cmd_set_worktree_copy,
+            commands::git::cmd_remove_worktree,
+            commands::git::cmd_prune_worktrees,
+            commands::git::cmd_lock_worktree,
+            commands::git::cmd_unlock_worktree,
             commands::git::cmd_claude_git_action,
             commands::git::cmd_git_fetch,
             commands::git::cmd_git_pull,
+            commands::git::cmd_git_push,
+            commands::git::cmd_git_stash_list,
+            commands::git::cmd_git_stash_save,
+            commands::git::cmd_git_stash_show,
+            commands::git::cmd_git_stash_apply,
+            commands::git::cmd_git_stash_pop,
+            commands::git::cmd_git_stash_drop,
             commands::git::cmd_git_create_branch,
             commands::git::cmd_git_add,
+            commands::git::cmd_git_stage_lines,
+            commands::git::cmd_git_unstage_lines,
+            commands::git::cmd_git_discard_lines,
+            commands::git::cmd_git_unstage,
+            commands::git::cmd_git_discard,
+            commands::git::cmd_git_commit,
+            commands::git::cmd_git_suggest_commit_message,
             commands::git::cmd_git_ignore,
             commands::git::cmd_git_exclude,
             commands::git::cmd_git_rebase,
-            commands::git::cmd_watch_git_head,
+            commands::git::cmd_git_conflicts,
+            commands::git::cmd_git_resolve_conflict,
+            commands::git::cmd_git_mark_resolved,
+            commands::git::cmd_git_operation_continue,
+            commands::git::cmd_git_operation_skip,
+            commands::git::cmd_git_operation_abort,
+            commands::git::cmd_git_suggest_resolution,
+            commands::git::cmd_watch_git,
+            commands::git::cmd_unwatch_git,
             commands::pty::pty_spawn,
+            commands::pty::pty_spawn_shell,
             commands::pty::pty_resize,
+            commands::pty::pty_nudge,
             commands::pty::pty_write,
+            commands::pty::pty_bind_session,
+            commands::pty::pty_attach,
+            commands::pty::pty_ack,
+            commands::pty::pty_search,
+            commands::pty::pty_snapshot,
+            commands::pty::pty_start_recording,
+            commands::pty::pty_stop_recording,
             commands::pty::pty_kill,
             commands::pty::pty_kill_all,
+            commands::pty::pty_list,
+            commands::recordings::cmd_list_recordings,
+            commands::recordings::cmd_play_recording,
+            commands::recordings::cmd_stop_playback,
+            commands::recordings::cmd_export_recording,
+            commands::terminal_profiles::cmd_list_terminal_profiles,
+            commands::terminal_profiles::cmd_save_terminal_profiles,
+            commands::launch_profiles::cmd_list_launch_profiles,
+            commands::launch_profiles::cmd_save_launch_profiles,
+            commands::trigger_rules::cmd_list_trigger_rules,
+            commands::trigger_rules::cmd_save_trigger_rules,
+            commands::prompt_queue::cmd_get_prompt_queue,
+            commands::prompt_queue::cmd_enqueue_prompt,
+            commands::prompt_queue::cmd_update_queued_prompt,
+            commands::prompt_queue::cmd_remove_queued_prompt,
+            commands::prompt_queue::cmd_reorder_prompt_queue,
+            commands::prompt_queue::cmd_set_prompt_queue_paused,
+            commands::prompt_queue::cmd_send_next_prompt,
+            commands::auto_resume::cmd_get_auto_resume_policy,
+            commands::auto_resume::cmd_save_auto_resume_policy,
+            commands::resource_limits::cmd_get_resource_limits,
+            commands::resource_limits::cmd_save_resource_limits,
+            commands::broadcast::cmd_broadcast_prompt,
+            commands::broadcast::cmd_get_broadcast,
+            commands::broadcast::cmd_list_broadcasts,
+            commands::broadcast::cmd_dismiss_broadcast,
             commands::issues::cmd_list_prs,
             commands::issues::cmd_list_issues,
             commands::issues::cmd_list_linear_issues,
@@ -130,6 +206,8 @@ pub fn run() {
             commands::integrations::cmd_jira_logout,
             commands::hooks::cmd_setup_hooks,
             commands::hooks::cmd_remove_hooks,
+            commands::hooks::cmd_preview_hooks,
+            commands::hooks::cmd_set_hook_scope,
             commands::hooks::cmd_get_active_sessions,
             commands::hooks::cmd_hooks_configured,
             commands::projects::cmd_list_projects,
@@ -152,6 +230,9 @@ pub fn run() {
             commands::plan_links::cmd_load_plan_links,
             commands::plan_links::cmd_save_plan_links,
             commands::claude_config::cmd_load_extensions,
+            commands::claude_homes::cmd_list_claude_homes,
+            commands::claude_homes::cmd_save_claude_homes,
+            commands::claude_homes::cmd_switch_claude_home,
             commands::notes::cmd_load_global_notes,
             commands::notes::cmd_load_project_notes,
             commands::notes::cmd_save_note,
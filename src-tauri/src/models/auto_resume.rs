@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+/// When to relaunch `claude --resume` after a Claude session dies unexpectedly.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AutoResumePolicy {
+    pub enabled: bool,
+    /// Resumes allowed within `window_secs` before giving up
+    pub max_retries: u32,
+    pub window_secs: u64,
+    /// Pause before relaunching, so a crash loop doesn't spin
+    pub delay_ms: u64,
+}
+
+impl Default for AutoResumePolicy {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_retries: 3,
+            window_secs: 600,
+            delay_ms: 2000,
+        }
+    }
+}
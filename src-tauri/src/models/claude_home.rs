@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+
+/// A Claude config root the user registered, e.g. a second account kept in
+/// `~/.claude-work`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeHome {
+    pub id: String,
+    pub name: String,
+    pub path: String,
+}
+
+/// Registered roots plus the one the studio is using. The default root
+/// (`CLAUDE_CONFIG_DIR` or `~/.claude`) is implicit and never stored.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ClaudeHomes {
+    /// Id of the active root; `None` means the default one
+    pub active: Option<String>,
+    pub homes: Vec<ClaudeHome>,
+}
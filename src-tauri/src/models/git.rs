@@ -1,10 +1,24 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Serialize)]
 pub enum GitFileSection {
     Staged,
     Unstaged,
     Untracked,
+    Conflicted,
+}
+
+/// Which sides of a merge touched a conflicted path, as in `git status`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ConflictKind {
+    BothModified,
+    BothAdded,
+    BothDeleted,
+    AddedByUs,
+    AddedByThem,
+    DeletedByUs,
+    DeletedByThem,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -13,6 +27,12 @@ pub struct GitFileEntry {
     pub path: String,
     pub section: GitFileSection,
     pub status_char: char,
+    /// Previous path when `status_char` is `R`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub old_path: Option<String>,
+    /// Set for entries in the `Conflicted` section
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conflict: Option<ConflictKind>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize)]
@@ -25,15 +45,420 @@ pub enum DiffLineKind {
 }
 
 #[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct DiffLine {
     pub kind: DiffLineKind,
     pub text: String,
+    /// Index of the hunk this line belongs to (its `@@` line included)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hunk: Option<usize>,
+    /// Line number on the old side, for context and removed lines
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub old_line: Option<u32>,
+    /// Line number on the new side, for context and added lines
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_line: Option<u32>,
+}
+
+/// Identifies one added (`new_line`) or removed (`old_line`) line of a diff.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffLineId {
+    pub old_line: Option<u32>,
+    pub new_line: Option<u32>,
+}
+
+/// The part of a file's diff to stage, unstage or discard: whole hunks plus
+/// individual lines.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DiffSelection {
+    pub hunks: Vec<usize>,
+    pub lines: Vec<DiffLineId>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmoduleStatus {
+    pub name: String,
+    pub path: String,
+    /// Commit recorded in the superproject's HEAD
+    pub head_id: Option<String>,
+    /// Commit checked out in the submodule
+    pub workdir_id: Option<String>,
+    pub initialized: bool,
+    /// Checked-out commit differs from the recorded one
+    pub commit_changed: bool,
+    /// Modified or untracked files inside the submodule
+    pub dirty: bool,
 }
 
 #[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct GitStatus {
     pub staged: Vec<GitFileEntry>,
     pub unstaged: Vec<GitFileEntry>,
     pub untracked: Vec<GitFileEntry>,
+    pub conflicted: Vec<GitFileEntry>,
+    /// Short branch name, or `HEAD` when detached
+    pub branch: String,
+    /// e.g. `origin/main`, when the branch tracks one
+    pub upstream: Option<String>,
+    pub ahead: usize,
+    pub behind: usize,
+    pub submodules: Vec<SubmoduleStatus>,
+    /// Merge, rebase, cherry-pick or revert stopped partway, if any
+    pub operation: Option<GitOperation>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum GitOperation {
+    Merge,
+    Rebase,
+    CherryPick,
+    Revert,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConflictFile {
+    pub path: String,
+    pub kind: ConflictKind,
+    /// Contents below are `None` for binary files
+    pub binary: bool,
+    /// Common ancestor version
+    pub base: Option<String>,
+    /// HEAD's version; during a rebase, the branch being rebased onto
+    pub ours: Option<String>,
+    /// The incoming version; during a rebase, the commit being replayed
+    pub theirs: Option<String>,
+    /// The working-tree file, usually with conflict markers
+    pub working: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConflictState {
+    pub operation: Option<GitOperation>,
+    /// Commit being merged, replayed, picked or reverted
+    pub incoming_hash: Option<String>,
+    pub incoming_summary: Option<String>,
+    /// Branch being rebased
+    pub rebase_branch: Option<String>,
+    /// Short id of the commit a rebase is replaying onto
+    pub rebase_onto: Option<String>,
+    /// 1-based rebase step and step count
+    pub step: Option<usize>,
+    pub total_steps: Option<usize>,
+    pub conflicts: Vec<ConflictFile>,
+}
+
+/// How to settle one conflicted file.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum ConflictResolution {
+    /// Take HEAD's version (deleting the file if HEAD deleted it)
+    Ours,
+    /// Take the incoming version (deleting the file if it was deleted there)
+    Theirs,
+    /// Write this content, e.g. an edited or agent-proposed merge
+    Content { content: String },
 }
 
+#[derive(Debug, Clone, Deserialize)]
+pub struct CommitAuthor {
+    pub name: String,
+    pub email: String,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CommitOptions {
+    /// Empty keeps the message when amending
+    pub message: String,
+    /// Replace HEAD instead of committing on top of it
+    pub amend: bool,
+    /// Append a `Signed-off-by` trailer for the committer
+    pub sign_off: bool,
+    /// Author to record instead of the configured user (or, when amending,
+    /// the original author)
+    pub author: Option<CommitAuthor>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatedCommit {
+    pub hash: String,
+    pub short_hash: String,
+    pub summary: String,
+    /// Signed because `commit.gpgsign` is set
+    pub signed: bool,
+}
+
+/// Filters and position for one page of `git log`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LogQuery {
+    /// `next_cursor` of the previous page; `None` starts from HEAD
+    pub cursor: Option<String>,
+    /// Page size, 100 when unset
+    pub limit: Option<usize>,
+    /// Case-insensitive match on the author name or email
+    pub author: Option<String>,
+    /// Only commits that change this file or directory (repo-relative or absolute)
+    pub path: Option<String>,
+    /// Case-insensitive match anywhere in the commit message
+    pub message: Option<String>,
+}
+
+/// Where a commit sits in the graph drawn beside the log. Columns are lane
+/// indices; lines run between this row and the rows above and below.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphRow {
+    /// Column of the commit's node
+    pub lane: usize,
+    /// Columns of lines from the row above that end at the node
+    pub up: Vec<usize>,
+    /// Columns of lines from the node down to its parents
+    pub down: Vec<usize>,
+    /// Columns of lines passing straight through the row
+    pub through: Vec<usize>,
+    /// Number of columns in use at this row
+    pub width: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitInfo {
+    pub hash: String,
+    pub short_hash: String,
+    pub parents: Vec<String>,
+    /// Subject line
+    pub message: String,
+    pub author: String,
+    pub email: String,
+    /// Unix seconds (author time)
+    pub timestamp: i64,
+    /// Relative date, e.g. "3 days ago"
+    pub date: String,
+    pub refs: Vec<String>,
+    /// `None` when the log is filtered, since the graph would have gaps
+    pub graph: Option<GraphRow>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogPage {
+    pub commits: Vec<CommitInfo>,
+    /// Cursor for the next page, `None` on the last one
+    pub next_cursor: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitFileChange {
+    pub path: String,
+    /// Previous path when `status_char` is `R` or `C`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub old_path: Option<String>,
+    /// `A`, `M`, `D`, `R`, `C` or `T`, as in `git show --name-status`
+    pub status_char: char,
+    pub additions: usize,
+    pub deletions: usize,
+    pub binary: bool,
+    pub diff: Vec<DiffLine>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitDetail {
+    pub hash: String,
+    pub short_hash: String,
+    pub parents: Vec<String>,
+    /// Full message, trailers included
+    pub message: String,
+    pub author: String,
+    pub email: String,
+    /// Unix seconds (author time)
+    pub timestamp: i64,
+    pub date: String,
+    pub committer: String,
+    pub committer_email: String,
+    pub commit_timestamp: i64,
+    pub refs: Vec<String>,
+    /// The commit carries the studio's `Co-Authored-By: The Associate` trailer
+    pub agent: bool,
+    /// Changes against the first parent (the empty tree for a root commit)
+    pub files: Vec<CommitFileChange>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PushOptions {
+    /// Overwrite the remote branch, but only if it is still where our
+    /// remote-tracking ref says it is
+    pub force_with_lease: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PushRefStatus {
+    FastForward,
+    Forced,
+    New,
+    Deleted,
+    UpToDate,
+    Rejected,
+}
+
+/// Why the remote refused a ref update.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PushRejection {
+    /// The remote has commits we don't; pull first
+    NonFastForward,
+    /// The remote has objects we haven't fetched
+    FetchFirst,
+    /// `--force-with-lease` found the remote moved since the last fetch
+    StaleLease,
+    /// Branch protection (or a hook enforcing it) declined the update
+    ProtectedBranch,
+    /// Any other hook or policy on the remote declined it
+    RemoteRejected,
+    Other,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PushRefUpdate {
+    pub local_ref: String,
+    pub remote_ref: String,
+    pub status: PushRefStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rejection: Option<PushRejection>,
+    /// e.g. `1a2b3c..4d5e6f` or `[rejected] (non-fast-forward)`
+    pub summary: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PushResult {
+    pub remote: String,
+    pub branch: String,
+    /// The branch had no upstream and now tracks `remote/branch`
+    pub upstream_set: bool,
+    pub refs: Vec<PushRefUpdate>,
+    /// True when any ref update was rejected
+    pub rejected: bool,
+    /// Non-progress stderr, including `remote:` messages
+    pub output: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PushProgress {
+    pub cwd: String,
+    /// e.g. "Writing objects"
+    pub stage: String,
+    pub percent: Option<u8>,
+    pub line: String,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct StashOptions {
+    pub message: Option<String>,
+    /// Stash untracked files too, removing them from the working tree
+    pub include_untracked: bool,
+    /// Leave staged changes in the index (and working tree)
+    pub keep_index: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StashEntry {
+    /// Position in the stash list (`stash@{index}`); shifts as stashes come and go
+    pub index: usize,
+    /// Stash commit id, which stays put and is what the other stash commands take
+    pub hash: String,
+    /// The given message, or the summary of the commit stashed on
+    pub message: String,
+    /// Branch checked out when the stash was made
+    pub branch: Option<String>,
+    /// Unix seconds
+    pub timestamp: i64,
+    /// Relative date, e.g. "2 hours ago"
+    pub date: String,
+    pub includes_untracked: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlameLine {
+    /// 1-based line number in the working-tree file
+    pub line: usize,
+    pub content: String,
+    /// `None` for lines changed since the last commit
+    pub hash: Option<String>,
+    pub short_hash: Option<String>,
+    pub author: String,
+    pub email: String,
+    /// Unix seconds (author time)
+    pub timestamp: i64,
+    /// Relative date, e.g. "3 days ago"
+    pub date: String,
+    pub summary: String,
+    /// The commit carries the studio's `Co-Authored-By: The Associate` trailer
+    pub agent: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorktreeInfo {
+    pub path: String,
+    /// Short SHA (first 8 chars)
+    pub head: String,
+    /// Short name, e.g. "feature/auth" not "refs/heads/feature/auth"
+    pub branch: String,
+    /// The first entry, where the repository itself lives
+    pub is_main: bool,
+    /// The directory is gone, so `git worktree prune` would drop the entry
+    pub is_prunable: bool,
+    pub is_locked: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lock_reason: Option<String>,
+    /// The branch is fully merged into the main worktree's branch
+    pub is_merged: bool,
+}
+
+/// What a new worktree checks out. The branch is the one named in the
+/// create call, except for `Local` where it is the existing branch itself.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum WorktreeSource {
+    /// A new branch from HEAD
+    #[default]
+    NewBranch,
+    /// An existing local branch
+    Local,
+    /// A new branch tracking a remote branch, e.g. `origin/feature`
+    Remote { branch: String },
+    /// A new branch from a GitHub pull request's head (`pull/<number>/head`)
+    PullRequest {
+        number: u64,
+        /// Defaults to `origin`
+        #[serde(default)]
+        remote: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RemoveWorktreeOptions {
+    /// Remove even with uncommitted or untracked changes
+    pub force: bool,
+    /// Also delete the worktree's Claude session data (`projects/<encoded path>`)
+    pub clean_claude_data: bool,
+}
@@ -13,4 +13,7 @@ pub struct HookEvent {
     pub agent_type: Option<String>,
     pub last_assistant_message: Option<String>,
     pub stop_hook_active: Option<bool>,
+    /// Unix millis when hook.js logged the event; absent from older logs
+    #[serde(default)]
+    pub timestamp: Option<i64>,
 }
@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+/// Where the studio hooks are registered for a project.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HookScope {
+    /// `~/.claude/settings.json` — applies to every project
+    #[default]
+    User,
+    /// `<project>/.claude/settings.local.json` — this project only, never committed
+    Project,
+}
@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Where a launch profile is stored.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LaunchProfileScope {
+    /// `~/.claude/theassociate/launch-profiles.json`
+    #[default]
+    Global,
+    /// `<project>/.claude/launch-profiles.json`, meant to be committed and shared
+    Project,
+}
+
+/// A named set of `claude` CLI flags and environment variables for new sessions.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchProfile {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub permission_mode: Option<String>,
+    #[serde(default)]
+    pub add_dirs: Vec<String>,
+    #[serde(default)]
+    pub mcp_config: Option<String>,
+    #[serde(default)]
+    pub append_system_prompt: Option<String>,
+    #[serde(default)]
+    pub allowed_tools: Vec<String>,
+    #[serde(default)]
+    pub disallowed_tools: Vec<String>,
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    /// Set on load from the file the profile came from
+    #[serde(default)]
+    pub scope: LaunchProfileScope,
+}
@@ -1,12 +1,19 @@
+pub mod auto_resume;
 pub mod claude_config;
+pub mod claude_home;
 pub mod git;
 pub mod hook_event;
+pub mod hook_scope;
 pub mod inbox;
+pub mod launch_profile;
 pub mod note;
 pub mod plan;
+pub mod resource_limits;
 pub mod session;
 pub mod summary;
 pub mod task;
 pub mod team;
+pub mod terminal_profile;
+pub mod trigger_rule;
 pub mod todo;
 pub mod transcript;
@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+
+/// What to do when a session's process tree stays over a limit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LimitAction {
+    /// Alert only (`terminal-alert`)
+    #[default]
+    Warn,
+    /// Kill everything below the session's process, leaving Claude or the shell running
+    KillChildren,
+    /// Kill the whole tree, ending the session
+    KillTree,
+}
+
+/// Per-session limits on the PTY child's process tree. Unset limits aren't checked.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ResourceLimits {
+    pub enabled: bool,
+    /// Summed CPU usage, where 100 is one full core
+    pub max_cpu_percent: Option<f32>,
+    pub max_memory_mb: Option<u64>,
+    pub max_processes: Option<usize>,
+    /// How long a limit must be exceeded before acting, so short spikes pass
+    pub sustain_secs: u64,
+    pub action: LimitAction,
+    /// Seconds between samples (and `pty-stats` events)
+    pub sample_secs: u64,
+}
+
+impl Default for ResourceLimits {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_cpu_percent: None,
+            max_memory_mb: None,
+            max_processes: None,
+            sustain_secs: 30,
+            action: LimitAction::Warn,
+            sample_secs: 2,
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// What a PTY session runs. Trigger rules can be limited to one kind; the
+/// built-in Claude rules (plan links, questions) only apply to `Claude`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SessionKind {
+    Claude,
+    Shell,
+}
+
+/// A shell (or any command) that can be opened as a terminal tab.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TerminalProfile {
+    pub id: String,
+    pub name: String,
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    /// Overrides the tab's project directory when set
+    #[serde(default)]
+    pub cwd: Option<String>,
+    /// Detected from the system rather than user-defined; never saved
+    #[serde(default)]
+    pub builtin: bool,
+}
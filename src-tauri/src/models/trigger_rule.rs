@@ -0,0 +1,89 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::models::terminal_profile::SessionKind;
+
+/// A regex matched against ANSI-stripped terminal output (or the rendered
+/// screen), with actions to run when it matches. Action text fields are templates: `$0` is the whole match,
+/// `$1`/`${name}` are capture groups.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TriggerRule {
+    pub id: String,
+    pub name: String,
+    pub pattern: String,
+    /// Matches of this regex (tested against the matched line) are ignored
+    #[serde(default)]
+    pub exclude: Option<String>,
+    #[serde(default)]
+    pub case_insensitive: bool,
+    /// Match against the whole output chunk (or screen) instead of line by line
+    #[serde(default)]
+    pub whole_chunk: bool,
+    #[serde(default)]
+    pub source: TriggerSource,
+    /// Session kinds the rule applies to; empty = all
+    #[serde(default)]
+    pub kinds: Vec<SessionKind>,
+    /// Launch/terminal profile ids the rule applies to; empty = all
+    #[serde(default)]
+    pub profiles: Vec<String>,
+    pub actions: Vec<TriggerAction>,
+    /// Ignore repeats of the same match within this many seconds.
+    /// `None` fires once per session for each distinct match.
+    #[serde(default = "default_dedupe_secs")]
+    pub dedupe_secs: Option<u64>,
+    /// Skip the remaining rules for this chunk once this one fires
+    #[serde(default)]
+    pub stop: bool,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Shipped with the app; a saved rule with the same id overrides it
+    #[serde(default)]
+    pub builtin: bool,
+}
+
+/// What a trigger rule is matched against.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TriggerSource {
+    /// Each chunk of output as it is read
+    #[default]
+    Output,
+    /// The rendered screen after each chunk. Fires when a match appears,
+    /// not again while it stays on screen.
+    Screen,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum TriggerAction {
+    /// Emit a Tauri event with `{ tab_id, ...payload }`
+    Emit {
+        event: String,
+        #[serde(default)]
+        payload: BTreeMap<String, String>,
+    },
+    /// In-app + desktop notification (`terminal-alert` event)
+    Notify {
+        title: String,
+        #[serde(default = "whole_match")]
+        body: String,
+    },
+    /// Open a file referenced in the output (`file-linked` event)
+    LinkFile { path: String },
+    /// Type `text` into the terminal (include `\r` to press Enter)
+    Reply { text: String },
+}
+
+fn default_dedupe_secs() -> Option<u64> {
+    Some(10)
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn whole_match() -> String {
+    "$0".to_string()
+}
@@ -0,0 +1,97 @@
+use std::path::{Path, PathBuf};
+use std::sync::{OnceLock, RwLock};
+
+use crate::data::claude_homes::load_claude_homes;
+
+/// The user's home directory (`USERPROFILE` on Windows, `HOME` elsewhere).
+pub fn home_dir() -> Result<PathBuf, String> {
+    let home = std::env::var("USERPROFILE")
+        .or_else(|_| std::env::var("HOME"))
+        .map_err(|_| "Neither USERPROFILE nor HOME environment variable is set".to_string())?;
+    if home.is_empty() {
+        return Err("Home directory environment variable is empty".to_string());
+    }
+    Ok(PathBuf::from(home))
+}
+
+/// The root Claude uses on its own: `CLAUDE_CONFIG_DIR` when set, else `~/.claude`.
+pub fn default_claude_home() -> Result<PathBuf, String> {
+    match std::env::var("CLAUDE_CONFIG_DIR") {
+        Ok(dir) if !dir.is_empty() => Ok(PathBuf::from(dir)),
+        _ => Ok(home_dir()?.join(".claude")),
+    }
+}
+
+/// Where the list of registered Claude homes is kept. Fixed to the default
+/// root so it is found no matter which home is active.
+pub fn claude_homes_dir() -> Result<PathBuf, String> {
+    Ok(default_claude_home()?.join("theassociate"))
+}
+
+fn active() -> &'static RwLock<Option<PathBuf>> {
+    static ACTIVE: OnceLock<RwLock<Option<PathBuf>>> = OnceLock::new();
+    ACTIVE.get_or_init(|| RwLock::new(saved_active_home()))
+}
+
+/// The home selected in a previous run, if it is still registered.
+fn saved_active_home() -> Option<PathBuf> {
+    let registry = load_claude_homes(&claude_homes_dir().ok()?);
+    let id = registry.active?;
+    registry
+        .homes
+        .into_iter()
+        .find(|h| h.id == id)
+        .map(|h| PathBuf::from(h.path))
+}
+
+/// The Claude root the studio reads and writes: the selected Claude home, or
+/// the default root when none is selected.
+pub fn claude_home() -> Result<PathBuf, String> {
+    if let Some(path) = active().read().ok().and_then(|a| a.clone()) {
+        return Ok(path);
+    }
+    default_claude_home()
+}
+
+/// The studio's own data dir inside the active Claude root.
+pub fn theassociate_home() -> Result<PathBuf, String> {
+    Ok(claude_home()?.join("theassociate"))
+}
+
+/// Point every later `claude_home()` at `path`, or back at the default root
+/// with `None`. Callers are responsible for restarting watchers and hooks.
+pub fn set_active_claude_home(path: Option<PathBuf>) {
+    if let Ok(mut a) = active().write() {
+        *a = path;
+    }
+}
+
+/// The environment Claude processes we launch need to use the active root.
+/// `None` for the default root, which they find on their own.
+pub fn claude_config_env() -> Option<(&'static str, PathBuf)> {
+    let path = active().read().ok()?.clone()?;
+    Some(("CLAUDE_CONFIG_DIR", path))
+}
+
+/// Claude's global state file (user MCP servers, trusted projects). It sits
+/// next to `~/.claude` for the stock root and inside any other root.
+pub fn claude_json_path(claude_home: &Path) -> Result<PathBuf, String> {
+    let home = home_dir()?;
+    if claude_home == home.join(".claude") {
+        Ok(home.join(".claude.json"))
+    } else {
+        Ok(claude_home.join(".claude.json"))
+    }
+}
+
+/// The default root followed by every registered Claude home.
+pub fn all_claude_homes() -> Result<Vec<PathBuf>, String> {
+    let mut all = vec![default_claude_home()?];
+    for home in load_claude_homes(&claude_homes_dir()?).homes {
+        let path = PathBuf::from(home.path);
+        if !all.contains(&path) {
+            all.push(path);
+        }
+    }
+    Ok(all)
+}
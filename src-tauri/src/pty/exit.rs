@@ -0,0 +1,60 @@
+use serde::Serialize;
+
+use crate::models::auto_resume::AutoResumePolicy;
+
+/// Lines of output kept with an exit report.
+pub const EXIT_TAIL_LINES: usize = 20;
+
+/// How a PTY's process ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExitReason {
+    /// Exit code 0, e.g. `/exit` or Ctrl+D
+    Clean,
+    /// Non-zero exit code, a signal, or a child that couldn't be reaped
+    Crashed,
+    /// Ended by `pty_kill` (tab closed)
+    Killed,
+}
+
+/// Payload of `pty-exit-{id}`, also kept on the session for `pty_list`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PtyExit {
+    pub reason: ExitReason,
+    /// Process exit code, when the child was reaped
+    pub code: Option<u32>,
+    /// e.g. "Exited with code 1" or "Terminated by Segmentation fault"
+    pub description: String,
+    /// Last lines of ANSI-stripped output before the exit
+    pub tail: Vec<String>,
+    /// Set when an automatic resume has been scheduled
+    pub resume: Option<ResumeAttempt>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResumeAttempt {
+    pub claude_session_id: String,
+    /// 1-based attempt within the policy window
+    pub attempt: u32,
+    pub max_retries: u32,
+    pub delay_ms: u64,
+}
+
+/// Spend one retry of `policy`'s budget at `now` (unix millis). `attempts`
+/// holds the times of earlier resumes of the same tab; ones older than the
+/// window are dropped. Returns the attempt number, or `None` when disabled or
+/// the budget is used up.
+pub fn take_retry(policy: &AutoResumePolicy, attempts: &mut Vec<i64>, now: i64) -> Option<u32> {
+    if !policy.enabled || policy.max_retries == 0 {
+        return None;
+    }
+    let window = policy.window_secs as i64 * 1000;
+    attempts.retain(|t| now - t < window);
+    if attempts.len() as u32 >= policy.max_retries {
+        return None;
+    }
+    attempts.push(now);
+    Some(attempts.len() as u32)
+}
@@ -0,0 +1,8 @@
+pub mod exit;
+pub mod monitor;
+pub mod prompt_queue;
+pub mod recording;
+pub mod screen;
+pub mod scrollback;
+pub mod stream;
+pub mod triggers;
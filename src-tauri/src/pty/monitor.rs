@@ -0,0 +1,230 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::commands::pty::{emit_alert, PtyState};
+use crate::data::resource_limits::load_resource_limits;
+use crate::models::resource_limits::{LimitAction, ResourceLimits};
+use crate::paths::theassociate_home;
+
+/// Processes listed per session in `PtyStats::top`.
+const TOP_PROCESSES: usize = 5;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessStats {
+    pub pid: u32,
+    pub name: String,
+    pub cpu_percent: f32,
+    pub memory_bytes: u64,
+}
+
+/// One sample of a session's process tree (the PTY child and its descendants).
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PtyStats {
+    pub session_id: String,
+    pub pid: u32,
+    /// Summed over the tree; 100 is one full core
+    pub cpu_percent: f32,
+    pub memory_bytes: u64,
+    pub process_count: usize,
+    /// Heaviest processes by CPU
+    pub top: Vec<ProcessStats>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Limit {
+    Cpu,
+    Memory,
+    Processes,
+}
+
+struct Breach {
+    since: Instant,
+    acted: bool,
+}
+
+/// Sample every session's process tree on a background thread, emit
+/// `pty-stats` with all samples, and enforce the saved `ResourceLimits`.
+pub fn start_resource_monitor(app: AppHandle) {
+    std::thread::spawn(move || {
+        let mut system = System::new();
+        let mut breaches: HashMap<(String, Limit), Breach> = HashMap::new();
+        loop {
+            // Reloaded every round so settings changes apply without a restart
+            let limits = theassociate_home()
+                .map(|dir| load_resource_limits(&dir))
+                .unwrap_or_default();
+            let interval = Duration::from_secs(limits.sample_secs.max(1));
+
+            let roots: Vec<(String, u32)> = app
+                .state::<PtyState>()
+                .0
+                .lock()
+                .map(|sessions| {
+                    sessions
+                        .iter()
+                        .filter(|(_, s)| s.exit.is_none())
+                        .filter_map(|(id, s)| s.pid.map(|pid| (id.clone(), pid)))
+                        .collect()
+                })
+                .unwrap_or_default();
+            breaches.retain(|(id, _), _| roots.iter().any(|(r, _)| r == id));
+            if roots.is_empty() {
+                std::thread::sleep(interval);
+                continue;
+            }
+
+            system.refresh_processes_specifics(
+                ProcessesToUpdate::All,
+                true,
+                ProcessRefreshKind::nothing().with_cpu().with_memory(),
+            );
+            let mut children: HashMap<Pid, Vec<Pid>> = HashMap::new();
+            for (pid, process) in system.processes() {
+                if let Some(parent) = process.parent() {
+                    children.entry(parent).or_default().push(*pid);
+                }
+            }
+
+            let mut samples = Vec::with_capacity(roots.len());
+            for (session_id, root) in roots {
+                let tree = process_tree(Pid::from_u32(root), &children);
+                let stats = sample(&system, &session_id, root, &tree);
+                if limits.enabled {
+                    enforce(&app, &system, &limits, &stats, &tree, &mut breaches);
+                }
+                samples.push(stats);
+            }
+            let _ = app.emit("pty-stats", &samples);
+            std::thread::sleep(interval);
+        }
+    });
+}
+
+/// `root` and all its descendants, parents before children.
+fn process_tree(root: Pid, children: &HashMap<Pid, Vec<Pid>>) -> Vec<Pid> {
+    let mut tree = vec![root];
+    let mut i = 0;
+    while i < tree.len() {
+        for kid in children.get(&tree[i]).into_iter().flatten() {
+            if !tree.contains(kid) {
+                tree.push(*kid);
+            }
+        }
+        i += 1;
+    }
+    tree
+}
+
+fn sample(system: &System, session_id: &str, root: u32, tree: &[Pid]) -> PtyStats {
+    let mut processes: Vec<ProcessStats> = tree
+        .iter()
+        .filter_map(|pid| system.process(*pid))
+        .map(|p| ProcessStats {
+            pid: p.pid().as_u32(),
+            name: p.name().to_string_lossy().into_owned(),
+            cpu_percent: p.cpu_usage(),
+            memory_bytes: p.memory(),
+        })
+        .collect();
+    let cpu_percent = processes.iter().map(|p| p.cpu_percent).sum();
+    let memory_bytes = processes.iter().map(|p| p.memory_bytes).sum();
+    let process_count = processes.len();
+    processes.sort_by(|a, b| b.cpu_percent.total_cmp(&a.cpu_percent));
+    processes.truncate(TOP_PROCESSES);
+    PtyStats {
+        session_id: session_id.to_string(),
+        pid: root,
+        cpu_percent,
+        memory_bytes,
+        process_count,
+        top: processes,
+    }
+}
+
+/// Track how long each limit has been exceeded and act once it has been for
+/// `sustain_secs`. A limit acts once per breach; dropping back under resets it.
+fn enforce(
+    app: &AppHandle,
+    system: &System,
+    limits: &ResourceLimits,
+    stats: &PtyStats,
+    tree: &[Pid],
+    breaches: &mut HashMap<(String, Limit), Breach>,
+) {
+    let memory_mb = stats.memory_bytes / (1024 * 1024);
+    let checks = [
+        (
+            Limit::Cpu,
+            limits.max_cpu_percent.filter(|max| stats.cpu_percent > *max).map(|max| {
+                format!("CPU {:.0}% (limit {:.0}%)", stats.cpu_percent, max)
+            }),
+        ),
+        (
+            Limit::Memory,
+            limits
+                .max_memory_mb
+                .filter(|max| memory_mb > *max)
+                .map(|max| format!("memory {} MB (limit {} MB)", memory_mb, max)),
+        ),
+        (
+            Limit::Processes,
+            limits
+                .max_processes
+                .filter(|max| stats.process_count > *max)
+                .map(|max| format!("{} processes (limit {})", stats.process_count, max)),
+        ),
+    ];
+
+    for (limit, over) in checks {
+        let key = (stats.session_id.clone(), limit);
+        let Some(detail) = over else {
+            breaches.remove(&key);
+            continue;
+        };
+        let breach = breaches.entry(key).or_insert(Breach {
+            since: Instant::now(),
+            acted: false,
+        });
+        if breach.acted || breach.since.elapsed().as_secs() < limits.sustain_secs {
+            continue;
+        }
+        breach.acted = true;
+        act(app, system, limits.action, stats, tree, &detail);
+    }
+}
+
+fn act(app: &AppHandle, system: &System, action: LimitAction, stats: &PtyStats, tree: &[Pid], detail: &str) {
+    let kill = |pids: &[Pid]| {
+        // Children first, so a parent can't respawn them in between
+        pids.iter()
+            .rev()
+            .filter_map(|pid| system.process(*pid))
+            .filter(|p| p.kill())
+            .count()
+    };
+    let text = match action {
+        LimitAction::Warn => format!("Over limit: {}", detail),
+        LimitAction::KillChildren => {
+            let killed = kill(&tree[1..]);
+            format!("Over limit: {}. Killed {} child process(es)", detail, killed)
+        }
+        LimitAction::KillTree => {
+            let reason = format!("Killed over resource limit: {}", detail);
+            if let Ok(mut sessions) = app.state::<PtyState>().0.lock() {
+                if let Some(session) = sessions.get_mut(&stats.session_id) {
+                    session.kill_reason = Some(reason.clone());
+                }
+            }
+            kill(tree);
+            reason
+        }
+    };
+    eprintln!("[monitor] {}: {}", stats.session_id, text);
+    emit_alert(app, &stats.session_id, "resource-limit", "Resource limit exceeded", &text);
+}
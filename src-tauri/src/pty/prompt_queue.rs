@@ -0,0 +1,153 @@
+use serde::{Deserialize, Serialize};
+
+/// History entries kept per queue.
+const MAX_HISTORY: usize = 100;
+
+/// Gate on a queued prompt, checked against Claude's last message when the
+/// prompt comes up. A prompt whose condition fails is skipped.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum PromptCondition {
+    #[serde(rename_all = "camelCase")]
+    LastMessageContains {
+        text: String,
+        #[serde(default)]
+        case_sensitive: bool,
+    },
+    #[serde(rename_all = "camelCase")]
+    LastMessageNotContains {
+        text: String,
+        #[serde(default)]
+        case_sensitive: bool,
+    },
+}
+
+impl PromptCondition {
+    fn holds(&self, last_message: Option<&str>) -> bool {
+        let contains = |needle: &str, case_sensitive: bool| {
+            let hay = last_message.unwrap_or("");
+            if case_sensitive {
+                hay.contains(needle)
+            } else {
+                hay.to_lowercase().contains(&needle.to_lowercase())
+            }
+        };
+        match self {
+            Self::LastMessageContains { text, case_sensitive } => contains(text, *case_sensitive),
+            Self::LastMessageNotContains { text, case_sensitive } => !contains(text, *case_sensitive),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueuedPrompt {
+    pub id: String,
+    pub text: String,
+    #[serde(default)]
+    pub condition: Option<PromptCondition>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PromptOutcome {
+    Sent,
+    Skipped,
+    /// Writing to the PTY failed; the prompt went back to the head of the queue
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PromptHistoryEntry {
+    pub id: String,
+    pub text: String,
+    pub outcome: PromptOutcome,
+    /// Why delivery failed, for `Failed`
+    pub error: Option<String>,
+    /// Unix millis
+    pub at: i64,
+}
+
+/// Prompts waiting to be typed into one PTY tab, oldest first.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PromptQueue {
+    pub items: Vec<QueuedPrompt>,
+    pub paused: bool,
+    /// Most recent first
+    pub history: Vec<PromptHistoryEntry>,
+    /// Claude's last message from the most recent `Stop`, used by conditions
+    pub last_message: Option<String>,
+}
+
+impl PromptQueue {
+    /// Reorder to match `ids`. Unknown ids are ignored; items missing from
+    /// `ids` keep their relative order at the end.
+    pub fn reorder(&mut self, ids: &[String]) {
+        let mut ordered = Vec::with_capacity(self.items.len());
+        for id in ids {
+            if let Some(pos) = self.items.iter().position(|p| &p.id == id) {
+                ordered.push(self.items.remove(pos));
+            }
+        }
+        ordered.append(&mut self.items);
+        self.items = ordered;
+    }
+
+    /// Pop the next prompt whose condition holds, recording skipped ones in
+    /// the history. Report how its delivery went with `sent` or `failed`.
+    pub fn take_next(&mut self) -> Option<QueuedPrompt> {
+        while !self.items.is_empty() {
+            let prompt = self.items.remove(0);
+            let ok = prompt
+                .condition
+                .as_ref()
+                .map(|c| c.holds(self.last_message.as_deref()))
+                .unwrap_or(true);
+            if ok {
+                return Some(prompt);
+            }
+            self.record(&prompt, PromptOutcome::Skipped, None);
+        }
+        None
+    }
+
+    /// Record a prompt from `take_next` as delivered.
+    pub fn sent(&mut self, prompt: &QueuedPrompt) {
+        self.record(prompt, PromptOutcome::Sent, None);
+    }
+
+    /// Record a prompt from `take_next` as failed and put it back at the head
+    /// of the queue, so the next `Stop` tries it again.
+    pub fn failed(&mut self, prompt: QueuedPrompt, error: &str) {
+        self.record(&prompt, PromptOutcome::Failed, Some(error.to_string()));
+        self.items.insert(0, prompt);
+    }
+
+    fn record(&mut self, prompt: &QueuedPrompt, outcome: PromptOutcome, error: Option<String>) {
+        self.history.insert(
+            0,
+            PromptHistoryEntry {
+                id: prompt.id.clone(),
+                text: prompt.text.clone(),
+                outcome,
+                error,
+                at: chrono::Utc::now().timestamp_millis(),
+            },
+        );
+        self.history.truncate(MAX_HISTORY);
+    }
+}
+
+/// Bytes to type a prompt into Claude's input box: multi-line text goes in as a
+/// bracketed paste so embedded newlines don't submit early. Enter is sent
+/// separately (see `deliver_prompt`) so the TUI doesn't treat it as pasted.
+pub fn prompt_input(text: &str) -> String {
+    let text = text.trim_end();
+    if text.contains('\n') {
+        format!("\x1b[200~{}\x1b[201~", text.replace("\r\n", "\n"))
+    } else {
+        text.to_string()
+    }
+}
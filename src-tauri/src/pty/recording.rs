@@ -0,0 +1,160 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Writes a PTY stream to an asciicast v2 file
+/// (https://docs.asciinema.org/manual/asciicast/v2/): a JSON header line
+/// followed by one `[seconds, code, data]` event per line.
+pub struct Recorder {
+    out: BufWriter<File>,
+    started: Instant,
+    path: PathBuf,
+}
+
+/// asciicast v2 header — only the fields the studio writes or reads back.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CastHeader {
+    pub version: u32,
+    pub width: u16,
+    pub height: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub env: Option<Value>,
+}
+
+/// One event from a cast file: `o` = output, `i` = input, `r` = resize ("COLSxROWS").
+#[derive(Debug, Clone)]
+pub struct CastEvent {
+    pub time: f64,
+    pub code: String,
+    pub data: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordingInfo {
+    /// File name, used as the recording id
+    pub id: String,
+    pub path: String,
+    pub title: Option<String>,
+    pub started_at: Option<i64>,
+    pub duration_secs: f64,
+    pub width: u16,
+    pub height: u16,
+    pub size_bytes: u64,
+}
+
+impl Recorder {
+    pub fn create(path: &Path, cols: u16, rows: u16, title: Option<String>) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut out = BufWriter::new(File::create(path)?);
+        let header = CastHeader {
+            version: 2,
+            width: cols,
+            height: rows,
+            timestamp: Some(chrono::Utc::now().timestamp()),
+            title,
+            env: Some(serde_json::json!({ "TERM": "xterm-256color" })),
+        };
+        writeln!(out, "{}", serde_json::to_string(&header)?)?;
+        out.flush()?;
+        Ok(Self {
+            out,
+            started: Instant::now(),
+            path: path.to_path_buf(),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn output(&mut self, data: &str) {
+        self.event("o", data);
+    }
+
+    pub fn resize(&mut self, cols: u16, rows: u16) {
+        self.event("r", &format!("{}x{}", cols, rows));
+    }
+
+    fn event(&mut self, code: &str, data: &str) {
+        let t = self.started.elapsed().as_secs_f64();
+        if let Ok(line) = serde_json::to_string(&(round_time(t), code, data)) {
+            // Best-effort: a full disk must not take down the PTY reader
+            writeln!(self.out, "{}", line).ok();
+            self.out.flush().ok();
+        }
+    }
+}
+
+/// Microsecond precision is plenty and keeps the file compact.
+fn round_time(t: f64) -> f64 {
+    (t * 1_000_000.0).round() / 1_000_000.0
+}
+
+/// Read a cast file's header and events. Malformed event lines are skipped.
+pub fn read_cast(path: &Path) -> Result<(CastHeader, Vec<CastEvent>)> {
+    let reader = BufReader::new(File::open(path)?);
+    let mut lines = reader.lines();
+    let header_line = lines
+        .next()
+        .ok_or_else(|| anyhow!("{} is empty", path.display()))??;
+    let header: CastHeader = serde_json::from_str(&header_line)
+        .map_err(|e| anyhow!("{} has an invalid asciicast header: {}", path.display(), e))?;
+    if header.version != 2 {
+        return Err(anyhow!("Unsupported asciicast version {}", header.version));
+    }
+
+    let events = lines
+        .map_while(|l| l.ok())
+        .filter(|l| !l.trim().is_empty())
+        .filter_map(|l| serde_json::from_str::<(f64, String, String)>(&l).ok())
+        .map(|(time, code, data)| CastEvent { time, code, data })
+        .collect();
+    Ok((header, events))
+}
+
+/// Summarise a cast file for the recordings list.
+pub fn recording_info(path: &Path) -> Result<RecordingInfo> {
+    let (header, events) = read_cast(path)?;
+    let size_bytes = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+    Ok(RecordingInfo {
+        id: path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or_default()
+            .to_string(),
+        path: path.to_string_lossy().replace('\\', "/"),
+        title: header.title,
+        started_at: header.timestamp,
+        duration_secs: events.last().map(|e| e.time).unwrap_or(0.0),
+        width: header.width,
+        height: header.height,
+        size_bytes,
+    })
+}
+
+/// All `.cast` files in `dir`, newest first.
+pub fn list_recordings(dir: &Path) -> Result<Vec<RecordingInfo>> {
+    if !dir.exists() {
+        return Ok(vec![]);
+    }
+    let mut recordings: Vec<RecordingInfo> = std::fs::read_dir(dir)?
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("cast"))
+        .filter_map(|p| recording_info(&p).ok())
+        .collect();
+    recordings.sort_by_key(|r| std::cmp::Reverse(r.started_at));
+    Ok(recordings)
+}
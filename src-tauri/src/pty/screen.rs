@@ -0,0 +1,148 @@
+use regex::Regex;
+use serde::Serialize;
+use std::sync::OnceLock;
+
+/// Tracks the window title, the only terminal callback we care about.
+#[derive(Default)]
+struct ScreenCallbacks {
+    title: Option<String>,
+}
+
+impl vt100::Callbacks for ScreenCallbacks {
+    fn set_window_title(&mut self, _: &mut vt100::Screen, title: &[u8]) {
+        self.title = Some(String::from_utf8_lossy(title).into_owned());
+    }
+}
+
+/// Headless VT100/xterm emulator fed with a session's raw output, so the
+/// backend can see the screen the way xterm.js renders it — redraws, cursor
+/// moves and split escape sequences included.
+pub struct ScreenModel {
+    parser: vt100::Parser<ScreenCallbacks>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreenCursor {
+    pub row: u16,
+    pub col: u16,
+    pub visible: bool,
+}
+
+/// A selection menu found on screen, e.g. Claude's permission prompt.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreenPrompt {
+    pub question: Option<String>,
+    pub options: Vec<String>,
+    /// Index into `options` of the highlighted entry
+    pub selected: Option<usize>,
+}
+
+/// The rendered screen at one point in time (`pty_snapshot`).
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreenSnapshot {
+    pub rows: u16,
+    pub cols: u16,
+    /// One entry per screen row, trailing blanks trimmed
+    pub lines: Vec<String>,
+    pub cursor: ScreenCursor,
+    pub alternate_screen: bool,
+    pub title: Option<String>,
+    pub prompt: Option<ScreenPrompt>,
+    /// The screen as escape sequences that redraw it, when requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ansi: Option<String>,
+}
+
+impl ScreenModel {
+    pub fn new(rows: u16, cols: u16) -> Self {
+        Self {
+            parser: vt100::Parser::new_with_callbacks(rows, cols, 0, ScreenCallbacks::default()),
+        }
+    }
+
+    pub fn process(&mut self, bytes: &[u8]) {
+        self.parser.process(bytes);
+    }
+
+    pub fn resize(&mut self, rows: u16, cols: u16) {
+        self.parser.screen_mut().set_size(rows, cols);
+    }
+
+    /// Screen rows as plain text, trailing blanks trimmed.
+    pub fn lines(&self) -> Vec<String> {
+        let screen = self.parser.screen();
+        let (_, cols) = screen.size();
+        screen.rows(0, cols).map(|r| r.trim_end().to_string()).collect()
+    }
+
+    pub fn snapshot(&self, with_ansi: bool) -> ScreenSnapshot {
+        let screen = self.parser.screen();
+        let (rows, cols) = screen.size();
+        let (row, col) = screen.cursor_position();
+        let lines = self.lines();
+        ScreenSnapshot {
+            rows,
+            cols,
+            prompt: detect_prompt(&lines),
+            lines,
+            cursor: ScreenCursor {
+                row,
+                col,
+                visible: !screen.hide_cursor(),
+            },
+            alternate_screen: screen.alternate_screen(),
+            title: self.parser.callbacks().title.clone(),
+            ansi: with_ansi.then(|| String::from_utf8_lossy(&screen.contents_formatted()).into_owned()),
+        }
+    }
+}
+
+fn option_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    // "❯ 1. Yes" / "  2. No, and tell Claude…", optionally inside a │ box
+    RE.get_or_init(|| Regex::new(r"^[\s│]*([❯>])?\s*(\d+)\.\s+(.+?)[\s│]*$").unwrap())
+}
+
+/// Find a numbered selection menu: at least two consecutive options, with a
+/// highlighted entry or an "Enter to select" hint. The question is the closest
+/// non-blank line above the options.
+pub fn detect_prompt(lines: &[String]) -> Option<ScreenPrompt> {
+    let re = option_regex();
+    let hint = lines.iter().any(|l| l.contains("Enter to select"));
+    // Search from the bottom: the live menu is the one nearest the input area
+    let mut end = lines.len();
+    while end > 0 {
+        let last = lines[..end].iter().rposition(|l| re.is_match(l))?;
+        let mut start = last;
+        while start > 0 && re.is_match(&lines[start - 1]) {
+            start -= 1;
+        }
+        let mut options = Vec::new();
+        let mut selected = None;
+        for (i, line) in lines[start..=last].iter().enumerate() {
+            let caps = re.captures(line)?;
+            if caps.get(1).is_some() {
+                selected = Some(i);
+            }
+            options.push(caps[3].to_string());
+        }
+        if options.len() >= 2 && (selected.is_some() || hint) {
+            let question = lines[..start]
+                .iter()
+                .rev()
+                .map(|l| l.trim_matches(|c: char| c.is_whitespace() || c == '│'))
+                .find(|l| !l.is_empty() && !l.chars().all(|c| "─╭╮╰╯".contains(c)))
+                .map(str::to_string);
+            return Some(ScreenPrompt {
+                question,
+                options,
+                selected,
+            });
+        }
+        end = start;
+    }
+    None
+}
@@ -0,0 +1,141 @@
+use std::collections::VecDeque;
+
+use regex::RegexBuilder;
+use serde::Serialize;
+
+use crate::utils::strip_ansi;
+
+/// Bytes of raw PTY output kept per session for reattach and search.
+pub const SCROLLBACK_BYTES: usize = 2 * 1024 * 1024;
+
+/// Bounded ring buffer of a session's raw PTY output (ANSI sequences included),
+/// so a reloaded webview can replay the terminal instead of starting blank.
+pub struct Scrollback {
+    buf: VecDeque<u8>,
+    capacity: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScrollbackMatch {
+    /// Index of the line within the ANSI-stripped scrollback
+    pub line: usize,
+    /// Character column of the match within the line
+    pub column: usize,
+    pub text: String,
+}
+
+impl Scrollback {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            buf: VecDeque::with_capacity(capacity.min(64 * 1024)),
+            capacity,
+        }
+    }
+
+    pub fn push(&mut self, data: &[u8]) {
+        // A single chunk larger than the buffer only keeps its tail
+        let data = if data.len() > self.capacity {
+            &data[data.len() - self.capacity..]
+        } else {
+            data
+        };
+        let overflow = (self.buf.len() + data.len()).saturating_sub(self.capacity);
+        self.buf.drain(..overflow);
+        self.buf.extend(data);
+    }
+
+    /// The buffered output as text. Bytes of a UTF-8 sequence cut in half by
+    /// eviction at the start, or still incomplete at the end, are left out
+    /// rather than decoded as U+FFFD.
+    pub fn contents(&self) -> String {
+        let bytes = self.bytes();
+        let start = bytes
+            .iter()
+            .position(|b| (b & 0xC0) != 0x80)
+            .unwrap_or(bytes.len());
+        let end = complete_len(&bytes).max(start);
+        String::from_utf8_lossy(&bytes[start..end]).into_owned()
+    }
+
+    /// Trailing bytes of a UTF-8 sequence whose remaining bytes haven't been
+    /// pushed yet; `contents` leaves these out.
+    pub fn incomplete_tail(&self) -> Vec<u8> {
+        let bytes = self.bytes();
+        bytes[complete_len(&bytes)..].to_vec()
+    }
+
+    fn bytes(&self) -> Vec<u8> {
+        let (a, b) = self.buf.as_slices();
+        let mut bytes = Vec::with_capacity(self.buf.len());
+        bytes.extend_from_slice(a);
+        bytes.extend_from_slice(b);
+        bytes
+    }
+
+    /// The last `lines` non-blank lines of the ANSI-stripped scrollback.
+    pub fn tail(&self, lines: usize) -> Vec<String> {
+        let clean = strip_ansi(&self.contents());
+        let mut tail: Vec<String> = clean
+            .split(['\n', '\r'])
+            .map(str::trim_end)
+            .filter(|l| !l.trim().is_empty())
+            .rev()
+            .take(lines)
+            .map(str::to_string)
+            .collect();
+        tail.reverse();
+        tail
+    }
+
+    /// Search the ANSI-stripped scrollback line by line, returning at most
+    /// `limit` matches in output order.
+    pub fn search(&self, query: &str, case_sensitive: bool, limit: usize) -> Vec<ScrollbackMatch> {
+        if query.is_empty() {
+            return vec![];
+        }
+        // Folding case in the regex keeps match offsets on the original line;
+        // lowercasing the line first can change its byte length
+        let Ok(needle) = RegexBuilder::new(&regex::escape(query))
+            .case_insensitive(!case_sensitive)
+            .build()
+        else {
+            return vec![];
+        };
+        let clean = strip_ansi(&self.contents());
+        let mut matches = Vec::new();
+        // Split on both \n and \r — TUI redraws leave bare \r between line fragments
+        for (idx, line) in clean.split(['\n', '\r']).enumerate() {
+            if let Some(m) = needle.find(line) {
+                matches.push(ScrollbackMatch {
+                    line: idx,
+                    column: line[..m.start()].chars().count(),
+                    text: line.trim_end().to_string(),
+                });
+                if matches.len() >= limit {
+                    break;
+                }
+            }
+        }
+        matches
+    }
+}
+
+/// Length of `bytes` without a trailing, not yet complete UTF-8 sequence.
+fn complete_len(bytes: &[u8]) -> usize {
+    let floor = bytes.len().saturating_sub(4);
+    let Some(lead) = (floor..bytes.len()).rev().find(|&i| (bytes[i] & 0xC0) != 0x80) else {
+        return bytes.len();
+    };
+    let width = match bytes[lead] {
+        b if b & 0xE0 == 0xC0 => 2,
+        b if b & 0xF0 == 0xE0 => 3,
+        b if b & 0xF8 == 0xF0 => 4,
+        _ => 1,
+    };
+    if lead + width > bytes.len() {
+        lead
+    } else {
+        bytes.len()
+    }
+}
@@ -0,0 +1,188 @@
+use std::sync::{Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use tauri::ipc::{Channel, InvokeResponseBody};
+
+use crate::pty::scrollback::{Scrollback, ScrollbackMatch, SCROLLBACK_BYTES};
+
+/// How often pending output is flushed to the frontend (~one frame at 60 Hz).
+pub const FRAME_INTERVAL: Duration = Duration::from_millis(16);
+/// Largest single message; bigger backlogs go out over several frames.
+const MAX_FRAME_BYTES: usize = 256 * 1024;
+/// Sent-but-unacknowledged bytes at which sending pauses until `ack` catches up.
+const HIGH_WATER: usize = 1024 * 1024;
+/// Pending bytes at which the reader thread blocks, which in turn stops the
+/// child process once the OS pipe fills up.
+const MAX_PENDING: usize = 4 * 1024 * 1024;
+/// A frontend that hasn't acknowledged anything for this long is assumed to
+/// have dropped the acks (e.g. reloaded mid-write) and the window resets.
+const ACK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Output side of a PTY session: the scrollback plus batched, flow-controlled
+/// delivery of raw bytes to the subscribed frontend channel.
+///
+/// The reader thread `push`es every read; a flusher thread calls `flush` once
+/// per `FRAME_INTERVAL` and sends everything pending as one binary message.
+/// The frontend calls `ack` once xterm.js has processed a message.
+///
+/// Each message starts with the stream offset of its first byte (a
+/// little-endian `u64`), so a subscriber can drop what its snapshot from
+/// `attach` already covers.
+pub struct OutputStream {
+    state: Mutex<StreamState>,
+    /// Signalled whenever pending output drains or the subscriber changes
+    drained: Condvar,
+}
+
+/// Scrollback returned by `attach`, and the stream offset it ends at.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OutputSnapshot {
+    pub data: String,
+    pub offset: u64,
+}
+
+struct StreamState {
+    scrollback: Scrollback,
+    /// Bytes pushed over the stream's lifetime
+    total: u64,
+    channel: Option<Channel<InvokeResponseBody>>,
+    pending: Vec<u8>,
+    in_flight: usize,
+    last_ack: Instant,
+    closed: bool,
+}
+
+impl OutputStream {
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(StreamState {
+                scrollback: Scrollback::new(SCROLLBACK_BYTES),
+                total: 0,
+                channel: None,
+                pending: Vec::new(),
+                in_flight: 0,
+                last_ack: Instant::now(),
+                closed: false,
+            }),
+            drained: Condvar::new(),
+        }
+    }
+
+    /// Record output from the PTY. Blocks while a subscriber is attached and
+    /// too far behind. Without a subscriber only the scrollback is kept.
+    pub fn push(&self, data: &[u8]) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        while state.channel.is_some() && state.pending.len() >= MAX_PENDING {
+            match self.drained.wait_timeout(state, FRAME_INTERVAL * 4) {
+                Ok((guard, _)) => state = guard,
+                Err(_) => return,
+            }
+        }
+        state.scrollback.push(data);
+        state.total += data.len() as u64;
+        if state.channel.is_some() {
+            state.pending.extend_from_slice(data);
+        }
+    }
+
+    /// Subscribe `channel` to live output, replacing any previous subscriber,
+    /// and return the scrollback so far. Output pushed after this call goes to
+    /// the channel; output before it is in the snapshot, so nothing is lost.
+    /// Messages can reach the frontend before the snapshot does, so it holds
+    /// them until the snapshot is written and skips bytes below its offset.
+    pub fn attach(&self, channel: Channel<InvokeResponseBody>) -> OutputSnapshot {
+        let Ok(mut state) = self.state.lock() else {
+            return OutputSnapshot {
+                data: String::new(),
+                offset: 0,
+            };
+        };
+        state.channel = Some(channel);
+        // A character still missing bytes stays out of the snapshot and goes
+        // out whole with the live output instead
+        state.pending = state.scrollback.incomplete_tail();
+        state.in_flight = 0;
+        state.last_ack = Instant::now();
+        self.drained.notify_all();
+        OutputSnapshot {
+            data: state.scrollback.contents(),
+            offset: state.total - state.pending.len() as u64,
+        }
+    }
+
+    /// The frontend finished processing `bytes` of output.
+    pub fn ack(&self, bytes: usize) {
+        if let Ok(mut state) = self.state.lock() {
+            state.in_flight = state.in_flight.saturating_sub(bytes);
+            state.last_ack = Instant::now();
+        }
+    }
+
+    pub fn search(&self, query: &str, case_sensitive: bool, limit: usize) -> Vec<ScrollbackMatch> {
+        self.state
+            .lock()
+            .map(|s| s.scrollback.search(query, case_sensitive, limit))
+            .unwrap_or_default()
+    }
+
+    pub fn tail(&self, lines: usize) -> Vec<String> {
+        self.state
+            .lock()
+            .map(|s| s.scrollback.tail(lines))
+            .unwrap_or_default()
+    }
+
+    /// Mark the stream finished; `flush` keeps draining what's pending.
+    pub fn close(&self) {
+        if let Ok(mut state) = self.state.lock() {
+            state.closed = true;
+        }
+        self.drained.notify_all();
+    }
+
+    /// Send one batch of pending output if the subscriber has room for it.
+    /// Returns `false` once the stream is closed and fully drained.
+    pub fn flush(&self) -> bool {
+        let (channel, batch) = {
+            let Ok(mut state) = self.state.lock() else {
+                return false;
+            };
+            let Some(channel) = state.channel.clone() else {
+                return !state.closed;
+            };
+            if state.pending.is_empty() {
+                return !state.closed;
+            }
+            if state.in_flight >= HIGH_WATER {
+                if state.last_ack.elapsed() < ACK_TIMEOUT {
+                    return true;
+                }
+                state.in_flight = 0;
+            }
+            let take = state.pending.len().min(MAX_FRAME_BYTES);
+            let start = state.total - state.pending.len() as u64;
+            let mut batch = Vec::with_capacity(8 + take);
+            batch.extend_from_slice(&start.to_le_bytes());
+            batch.extend(state.pending.drain(..take));
+            state.in_flight += take;
+            (channel, batch)
+        };
+        self.drained.notify_all();
+
+        if channel.send(InvokeResponseBody::Raw(batch)).is_err() {
+            // Webview is gone; stop buffering until someone re-attaches
+            if let Ok(mut state) = self.state.lock() {
+                if state.channel.as_ref().map(|c| c.id()) == Some(channel.id()) {
+                    state.channel = None;
+                    state.pending.clear();
+                }
+            }
+            self.drained.notify_all();
+        }
+        true
+    }
+}
@@ -0,0 +1,251 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::time::Instant;
+
+use regex::{Captures, Regex, RegexBuilder};
+
+use crate::models::terminal_profile::SessionKind;
+use crate::models::trigger_rule::{TriggerAction, TriggerRule, TriggerSource};
+use crate::utils::strip_ansi;
+
+/// A trigger action with its templates filled in from the match.
+#[derive(Debug, Clone)]
+pub enum FiredAction {
+    Emit {
+        event: String,
+        payload: BTreeMap<String, String>,
+    },
+    Notify {
+        title: String,
+        body: String,
+    },
+    LinkFile {
+        path: String,
+    },
+    Reply {
+        text: String,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct Firing {
+    pub rule_id: String,
+    pub actions: Vec<FiredAction>,
+}
+
+/// Most dedupe keys remembered per session.
+const MAX_DEDUPE_KEYS: usize = 4096;
+
+struct CompiledRule {
+    rule: TriggerRule,
+    pattern: Regex,
+    exclude: Option<Regex>,
+}
+
+/// Per-session matcher for trigger rules. Holds only the rules that apply to
+/// the session's kind and profile, plus the dedupe history.
+pub struct TriggerEngine {
+    rules: Vec<CompiledRule>,
+    /// (rule index, dedupe key) -> last time it fired
+    fired: HashMap<(usize, String), Instant>,
+    /// Screen-rule matches seen on the previous screen scan
+    on_screen: HashSet<(usize, String)>,
+}
+
+fn build_regex(pattern: &str, case_insensitive: bool) -> Result<Regex, regex::Error> {
+    RegexBuilder::new(pattern)
+        .case_insensitive(case_insensitive)
+        .build()
+}
+
+/// Check that a rule's regexes compile, for validation before saving.
+pub fn validate_rule(rule: &TriggerRule) -> Result<(), String> {
+    build_regex(&rule.pattern, rule.case_insensitive)
+        .map_err(|e| format!("Rule '{}': invalid pattern: {}", rule.name, e))?;
+    if let Some(exclude) = &rule.exclude {
+        build_regex(exclude, rule.case_insensitive)
+            .map_err(|e| format!("Rule '{}': invalid exclude pattern: {}", rule.name, e))?;
+    }
+    Ok(())
+}
+
+impl TriggerEngine {
+    pub fn new(rules: &[TriggerRule], kind: SessionKind, profile_id: Option<&str>) -> Self {
+        let rules = rules
+            .iter()
+            .filter(|r| r.enabled)
+            .filter(|r| r.kinds.is_empty() || r.kinds.contains(&kind))
+            .filter(|r| {
+                r.profiles.is_empty()
+                    || profile_id.is_some_and(|id| r.profiles.iter().any(|p| p == id))
+            })
+            .filter_map(|r| {
+                let pattern = match build_regex(&r.pattern, r.case_insensitive) {
+                    Ok(p) => p,
+                    Err(e) => {
+                        eprintln!("[triggers] skipping rule {}: {}", r.id, e);
+                        return None;
+                    }
+                };
+                let exclude = r
+                    .exclude
+                    .as_deref()
+                    .and_then(|x| build_regex(x, r.case_insensitive).ok());
+                Some(CompiledRule {
+                    rule: r.clone(),
+                    pattern,
+                    exclude,
+                })
+            })
+            .collect();
+        Self {
+            rules,
+            fired: HashMap::new(),
+            on_screen: HashSet::new(),
+        }
+    }
+
+    pub fn has_screen_rules(&self) -> bool {
+        self.rules.iter().any(|r| r.rule.source == TriggerSource::Screen)
+    }
+
+    /// Run the output rules over one chunk of output (ANSI is stripped first).
+    pub fn scan(&mut self, data: &str) -> Vec<Firing> {
+        if self.rules.is_empty() {
+            return vec![];
+        }
+        let clean = strip_ansi(data);
+        // Split on both \n and \r — PTY output uses \r\n and ANSI cursor codes
+        // leave bare \r characters that prevent clean line matching.
+        let lines: Vec<&str> = clean.split(['\n', '\r']).collect();
+
+        let mut firings = Vec::new();
+        for idx in 0..self.rules.len() {
+            if self.rules[idx].rule.source != TriggerSource::Output {
+                continue;
+            }
+            let Some((key, firing)) = self.match_rule(idx, &clean, &lines) else {
+                continue;
+            };
+            if !self.take_dedupe(idx, key) {
+                continue;
+            }
+            firings.push(firing);
+            if self.rules[idx].rule.stop {
+                break;
+            }
+        }
+        firings
+    }
+
+    /// Run the screen rules over the rendered screen rows. A rule fires when
+    /// its match appears; while the same match stays on screen it doesn't
+    /// fire again, and `stop` holds back later rules.
+    pub fn scan_screen(&mut self, rows: &[String]) -> Vec<Firing> {
+        let screen = rows.join("\n");
+        let lines: Vec<&str> = rows.iter().map(String::as_str).collect();
+
+        let mut visible = HashSet::new();
+        let mut firings = Vec::new();
+        for idx in 0..self.rules.len() {
+            if self.rules[idx].rule.source != TriggerSource::Screen {
+                continue;
+            }
+            let Some((key, firing)) = self.match_rule(idx, &screen, &lines) else {
+                continue;
+            };
+            let shown = (idx, key.clone());
+            let appeared = !self.on_screen.contains(&shown);
+            visible.insert(shown);
+            if appeared && self.take_dedupe(idx, key) {
+                firings.push(firing);
+            }
+            if self.rules[idx].rule.stop {
+                break;
+            }
+        }
+        self.on_screen = visible;
+        firings
+    }
+
+    /// The rule's first match in `clean` (whole) or `lines`, as its dedupe key
+    /// and the filled-in actions.
+    fn match_rule(&self, idx: usize, clean: &str, lines: &[&str]) -> Option<(String, Firing)> {
+        let compiled = &self.rules[idx];
+        let haystacks: &[&str] = if compiled.rule.whole_chunk { &[clean] } else { lines };
+        let caps = haystacks.iter().find_map(|hay| {
+            let caps = compiled.pattern.captures(hay)?;
+            match &compiled.exclude {
+                Some(exclude) if exclude.is_match(hay) => None,
+                _ => Some(caps),
+            }
+        })?;
+
+        // Dedupe on the first capture group when there is one (e.g. the question
+        // text), so redraws around it don't count as a new match
+        let matched = caps.get(1).or_else(|| caps.get(0)).map(|m| m.as_str()).unwrap_or("");
+        let firing = Firing {
+            rule_id: compiled.rule.id.clone(),
+            actions: compiled
+                .rule
+                .actions
+                .iter()
+                .map(|a| fill_action(a, &caps))
+                .collect(),
+        };
+        Some((matched.to_string(), firing))
+    }
+
+    /// Record a firing of `key` unless it repeats within the rule's dedupe window.
+    fn take_dedupe(&mut self, idx: usize, key: String) -> bool {
+        let key = (idx, key);
+        if let Some(last) = self.fired.get(&key) {
+            match self.rules[idx].rule.dedupe_secs {
+                None => return false,
+                Some(secs) if last.elapsed().as_secs() < secs => return false,
+                _ => {}
+            }
+        }
+        // Entries past their rule's window can't suppress anything any more
+        let rules = &self.rules;
+        self.fired.retain(|(i, _), last| match rules[*i].rule.dedupe_secs {
+            Some(secs) => last.elapsed().as_secs() < secs,
+            None => true,
+        });
+        // Once-per-session keys never expire; past the cap the oldest is forgotten
+        if self.fired.len() >= MAX_DEDUPE_KEYS {
+            if let Some(oldest) = self.fired.iter().min_by_key(|(_, t)| **t).map(|(k, _)| k.clone()) {
+                self.fired.remove(&oldest);
+            }
+        }
+        self.fired.insert(key, Instant::now());
+        true
+    }
+}
+
+fn expand(template: &str, caps: &Captures) -> String {
+    let mut out = String::new();
+    caps.expand(template, &mut out);
+    out
+}
+
+fn fill_action(action: &TriggerAction, caps: &Captures) -> FiredAction {
+    match action {
+        TriggerAction::Emit { event, payload } => FiredAction::Emit {
+            event: event.clone(),
+            payload: payload
+                .iter()
+                .map(|(k, v)| (k.clone(), expand(v, caps)))
+                .collect(),
+        },
+        TriggerAction::Notify { title, body } => FiredAction::Notify {
+            title: expand(title, caps),
+            body: expand(body, caps),
+        },
+        TriggerAction::LinkFile { path } => FiredAction::LinkFile {
+            path: expand(path, caps),
+        },
+        TriggerAction::Reply { text } => FiredAction::Reply {
+            text: expand(text, caps),
+        },
+    }
+}
@@ -13,3 +13,21 @@ pub fn silent_command(program: &str) -> std::process::Command {
     }
     cmd
 }
+
+/// Strip ANSI escape sequences from a string.
+pub fn strip_ansi(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            match chars.next() {
+                Some('[') => { while let Some(c) = chars.next() { if c.is_ascii_alphabetic() { break; } } }
+                Some(']') => { while let Some(c) = chars.next() { if c == '\x07' || c == '\\' { break; } } }
+                _ => {}
+            }
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
@@ -1,7 +1,8 @@
 use notify::{Config, RecommendedWatcher, RecursiveMode, Watcher};
-use std::path::{Component, PathBuf};
+use std::path::{Component, Path};
+use std::sync::Mutex;
 use std::time::Duration;
-use tauri::Emitter;
+use tauri::{Emitter, Manager};
 use serde::Serialize;
 
 #[derive(Debug, Clone, Serialize)]
@@ -18,33 +19,31 @@ struct SessionChangedPayload {
     encoded_project_dir: String,
 }
 
-/// Check whether the given path has a specific directory name as a direct child
-/// of the `.claude` home directory.  For example, for `segment = "teams"` this
-/// matches `~/.claude/teams/…` but NOT `~/.claude/projects/foo/teams`.
-fn is_claude_child(path: &std::path::Path, segment: &str) -> bool {
-    let mut components = path.components().peekable();
-    while let Some(c) = components.next() {
-        if c == Component::Normal(".claude".as_ref()) {
-            // The very next component must be `segment`
-            return components.next() == Some(Component::Normal(segment.as_ref()));
-        }
+/// Holds the running Claude home watcher; replacing it stops the old one's thread.
+pub struct ClaudeWatcherState(pub Mutex<Option<RecommendedWatcher>>);
+
+impl ClaudeWatcherState {
+    pub fn new() -> Self {
+        Self(Mutex::new(None))
     }
-    false
 }
 
-fn get_claude_home() -> Option<PathBuf> {
-    let home = std::env::var("USERPROFILE")
-        .or_else(|_| std::env::var("HOME"))
+/// Check whether the given path has a specific directory name as a direct child
+/// of the Claude home directory.  For example, for `segment = "teams"` this
+/// matches `~/.claude/teams/…` but NOT `~/.claude/projects/foo/teams`.
+fn is_claude_child(path: &Path, claude_home: &Path, segment: &str) -> bool {
+    path.strip_prefix(claude_home)
         .ok()
-        .filter(|h| !h.is_empty())?;
-    Some(PathBuf::from(home).join(".claude"))
+        .and_then(|rest| rest.components().next())
+        == Some(Component::Normal(segment.as_ref()))
 }
 
+/// Watch the active Claude home, replacing the watcher of any previous one.
 pub fn start_claude_watcher(app_handle: tauri::AppHandle) {
-    let claude_home = match get_claude_home() {
-        Some(p) => p,
-        None => {
-            eprintln!("Cannot start claude watcher: neither USERPROFILE nor HOME is set");
+    let claude_home = match crate::paths::claude_home() {
+        Ok(p) => p,
+        Err(e) => {
+            eprintln!("Cannot start claude watcher: {}", e);
             return;
         }
     };
@@ -114,9 +113,12 @@ pub fn start_claude_watcher(app_handle: tauri::AppHandle) {
     }
 
     let ide_dir_for_thread = ide_dir.clone();
+    // Dropping the previous watcher closes its channel and ends its thread
+    if let Ok(mut guard) = app_handle.state::<ClaudeWatcherState>().0.lock() {
+        *guard = Some(watcher);
+    }
 
     std::thread::spawn(move || {
-        let _watcher = watcher; // Keep alive
         for result in rx {
             match result {
                 Ok(event) => {
@@ -127,13 +129,13 @@ pub fn start_claude_watcher(app_handle: tauri::AppHandle) {
                     let path_str = path.to_string_lossy().to_string();
 
                     // Classify by path component (direct child of .claude/) and emit targeted events
-                    if is_claude_child(path, "teams") && path_str.contains("inboxes") {
+                    if is_claude_child(path, &claude_home, "teams") && path_str.contains("inboxes") {
                         let _ = app_handle.emit("inbox-changed", &path_str);
-                    } else if is_claude_child(path, "teams") {
+                    } else if is_claude_child(path, &claude_home, "teams") {
                         let _ = app_handle.emit("team-changed", &path_str);
-                    } else if is_claude_child(path, "projects") && path_str.ends_with(".jsonl") {
+                    } else if is_claude_child(path, &claude_home, "projects") && path_str.ends_with(".jsonl") {
                         let _ = app_handle.emit("transcript-updated", &path_str);
-                    } else if is_claude_child(path, "projects")
+                    } else if is_claude_child(path, &claude_home, "projects")
                         && path_str.ends_with("sessions-index.json")
                     {
                         let encoded_project_dir = path
@@ -145,15 +147,15 @@ pub fn start_claude_watcher(app_handle: tauri::AppHandle) {
                             "session-changed",
                             &SessionChangedPayload { encoded_project_dir },
                         );
-                    } else if is_claude_child(path, "todos") {
+                    } else if is_claude_child(path, &claude_home, "todos") {
                         let _ = app_handle.emit("todos-changed", &path_str);
-                    } else if is_claude_child(path, "plans") {
+                    } else if is_claude_child(path, &claude_home, "plans") {
                         let _ = app_handle.emit("plans-changed", &path_str);
-                    } else if is_claude_child(path, "projects")
+                    } else if is_claude_child(path, &claude_home, "projects")
                         && (path_str.contains("/notes/") || path_str.contains("\\notes\\"))
                     {
                         let _ = app_handle.emit("notes-changed", &path_str);
-                    } else if is_claude_child(path, "theassociate")
+                    } else if is_claude_child(path, &claude_home, "theassociate")
                         && (path_str.contains("/notes/") || path_str.contains("\\notes\\"))
                     {
                         let _ = app_handle.emit("notes-changed", &path_str);
@@ -196,41 +198,49 @@ pub fn start_claude_watcher(app_handle: tauri::AppHandle) {
                                                 if let Some(ref msg) = hook_event.last_assistant_message {
                                                     if crate::data::summaries::is_completion_summary(msg) {
                                                         if let Some(ref cwd) = hook_event.cwd {
-                                                            let claude_home = get_claude_home();
-                                                            if let Some(home) = claude_home {
-                                                                let encoded = crate::data::path_encoding::encode_project_path(
-                                                                    &std::path::PathBuf::from(cwd)
-                                                                );
-                                                                let sessions_dir = home
-                                                                    .join("theassociate")
-                                                                    .join("projects")
-                                                                    .join(&encoded);
-                                                                std::fs::create_dir_all(&sessions_dir).ok();
-                                                                match crate::data::summaries::save_summary(
-                                                                    &sessions_dir,
-                                                                    &hook_event.session_id,
-                                                                    msg,
-                                                                ) {
-                                                                    Ok(filename) => {
-                                                                        let preview: String = msg.chars().take(200).collect();
-                                                                        let payload = SummaryPayload {
-                                                                            session_id: hook_event.session_id.clone(),
-                                                                            project_path: cwd.clone(),
-                                                                            project_dir: encoded,
-                                                                            filename,
-                                                                            preview,
-                                                                        };
-                                                                        let _ = app_handle.emit("session-summary", &payload);
-                                                                    }
-                                                                    Err(e) => {
-                                                                        eprintln!("[watcher] failed to save summary: {}", e);
-                                                                    }
+                                                            let encoded = crate::data::path_encoding::encode_project_path(
+                                                                &std::path::PathBuf::from(cwd)
+                                                            );
+                                                            let sessions_dir = ide_dir_for_thread
+                                                                .join("projects")
+                                                                .join(&encoded);
+                                                            std::fs::create_dir_all(&sessions_dir).ok();
+                                                            match crate::data::summaries::save_summary(
+                                                                &sessions_dir,
+                                                                &hook_event.session_id,
+                                                                msg,
+                                                            ) {
+                                                                Ok(filename) => {
+                                                                    let preview: String = msg.chars().take(200).collect();
+                                                                    let payload = SummaryPayload {
+                                                                        session_id: hook_event.session_id.clone(),
+                                                                        project_path: cwd.clone(),
+                                                                        project_dir: encoded,
+                                                                        filename,
+                                                                        preview,
+                                                                    };
+                                                                    let _ = app_handle.emit("session-summary", &payload);
+                                                                }
+                                                                Err(e) => {
+                                                                    eprintln!("[watcher] failed to save summary: {}", e);
                                                                 }
                                                             }
                                                         }
                                                     }
                                                 }
                                             }
+                                            // Session went idle: deliver the next queued prompt
+                                            // and mark any broadcast it was part of as finished
+                                            if hook_event.hook_event_name == "Stop" {
+                                                crate::commands::prompt_queue::on_session_stop(
+                                                    &app_handle,
+                                                    &hook_event,
+                                                );
+                                                crate::commands::broadcast::on_session_stop(
+                                                    &app_handle,
+                                                    &hook_event,
+                                                );
+                                            }
                                             let _ =
                                                 app_handle.emit("hook-event", &hook_event);
                                         }
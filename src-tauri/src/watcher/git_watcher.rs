@@ -1,7 +1,9 @@
-use notify::{Config, RecommendedWatcher, RecursiveMode, Watcher};
-use std::path::PathBuf;
-use std::sync::{Arc, Mutex};
-use std::time::Duration;
+use notify::{Config, Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::{HashMap, HashSet};
+use std::path::{Component, Path, PathBuf};
+use std::sync::mpsc::RecvTimeoutError;
+use std::sync::{Arc, Mutex, Weak};
+use std::time::{Duration, Instant};
 use tauri::Emitter;
 
 #[derive(Debug, Clone, serde::Serialize)]
@@ -10,22 +12,64 @@ pub struct GitBranchPayload {
     pub branch: String,
 }
 
-/// Holds the active git HEAD watcher so it can be replaced when the project changes.
+/// Payload of `git-status-changed` and `git-refs-changed`.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct GitRepoPayload {
+    pub cwd: String,
+}
+
+/// Events are emitted once the repository has been quiet this long...
+const QUIET_PERIOD: Duration = Duration::from_millis(300);
+/// ...or at least this often while it keeps changing (a long build, a big checkout).
+const MAX_DELAY: Duration = Duration::from_secs(2);
+/// inotify needs a watch per directory; stop adding them past this many.
+const MAX_DIR_WATCHES: usize = 8192;
+/// inotify can't watch a tree, so on Linux each non-ignored directory is
+/// watched on its own. Elsewhere the native recursive watch is cheap.
+const PER_DIR_WATCHES: bool = cfg!(target_os = "linux");
+
+/// Working-tree directories with a watch of their own, kept by the watcher thread.
+#[derive(Default)]
+struct DirWatches {
+    dirs: HashSet<PathBuf>,
+    /// Reaching `MAX_DIR_WATCHES` is logged once per repository
+    limit_logged: bool,
+}
+
+impl DirWatches {
+    fn is_full(&self) -> bool {
+        self.dirs.len() >= MAX_DIR_WATCHES
+    }
+}
+
+/// Git-dir entries that change what `git status` reports.
+const STATUS_FILES: &[&str] = &[
+    "index",
+    "MERGE_HEAD",
+    "REBASE_HEAD",
+    "CHERRY_PICK_HEAD",
+    "REVERT_HEAD",
+    "rebase-merge",
+    "rebase-apply",
+];
+
+/// One watched repository; dropping it stops its thread.
 pub(crate) struct GitWatcherInner {
-    _watcher: RecommendedWatcher,
-    _cwd: String,
+    _watcher: Arc<Mutex<RecommendedWatcher>>,
 }
 
-pub struct GitWatcherState(pub Arc<Mutex<Option<GitWatcherInner>>>);
+/// Running watchers keyed by the directory the frontend asked for, so every
+/// open project and worktree stays fresh, not just the visible one.
+pub struct GitWatcherState(pub Arc<Mutex<HashMap<String, GitWatcherInner>>>);
 
 impl GitWatcherState {
     pub fn new() -> Self {
-        Self(Arc::new(Mutex::new(None)))
+        Self(Arc::new(Mutex::new(HashMap::new())))
     }
 }
 
 /// Read the current branch from the HEAD file inside the given git directory.
-fn read_head_branch(git_dir: &PathBuf) -> Option<String> {
+fn read_head_branch(git_dir: &Path) -> Option<String> {
     let head_path = git_dir.join("HEAD");
     let content = std::fs::read_to_string(&head_path).ok()?;
     let trimmed = content.trim();
@@ -39,75 +83,296 @@ fn read_head_branch(git_dir: &PathBuf) -> Option<String> {
     }
 }
 
-/// Start watching `.git/HEAD` for the given project directory.
-/// Replaces any existing watcher.  Emits `"git-branch-changed"` when the
-/// branch changes.
-pub fn start_git_head_watch(
+/// What a batch of file events touched.
+#[derive(Default)]
+struct Changes {
+    head: bool,
+    refs: bool,
+    status: bool,
+    /// A `.gitignore` changed, so directories may have become unignored
+    ignore_rules: bool,
+}
+
+/// Everything the watcher thread needs to classify events.
+struct RepoPaths {
+    repo: git2::Repository,
+    /// The worktree's own git dir (`.git`, or `.git/worktrees/<name>`)
+    git_dir: PathBuf,
+    /// The shared git dir holding refs; the same as `git_dir` outside worktrees
+    common_dir: PathBuf,
+    workdir: Option<PathBuf>,
+}
+
+impl RepoPaths {
+    fn classify(&self, path: &Path, changes: &mut Changes) {
+        if path.extension().is_some_and(|ext| ext == "lock") {
+            return;
+        }
+        if let Ok(rel) = path.strip_prefix(&self.git_dir) {
+            self.classify_git_path(rel, changes);
+            return;
+        }
+        if let Ok(rel) = path.strip_prefix(&self.common_dir) {
+            self.classify_git_path(rel, changes);
+            return;
+        }
+        let Some(workdir) = &self.workdir else {
+            return;
+        };
+        let Ok(rel) = path.strip_prefix(workdir) else {
+            return;
+        };
+        if rel.components().any(|c| c == Component::Normal(".git".as_ref())) {
+            return;
+        }
+        if rel.file_name().is_some_and(|name| name == ".gitignore") {
+            changes.ignore_rules = true;
+            changes.status = true;
+            return;
+        }
+        if !rel.as_os_str().is_empty() && !self.repo.is_path_ignored(rel).unwrap_or(false) {
+            changes.status = true;
+        }
+    }
+
+    fn classify_git_path(&self, rel: &Path, changes: &mut Changes) {
+        let Some(Component::Normal(first)) = rel.components().next() else {
+            return;
+        };
+        let first = first.to_string_lossy();
+        match first.as_ref() {
+            "HEAD" => {
+                changes.head = true;
+                changes.refs = true;
+                changes.status = true;
+            }
+            "refs" | "packed-refs" => changes.refs = true,
+            name if STATUS_FILES.contains(&name) => changes.status = true,
+            _ => {}
+        }
+    }
+
+    /// Whether a working-tree directory should get its own watch.
+    fn wants_dir(&self, dir: &Path) -> bool {
+        let Some(workdir) = &self.workdir else {
+            return false;
+        };
+        let Ok(rel) = dir.strip_prefix(workdir) else {
+            return false;
+        };
+        if rel.as_os_str().is_empty() {
+            return true;
+        }
+        !rel.components().any(|c| c == Component::Normal(".git".as_ref()))
+            && !self.repo.is_path_ignored(rel).unwrap_or(true)
+    }
+}
+
+/// Record what `event` touched, and on Linux watch directories it created.
+fn handle_event(
+    paths: &RepoPaths,
+    watcher: &Weak<Mutex<RecommendedWatcher>>,
+    event: Event,
+    changes: &mut Changes,
+    watched: &mut DirWatches,
+) {
+    // inotify reports reads too, and refreshing the status reads the repository
+    if matches!(event.kind, EventKind::Access(_)) {
+        return;
+    }
+    for path in &event.paths {
+        paths.classify(path, changes);
+    }
+    if !PER_DIR_WATCHES {
+        return;
+    }
+    match event.kind {
+        // Past the limit a new directory would only be logged again, not watched
+        EventKind::Create(_) if !watched.is_full() => {
+            if let Some(watcher) = watcher.upgrade() {
+                for path in event.paths.iter().filter(|p| p.is_dir()) {
+                    watch_tree(&watcher, paths, path, watched);
+                }
+            }
+        }
+        // inotify drops the watches of deleted directories itself
+        EventKind::Remove(_) => {
+            for path in &event.paths {
+                watched.dirs.retain(|dir| !dir.starts_with(path));
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Add a non-recursive watch to `dir` and every non-ignored directory below
+/// it that isn't watched yet.
+fn watch_tree(watcher: &Mutex<RecommendedWatcher>, paths: &RepoPaths, dir: &Path, watched: &mut DirWatches) {
+    let Ok(mut watcher) = watcher.lock() else {
+        return;
+    };
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        if watched.is_full() {
+            if !watched.limit_logged {
+                watched.limit_logged = true;
+                eprintln!(
+                    "[git-watcher] watch limit of {} directories reached; {} and later directories not watched",
+                    MAX_DIR_WATCHES,
+                    dir.display()
+                );
+            }
+            return;
+        }
+        if !watched.dirs.contains(&dir) {
+            if !paths.wants_dir(&dir) || watcher.watch(&dir, RecursiveMode::NonRecursive).is_err() {
+                continue;
+            }
+            watched.dirs.insert(dir.clone());
+        }
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            // `file_type` doesn't follow symlinks, so linked trees aren't walked
+            if entry.file_type().is_ok_and(|t| t.is_dir()) {
+                pending.push(entry.path());
+            }
+        }
+    }
+}
+
+/// Start watching the repository containing `cwd`: its HEAD, index and refs,
+/// and the working tree minus ignored paths. Emits `git-status-changed` and
+/// `git-refs-changed` (debounced), plus `git-branch-changed` when HEAD moves to
+/// another branch. Watching an already-watched `cwd` does nothing.
+pub fn start_repo_watch(
     app_handle: tauri::AppHandle,
     cwd: String,
     state: &GitWatcherState,
 ) -> Result<(), String> {
-    // Drop the previous watcher (stops its thread)
-    if let Ok(mut guard) = state.0.lock() {
-        *guard = None;
+    if state.0.lock().map(|m| m.contains_key(&cwd)).unwrap_or(false) {
+        return Ok(());
     }
 
     // Resolve the actual git directory (handles worktrees transparently)
     let repo = git2::Repository::discover(&cwd)
         .map_err(|e| format!("Not a git repository: {}", e))?;
     let git_dir = repo.path().to_path_buf();
+    let common_dir = repo.commondir().to_path_buf();
+    let workdir = repo.workdir().map(Path::to_path_buf);
+    let paths = RepoPaths {
+        repo,
+        git_dir,
+        common_dir,
+        workdir,
+    };
 
-    let initial_branch = read_head_branch(&git_dir).unwrap_or_default();
-
-    let (tx, rx) = std::sync::mpsc::channel();
+    let (tx, rx) = std::sync::mpsc::channel::<notify::Result<Event>>();
     let mut watcher = RecommendedWatcher::new(
         tx,
         Config::default().with_poll_interval(Duration::from_millis(500)),
     )
     .map_err(|e| format!("Failed to create git watcher: {}", e))?;
 
-    // Watch the git directory (non-recursive) so we catch HEAD changes even
-    // when git performs atomic rename operations.
+    // The git dirs non-recursively so we catch HEAD and index changes even
+    // when git performs atomic rename operations; refs as a tree.
     watcher
-        .watch(&git_dir, RecursiveMode::NonRecursive)
+        .watch(&paths.git_dir, RecursiveMode::NonRecursive)
         .map_err(|e| format!("Failed to watch git dir: {}", e))?;
+    if paths.common_dir != paths.git_dir {
+        watcher.watch(&paths.common_dir, RecursiveMode::NonRecursive).ok();
+    }
+    watcher.watch(&paths.common_dir.join("refs"), RecursiveMode::Recursive).ok();
+    if !PER_DIR_WATCHES {
+        if let Some(workdir) = &paths.workdir {
+            watcher
+                .watch(workdir, RecursiveMode::Recursive)
+                .map_err(|e| format!("Failed to watch working tree: {}", e))?;
+        }
+    }
 
+    let watcher = Arc::new(Mutex::new(watcher));
+    let initial_branch = read_head_branch(&paths.git_dir).unwrap_or_default();
+    // The thread only holds a weak reference, so dropping the state's entry
+    // drops the watcher, closes the channel and ends the loop
+    let weak_watcher: Weak<Mutex<RecommendedWatcher>> = Arc::downgrade(&watcher);
     let cwd_for_thread = cwd.clone();
-    let git_dir_for_thread = git_dir.clone();
 
     std::thread::spawn(move || {
         let mut last_branch = initial_branch;
-        // Small debounce: collapse rapid successive events
-        let debounce = Duration::from_millis(100);
-
-        for result in &rx {
-            if result.is_err() {
-                continue;
+        let mut watched = DirWatches::default();
+        // Walking a large tree takes a while, so it happens here rather than
+        // in the command
+        if PER_DIR_WATCHES {
+            if let (Some(watcher), Some(workdir)) = (weak_watcher.upgrade(), paths.workdir.clone()) {
+                watch_tree(&watcher, &paths, &workdir, &mut watched);
+            }
+        }
+        loop {
+            let mut changes = Changes::default();
+            match rx.recv() {
+                Ok(Ok(event)) => handle_event(&paths, &weak_watcher, event, &mut changes, &mut watched),
+                Ok(Err(_)) => continue,
+                Err(_) => return,
+            }
+            // Collect the rest of the burst
+            let started = Instant::now();
+            let mut closed = false;
+            while let Some(left) = MAX_DELAY.checked_sub(started.elapsed()) {
+                match rx.recv_timeout(QUIET_PERIOD.min(left)) {
+                    Ok(Ok(event)) => handle_event(&paths, &weak_watcher, event, &mut changes, &mut watched),
+                    Ok(Err(_)) => {}
+                    Err(RecvTimeoutError::Timeout) => break,
+                    Err(RecvTimeoutError::Disconnected) => {
+                        closed = true;
+                        break;
+                    }
+                }
+            }
+            if closed {
+                return;
             }
 
-            // Drain any queued events within the debounce window
-            std::thread::sleep(debounce);
-            while rx.try_recv().is_ok() {}
-
-            if let Some(current) = read_head_branch(&git_dir_for_thread) {
-                if current != last_branch {
-                    last_branch = current.clone();
-                    let payload = GitBranchPayload {
-                        cwd: cwd_for_thread.clone(),
-                        branch: current,
-                    };
-                    let _ = app_handle.emit("git-branch-changed", &payload);
+            if changes.ignore_rules && PER_DIR_WATCHES {
+                if let (Some(watcher), Some(workdir)) = (weak_watcher.upgrade(), paths.workdir.clone()) {
+                    watch_tree(&watcher, &paths, &workdir, &mut watched);
+                }
+            }
+            let payload = GitRepoPayload {
+                cwd: cwd_for_thread.clone(),
+            };
+            if changes.refs {
+                let _ = app_handle.emit("git-refs-changed", &payload);
+            }
+            if changes.status {
+                let _ = app_handle.emit("git-status-changed", &payload);
+            }
+            if changes.head {
+                if let Some(current) = read_head_branch(&paths.git_dir) {
+                    if current != last_branch {
+                        last_branch = current.clone();
+                        let payload = GitBranchPayload {
+                            cwd: cwd_for_thread.clone(),
+                            branch: current,
+                        };
+                        let _ = app_handle.emit("git-branch-changed", &payload);
+                    }
                 }
             }
         }
     });
 
     if let Ok(mut guard) = state.0.lock() {
-        *guard = Some(GitWatcherInner {
-            _watcher: watcher,
-            _cwd: cwd,
-        });
+        guard.insert(cwd, GitWatcherInner { _watcher: watcher });
     }
 
     Ok(())
 }
+
+/// Stop watching `cwd`; a no-op when it isn't watched.
+pub fn stop_repo_watch(cwd: &str, state: &GitWatcherState) {
+    if let Ok(mut guard) = state.0.lock() {
+        guard.remove(cwd);
+    }
+}
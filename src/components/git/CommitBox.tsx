@@ -0,0 +1,139 @@
+import { useState } from "react";
+import { GitCommitHorizontal, Loader2, Sparkles } from "lucide-react";
+import { useQueryClient } from "@tanstack/react-query";
+import { gitCommit, gitSuggestCommitMessage } from "@/lib/tauri";
+import { useOutputStore } from "@/stores/outputStore";
+
+interface CommitBoxProps {
+  cwd: string;
+  stagedCount: number;
+  /** A merge is in progress: an empty message uses .git/MERGE_MSG */
+  merging: boolean;
+}
+
+export function CommitBox({ cwd, stagedCount, merging }: CommitBoxProps) {
+  const queryClient = useQueryClient();
+  const addMessage = useOutputStore((s) => s.addMessage);
+  const [message, setMessage] = useState("");
+  const [amend, setAmend] = useState(false);
+  const [signOff, setSignOff] = useState(false);
+  const [showAuthor, setShowAuthor] = useState(false);
+  const [authorName, setAuthorName] = useState("");
+  const [authorEmail, setAuthorEmail] = useState("");
+  const [busy, setBusy] = useState<"commit" | "suggest" | null>(null);
+  const [error, setError] = useState<string | null>(null);
+
+  const author =
+    showAuthor && authorName.trim() && authorEmail.trim()
+      ? { name: authorName.trim(), email: authorEmail.trim() }
+      : null;
+  const canCommit = !busy && (amend || merging || (stagedCount > 0 && message.trim().length > 0));
+
+  const handleCommit = async () => {
+    if (!canCommit) return;
+    setBusy("commit");
+    setError(null);
+    try {
+      const commit = await gitCommit(cwd, { message, amend, signOff, author });
+      addMessage(
+        "success",
+        `${commit.shortHash} ${commit.summary}${commit.signed ? " (signed)" : ""}`,
+        amend ? "git commit --amend" : "git commit"
+      );
+      setMessage("");
+      setAmend(false);
+      queryClient.invalidateQueries({ queryKey: ["git-status", cwd] });
+      queryClient.invalidateQueries({ queryKey: ["git-log", cwd] });
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setBusy(null);
+    }
+  };
+
+  const handleSuggest = async () => {
+    setBusy("suggest");
+    setError(null);
+    try {
+      setMessage(await gitSuggestCommitMessage(cwd));
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setBusy(null);
+    }
+  };
+
+  return (
+    <div className="flex flex-col gap-1.5 px-3 py-2 border-b border-border-muted text-[10px]">
+      <textarea
+        value={message}
+        onChange={(e) => setMessage(e.target.value)}
+        onKeyDown={(e) => {
+          if (e.key === "Enter" && (e.ctrlKey || e.metaKey)) {
+            e.preventDefault();
+            handleCommit();
+          }
+        }}
+        placeholder={
+          amend
+            ? "Message (empty keeps the current one)"
+            : merging
+              ? "Message (empty uses the merge message)"
+              : "Commit message (Ctrl+Enter to commit)"
+        }
+        rows={3}
+        className="bg-bg-raised border border-border-muted rounded-lg px-2 py-1 text-xs outline-none resize-none focus:border-border-focus"
+      />
+      <div className="flex items-center gap-3 text-text-muted">
+        <label className="flex items-center gap-1">
+          <input type="checkbox" checked={amend} onChange={(e) => setAmend(e.target.checked)} />
+          Amend
+        </label>
+        <label className="flex items-center gap-1">
+          <input type="checkbox" checked={signOff} onChange={(e) => setSignOff(e.target.checked)} />
+          Sign-off
+        </label>
+        <label className="flex items-center gap-1">
+          <input type="checkbox" checked={showAuthor} onChange={(e) => setShowAuthor(e.target.checked)} />
+          Author
+        </label>
+      </div>
+      {showAuthor && (
+        <div className="flex gap-1">
+          <input
+            value={authorName}
+            onChange={(e) => setAuthorName(e.target.value)}
+            placeholder="Name"
+            className="flex-1 min-w-0 bg-bg-raised border border-border-muted rounded px-1.5 py-0.5 outline-none"
+          />
+          <input
+            value={authorEmail}
+            onChange={(e) => setAuthorEmail(e.target.value)}
+            placeholder="email@example.com"
+            className="flex-1 min-w-0 bg-bg-raised border border-border-muted rounded px-1.5 py-0.5 outline-none"
+          />
+        </div>
+      )}
+      <div className="flex items-center gap-1">
+        <button
+          onClick={handleCommit}
+          disabled={!canCommit}
+          className="flex flex-1 items-center justify-center gap-1 px-2.5 py-1 rounded-lg bg-accent-primary/10 text-accent-primary disabled:opacity-40 transition-all duration-200"
+        >
+          {busy === "commit" ? <Loader2 size={9} className="animate-spin" /> : <GitCommitHorizontal size={9} />}
+          {amend ? "Amend" : `Commit${stagedCount > 0 ? ` ${stagedCount}` : ""}`}
+        </button>
+        <button
+          onClick={handleSuggest}
+          disabled={!!busy || stagedCount === 0}
+          title="Suggest a message for the staged changes"
+          className="flex items-center gap-1 px-2 py-1 rounded-lg border border-border-muted text-text-muted hover:text-text-primary hover:border-border-focus disabled:opacity-40 transition-all duration-200"
+        >
+          {busy === "suggest" ? <Loader2 size={9} className="animate-spin" /> : <Sparkles size={9} />}
+          Suggest
+        </button>
+      </div>
+      {error && <p className="text-status-error whitespace-pre-wrap">{error}</p>}
+    </div>
+  );
+}
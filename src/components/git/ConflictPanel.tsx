@@ -0,0 +1,300 @@
+import { useState } from "react";
+import { ChevronDown, ChevronRight, GitMerge, Loader2, Sparkles } from "lucide-react";
+import { useQueryClient } from "@tanstack/react-query";
+import { useGitConflicts } from "@/hooks/useClaudeData";
+import {
+  gitResolveConflict,
+  gitMarkResolved,
+  gitOperationContinue,
+  gitOperationSkip,
+  gitOperationAbort,
+  gitSuggestResolution,
+} from "@/lib/tauri";
+import type { ConflictFile, ConflictKind, ConflictState, GitOperation } from "@/lib/tauri";
+import { cn } from "@/lib/utils";
+
+export const CONFLICT_LABELS: Record<ConflictKind, string> = {
+  bothModified: "Both modified",
+  bothAdded: "Both added",
+  bothDeleted: "Both deleted",
+  addedByUs: "Added by us",
+  addedByThem: "Added by them",
+  deletedByUs: "Deleted by us",
+  deletedByThem: "Deleted by them",
+};
+
+const OPERATION_LABELS: Record<GitOperation, string> = {
+  merge: "Merging",
+  rebase: "Rebasing",
+  cherryPick: "Cherry-picking",
+  revert: "Reverting",
+};
+
+interface ConflictPanelProps {
+  cwd: string;
+  operation: GitOperation;
+}
+
+/** Banner and per-file resolution for a merge, rebase, cherry-pick or revert that stopped. */
+export function ConflictPanel({ cwd, operation }: ConflictPanelProps) {
+  const queryClient = useQueryClient();
+  const { data: state } = useGitConflicts(cwd, true);
+  const [busy, setBusy] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  // Aborting throws away the resolutions made so far, so it takes a second click
+  const [armedAbort, setArmedAbort] = useState(false);
+
+  const run = async (key: string, fn: () => Promise<unknown>) => {
+    setBusy(key);
+    setError(null);
+    try {
+      await fn();
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setBusy(null);
+      setArmedAbort(false);
+      queryClient.invalidateQueries({ queryKey: ["git-status", cwd] });
+      queryClient.invalidateQueries({ queryKey: ["git-conflicts", cwd] });
+      queryClient.invalidateQueries({ queryKey: ["git-log", cwd] });
+    }
+  };
+
+  const handleAbort = () => {
+    if (!armedAbort) {
+      setArmedAbort(true);
+      return;
+    }
+    run("abort", () => gitOperationAbort(cwd));
+  };
+
+  const conflicts = state?.conflicts ?? [];
+
+  return (
+    <div className="border-b border-border-muted bg-status-warning/5">
+      <div className="flex items-start gap-2 px-3 py-1.5 text-xs">
+        <GitMerge size={12} className="mt-0.5 shrink-0 text-status-warning" />
+        <div className="flex-1 min-w-0">
+          <p className="text-text-primary font-medium truncate" title={state?.incomingHash ?? undefined}>
+            {describeOperation(operation, state)}
+          </p>
+          <p className="text-[10px] text-text-muted">
+            {conflicts.length > 0
+              ? `${conflicts.length} conflicted file${conflicts.length === 1 ? "" : "s"}`
+              : "All conflicts resolved"}
+          </p>
+        </div>
+        <div className="flex items-center gap-1 shrink-0 text-[10px]">
+          <ConflictAction
+            disabled={!!busy || conflicts.length > 0}
+            onClick={() => run("continue", () => gitOperationContinue(cwd))}
+          >
+            {busy === "continue" && <Loader2 size={9} className="animate-spin" />}
+            Continue
+          </ConflictAction>
+          {operation !== "merge" && (
+            <ConflictAction disabled={!!busy} onClick={() => run("skip", () => gitOperationSkip(cwd))}>
+              {busy === "skip" && <Loader2 size={9} className="animate-spin" />}
+              Skip
+            </ConflictAction>
+          )}
+          <ConflictAction disabled={!!busy} danger onClick={handleAbort}>
+            {busy === "abort" && <Loader2 size={9} className="animate-spin" />}
+            {armedAbort ? "Confirm" : "Abort"}
+          </ConflictAction>
+        </div>
+      </div>
+
+      {error && <p className="px-3 pb-1 text-[10px] text-status-error whitespace-pre-wrap">{error}</p>}
+
+      {conflicts.map((file) => (
+        <ConflictRow key={file.path} cwd={cwd} file={file} rebasing={operation === "rebase"} busy={!!busy} run={run} />
+      ))}
+    </div>
+  );
+}
+
+function describeOperation(operation: GitOperation, state: ConflictState | undefined): string {
+  const label = OPERATION_LABELS[operation];
+  if (operation === "rebase") {
+    const branch = state?.rebaseBranch ?? "HEAD";
+    const onto = state?.rebaseOnto ? ` onto ${state.rebaseOnto}` : "";
+    const step = state?.step && state.totalSteps ? ` (${state.step}/${state.totalSteps})` : "";
+    return `${label} ${branch}${onto}${step}`;
+  }
+  return state?.incomingSummary ? `${label} "${state.incomingSummary}"` : label;
+}
+
+type ConflictTab = "working" | "base" | "ours" | "theirs";
+
+interface ConflictRowProps {
+  cwd: string;
+  file: ConflictFile;
+  rebasing: boolean;
+  busy: boolean;
+  run: (key: string, fn: () => Promise<unknown>) => Promise<void>;
+}
+
+function ConflictRow({ cwd, file, rebasing, busy, run }: ConflictRowProps) {
+  const [expanded, setExpanded] = useState(false);
+  const [tab, setTab] = useState<ConflictTab>("working");
+  const [proposal, setProposal] = useState<string | null>(null);
+  const [suggesting, setSuggesting] = useState(false);
+  const [suggestError, setSuggestError] = useState<string | null>(null);
+
+  const filename = file.path.split("/").pop() ?? file.path;
+  // During a rebase "ours" is the branch being rebased onto
+  const oursTitle = rebasing ? "Keep the upstream version" : "Keep the checked-out branch's version";
+  const theirsTitle = rebasing ? "Keep the version from the commit being replayed" : "Keep the incoming version";
+
+  const handleSuggest = async () => {
+    setSuggesting(true);
+    setSuggestError(null);
+    try {
+      setProposal(await gitSuggestResolution(cwd, file.path));
+      setExpanded(false);
+    } catch (e) {
+      setSuggestError(String(e));
+    } finally {
+      setSuggesting(false);
+    }
+  };
+
+  const handleAccept = (content: string) =>
+    run(`resolve:${file.path}`, async () => {
+      await gitResolveConflict(cwd, file.path, { type: "content", content });
+      setProposal(null);
+    });
+
+  const tabs: { key: ConflictTab; label: string; text: string | null }[] = [
+    { key: "working", label: "Working", text: file.working },
+    { key: "base", label: "Base", text: file.base },
+    { key: "ours", label: rebasing ? "Upstream" : "Ours", text: file.ours },
+    { key: "theirs", label: rebasing ? "Replayed" : "Theirs", text: file.theirs },
+  ];
+  const shown = tabs.find((t) => t.key === tab)!;
+
+  return (
+    <div>
+      <div className="group flex items-center gap-2 px-4 py-1 text-xs">
+        <button
+          onClick={() => setExpanded(!expanded)}
+          disabled={file.binary}
+          className="flex items-center gap-1 flex-1 min-w-0 text-left text-text-secondary hover:text-text-primary"
+          title={file.path}
+        >
+          {file.binary ? <span className="w-2.5" /> : expanded ? <ChevronDown size={10} /> : <ChevronRight size={10} />}
+          <span className="truncate">{filename}</span>
+          <span className="shrink-0 text-[10px] text-text-muted">
+            {CONFLICT_LABELS[file.kind]}
+            {file.binary && " · binary"}
+          </span>
+        </button>
+        <div className="flex items-center gap-1 shrink-0 text-[10px]">
+          <ConflictAction
+            disabled={busy}
+            title={oursTitle}
+            onClick={() => run(`resolve:${file.path}`, () => gitResolveConflict(cwd, file.path, { type: "ours" }))}
+          >
+            {rebasing ? "Upstream" : "Ours"}
+          </ConflictAction>
+          <ConflictAction
+            disabled={busy}
+            title={theirsTitle}
+            onClick={() => run(`resolve:${file.path}`, () => gitResolveConflict(cwd, file.path, { type: "theirs" }))}
+          >
+            {rebasing ? "Replayed" : "Theirs"}
+          </ConflictAction>
+          <ConflictAction
+            disabled={busy}
+            title="Stage the file as edited in the working tree"
+            onClick={() => run(`resolve:${file.path}`, () => gitMarkResolved(cwd, [file.path]))}
+          >
+            Resolved
+          </ConflictAction>
+          {!file.binary && (
+            <ConflictAction disabled={busy || suggesting} title="Have Claude propose a merged version" onClick={handleSuggest}>
+              {suggesting ? <Loader2 size={9} className="animate-spin" /> : <Sparkles size={9} />}
+            </ConflictAction>
+          )}
+        </div>
+      </div>
+
+      {suggestError && <p className="px-6 pb-1 text-[10px] text-status-error whitespace-pre-wrap">{suggestError}</p>}
+
+      {proposal !== null && (
+        <div className="mx-2 mb-1.5 flex flex-col gap-1">
+          <span className="text-[10px] text-text-muted">Proposed resolution — review before accepting</span>
+          <textarea
+            value={proposal}
+            onChange={(e) => setProposal(e.target.value)}
+            spellCheck={false}
+            className="h-48 resize-y rounded border border-border-muted bg-bg-raised p-1.5 font-mono text-[10px] text-text-secondary outline-none focus:border-border-focus"
+          />
+          <div className="flex items-center gap-1.5 text-[10px]">
+            <button
+              onClick={() => handleAccept(proposal)}
+              disabled={busy}
+              className="px-2 py-0.5 rounded bg-accent-primary/10 text-accent-primary disabled:opacity-40"
+            >
+              Accept
+            </button>
+            <button onClick={() => setProposal(null)} className="px-2 py-0.5 text-text-muted hover:text-text-primary">
+              Discard
+            </button>
+          </div>
+        </div>
+      )}
+
+      {expanded && !file.binary && (
+        <div className="mx-2 mb-1.5">
+          <div className="flex items-center gap-1 text-[10px]">
+            {tabs.map((t) => (
+              <button
+                key={t.key}
+                onClick={() => setTab(t.key)}
+                className={cn(
+                  "px-1.5 py-0.5 rounded-t",
+                  tab === t.key ? "bg-bg-raised text-text-primary" : "text-text-muted hover:text-text-secondary"
+                )}
+              >
+                {t.label}
+              </button>
+            ))}
+          </div>
+          <pre className="max-h-64 overflow-auto rounded-b rounded-tr border border-border-muted bg-bg-raised p-1.5 font-mono text-[10px] text-text-secondary whitespace-pre">
+            {shown.text ?? "(file does not exist on this side)"}
+          </pre>
+        </div>
+      )}
+    </div>
+  );
+}
+
+function ConflictAction({
+  children,
+  onClick,
+  disabled,
+  danger,
+  title,
+}: {
+  children: React.ReactNode;
+  onClick: () => void;
+  disabled?: boolean;
+  danger?: boolean;
+  title?: string;
+}) {
+  return (
+    <button
+      onClick={onClick}
+      disabled={disabled}
+      title={title}
+      className={cn(
+        "flex items-center gap-1 px-1.5 py-0.5 rounded border border-border-muted text-text-muted disabled:opacity-50 transition-colors",
+        danger ? "hover:text-status-error hover:border-status-error" : "hover:text-text-primary hover:border-border-focus"
+      )}
+    >
+      {children}
+    </button>
+  );
+}
@@ -1,5 +1,8 @@
+import { useState } from "react";
+import { useQueryClient } from "@tanstack/react-query";
 import { useGitDiff } from "@/hooks/useClaudeData";
-import type { DiffLine, DiffLineKind } from "@/lib/tauri";
+import { gitStageLines, gitUnstageLines, gitDiscardLines } from "@/lib/tauri";
+import type { DiffLine, DiffLineKind, DiffSelection } from "@/lib/tauri";
 import { cn } from "@/lib/utils";
 
 interface DiffViewerProps {
@@ -8,8 +11,59 @@ interface DiffViewerProps {
   staged: boolean;
 }
 
+type SelectionAction = "stage" | "unstage" | "discard";
+
+const lineKey = (line: DiffLine) => `${line.oldLine ?? ""}:${line.newLine ?? ""}`;
+
 export function DiffViewer({ cwd, filePath, staged }: DiffViewerProps) {
   const { data: lines, isLoading } = useGitDiff(cwd, filePath, staged);
+  const queryClient = useQueryClient();
+  // Changed lines picked for a line-level action, keyed by old/new line number
+  const [selected, setSelected] = useState<Set<string>>(new Set());
+  const [busy, setBusy] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+  // Discard is destructive, so it takes a second click
+  const [armedDiscard, setArmedDiscard] = useState<string | null>(null);
+
+  const apply = async (action: SelectionAction, selection: DiffSelection) => {
+    setBusy(true);
+    setError(null);
+    setArmedDiscard(null);
+    try {
+      if (action === "stage") await gitStageLines(cwd, filePath, selection);
+      else if (action === "unstage") await gitUnstageLines(cwd, filePath, selection);
+      else await gitDiscardLines(cwd, filePath, selection);
+      setSelected(new Set());
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setBusy(false);
+      queryClient.invalidateQueries({ queryKey: ["git-status", cwd] });
+      queryClient.invalidateQueries({ queryKey: ["git-diff", cwd, filePath] });
+    }
+  };
+
+  const toggleLine = (line: DiffLine) => {
+    const key = lineKey(line);
+    setSelected((prev) => {
+      const next = new Set(prev);
+      if (next.has(key)) next.delete(key);
+      else next.add(key);
+      return next;
+    });
+  };
+
+  const selectedLines = (): DiffSelection => ({
+    hunks: [],
+    lines: (lines ?? [])
+      .filter((l) => (l.kind === "Add" || l.kind === "Remove") && selected.has(lineKey(l)))
+      .map((l) => ({ oldLine: l.oldLine ?? null, newLine: l.newLine ?? null })),
+  });
+
+  const discard = (key: string, selection: DiffSelection) => {
+    if (armedDiscard === key) apply("discard", selection);
+    else setArmedDiscard(key);
+  };
 
   if (isLoading) {
     return <div className="p-3 text-xs text-text-muted">Loading diff...</div>;
@@ -20,18 +74,125 @@ export function DiffViewer({ cwd, filePath, staged }: DiffViewerProps) {
   }
 
   return (
-    <div className="font-mono text-xs overflow-auto h-full">
-      {lines.map((line, i) => (
-        <DiffLineRow key={i} line={line} />
-      ))}
+    <div className="flex flex-col h-full">
+      {(selected.size > 0 || error) && (
+        <div className="flex items-center gap-2 px-3 py-1 border-b border-border-muted text-[10px] shrink-0">
+          {selected.size > 0 && (
+            <>
+              <span className="text-text-muted">{selected.size} selected</span>
+              {staged ? (
+                <DiffAction disabled={busy} onClick={() => apply("unstage", selectedLines())}>
+                  Unstage lines
+                </DiffAction>
+              ) : (
+                <>
+                  <DiffAction disabled={busy} onClick={() => apply("stage", selectedLines())}>
+                    Stage lines
+                  </DiffAction>
+                  <DiffAction disabled={busy} danger onClick={() => discard("lines", selectedLines())}>
+                    {armedDiscard === "lines" ? "Confirm discard" : "Discard lines"}
+                  </DiffAction>
+                </>
+              )}
+              <DiffAction disabled={busy} onClick={() => setSelected(new Set())}>
+                Clear
+              </DiffAction>
+            </>
+          )}
+          {error && <span className="text-status-error truncate">{error}</span>}
+        </div>
+      )}
+      <div className="font-mono text-xs overflow-auto flex-1">
+        {lines.map((line, i) => {
+          const hunk = line.hunk;
+          if (line.kind === "Hunk" && hunk !== undefined) {
+            const whole = { hunks: [hunk], lines: [] };
+            return (
+              <div key={i} className="group flex items-center">
+                <div className="flex-1 min-w-0">
+                  <DiffLineRow line={line} />
+                </div>
+                <div className="flex items-center gap-1 px-2 opacity-0 group-hover:opacity-100 transition-opacity text-[10px] font-sans">
+                  {staged ? (
+                    <DiffAction disabled={busy} onClick={() => apply("unstage", whole)}>
+                      Unstage hunk
+                    </DiffAction>
+                  ) : (
+                    <>
+                      <DiffAction disabled={busy} onClick={() => apply("stage", whole)}>
+                        Stage hunk
+                      </DiffAction>
+                      <DiffAction disabled={busy} danger onClick={() => discard(`hunk:${hunk}`, whole)}>
+                        {armedDiscard === `hunk:${hunk}` ? "Confirm discard" : "Discard hunk"}
+                      </DiffAction>
+                    </>
+                  )}
+                </div>
+              </div>
+            );
+          }
+          const selectable = hunk !== undefined && (line.kind === "Add" || line.kind === "Remove");
+          return (
+            <DiffLineRow
+              key={i}
+              line={line}
+              selected={selectable && selected.has(lineKey(line))}
+              onClick={selectable ? () => toggleLine(line) : undefined}
+            />
+          );
+        })}
+      </div>
     </div>
   );
 }
 
-function DiffLineRow({ line }: { line: DiffLine }) {
+function DiffAction({
+  children,
+  onClick,
+  disabled,
+  danger,
+}: {
+  children: React.ReactNode;
+  onClick: () => void;
+  disabled?: boolean;
+  danger?: boolean;
+}) {
+  return (
+    <button
+      onClick={onClick}
+      disabled={disabled}
+      className={cn(
+        "px-1.5 py-0.5 rounded border border-border-muted disabled:opacity-50 transition-colors",
+        danger
+          ? "text-text-muted hover:text-status-error hover:border-status-error"
+          : "text-text-muted hover:text-text-primary hover:border-border-focus"
+      )}
+    >
+      {children}
+    </button>
+  );
+}
+
+export function DiffLineRow({
+  line,
+  selected,
+  onClick,
+}: {
+  line: DiffLine;
+  selected?: boolean;
+  onClick?: () => void;
+}) {
   const style = getDiffLineStyle(line.kind);
   return (
-    <div className={cn("px-3 py-0 leading-5 whitespace-pre", style.bg)}>
+    <div
+      onClick={onClick}
+      className={cn(
+        "px-3 py-0 leading-5 whitespace-pre",
+        style.bg,
+        onClick && "cursor-pointer",
+        selected && "ring-1 ring-inset ring-accent-primary"
+      )}
+    >
       <span className={cn("select-none mr-2", style.prefix)}>
         {getDiffPrefix(line.kind)}
       </span>
@@ -1,13 +1,20 @@
 import { useState } from "react";
-import { ChevronDown, ChevronRight, GitBranch, GitFork, Pin, Loader2 } from "lucide-react";
+import { ChevronDown, ChevronRight, GitBranch, GitFork, Pin, Loader2, Bot, X } from "lucide-react";
 import { useQueryClient } from "@tanstack/react-query";
-import { useGitBranches, useGitCurrentBranch, useGitLog, useGitRemoteBranches } from "@/hooks/useClaudeData";
+import {
+  useGitBranches,
+  useGitCommitDetail,
+  useGitCurrentBranch,
+  useGitLog,
+  useGitRemoteBranches,
+} from "@/hooks/useClaudeData";
 import { useProjectsStore } from "@/stores/projectsStore";
 import { useGitAction } from "@/hooks/useGitAction";
 import { gitCreateBranch } from "@/lib/tauri";
 import { BranchContextMenu } from "@/components/git/BranchContextMenu";
+import { DiffLineRow } from "@/components/git/DiffViewer";
 import { cn } from "@/lib/utils";
-import type { CommitInfo } from "@/lib/tauri";
+import type { CommitFileChange, CommitInfo, GraphRow, LogQuery } from "@/lib/tauri";
 
 // ─── Branch tree helpers ──────────────────────────────────────────────────────
 
@@ -43,14 +50,45 @@ function RefBadge({ label }: { label: string }) {
   );
 }
 
+// ─── Commit graph ─────────────────────────────────────────────────────────────
+
+const LANE_WIDTH = 12;
+const ROW_HEIGHT = 28;
+const LANE_COLORS = ["#60a5fa", "#4ade80", "#f472b6", "#facc15", "#a78bfa", "#fb923c", "#2dd4bf", "#f87171"];
+
+const laneX = (lane: number) => lane * LANE_WIDTH + LANE_WIDTH / 2;
+const laneColor = (lane: number) => LANE_COLORS[lane % LANE_COLORS.length];
+
+function GraphCell({ graph, width }: { graph: GraphRow; width: number }) {
+  const mid = ROW_HEIGHT / 2;
+  const node = laneX(graph.lane);
+  return (
+    <svg width={width * LANE_WIDTH} height={ROW_HEIGHT} className="shrink-0">
+      {graph.through.map((l) => (
+        <line key={`t${l}`} x1={laneX(l)} y1={0} x2={laneX(l)} y2={ROW_HEIGHT} stroke={laneColor(l)} strokeWidth={1.5} />
+      ))}
+      {graph.up.map((l) => (
+        <line key={`u${l}`} x1={laneX(l)} y1={0} x2={node} y2={mid} stroke={laneColor(l)} strokeWidth={1.5} />
+      ))}
+      {graph.down.map((l) => (
+        <line key={`d${l}`} x1={node} y1={mid} x2={laneX(l)} y2={ROW_HEIGHT} stroke={laneColor(l)} strokeWidth={1.5} />
+      ))}
+      <circle cx={node} cy={mid} r={3.5} fill={laneColor(graph.lane)} />
+    </svg>
+  );
+}
+
 // ─── Commit row ───────────────────────────────────────────────────────────────
 
 function CommitRow({
   commit,
+  graphWidth,
   selected,
   onClick,
 }: {
   commit: CommitInfo;
+  /** Lane columns to reserve so every row's graph lines up */
+  graphWidth: number;
   selected: boolean;
   onClick: () => void;
 }) {
@@ -58,14 +96,20 @@ function CommitRow({
     <button
       onClick={onClick}
       className={cn(
-        "flex items-start gap-2 w-full px-3 py-1.5 text-left transition-colors group",
+        "flex items-center gap-2 w-full px-3 text-left transition-colors group",
         selected
           ? "bg-bg-raised text-text-primary"
           : "text-text-secondary hover:bg-bg-raised hover:text-text-primary"
       )}
+      style={{ height: ROW_HEIGHT }}
+      title={commit.parents.length > 1 ? `Merge of ${commit.parents.map((p) => p.slice(0, 7)).join(", ")}` : undefined}
     >
-      <span className="text-accent-primary shrink-0 mt-0.5 text-[10px]">●</span>
-      <span className="font-mono text-[10px] text-text-muted shrink-0 w-14">{commit.hash}</span>
+      {commit.graph ? (
+        <GraphCell graph={commit.graph} width={graphWidth} />
+      ) : (
+        <span className="text-accent-primary shrink-0 text-[10px]">●</span>
+      )}
+      <span className="font-mono text-[10px] text-text-muted shrink-0 w-14">{commit.shortHash}</span>
       <span className="flex-1 text-xs truncate">{commit.message}</span>
       {commit.refs.length > 0 && (
         <span className="flex gap-1 flex-wrap shrink-0 max-w-40">
@@ -74,16 +118,154 @@ function CommitRow({
           ))}
         </span>
       )}
-      <span className="text-[10px] text-text-muted shrink-0 w-20 text-right truncate">
+      <span className="text-[10px] text-text-muted shrink-0 w-20 text-right truncate" title={commit.email}>
         {commit.author}
       </span>
-      <span className="text-[10px] text-text-muted shrink-0 w-20 text-right truncate">
+      <span
+        className="text-[10px] text-text-muted shrink-0 w-20 text-right truncate"
+        title={new Date(commit.timestamp * 1000).toLocaleString()}
+      >
         {commit.date}
       </span>
     </button>
   );
 }
 
+// ─── Commit detail ────────────────────────────────────────────────────────────
+
+function CommitDetailPane({ cwd, hash, onClose }: { cwd: string; hash: string; onClose: () => void }) {
+  const { data: detail, isLoading, error } = useGitCommitDetail(cwd, hash);
+  const [openFile, setOpenFile] = useState<string | null>(null);
+
+  return (
+    <div className="h-1/2 border-t border-border-muted flex flex-col overflow-hidden shrink-0">
+      <div className="flex items-center gap-2 px-3 h-6 border-b border-border-muted bg-bg-surface text-[10px] text-text-muted shrink-0">
+        <span className="font-mono">{detail?.shortHash ?? hash.slice(0, 7)}</span>
+        {detail?.agent && (
+          <span className="flex items-center gap-1 text-accent-primary" title="Co-authored by The Associate">
+            <Bot size={10} /> agent
+          </span>
+        )}
+        <button onClick={onClose} className="ml-auto hover:text-text-primary" title="Close">
+          <X size={11} />
+        </button>
+      </div>
+      {isLoading ? (
+        <div className="flex items-center justify-center flex-1 gap-2 text-xs text-text-muted">
+          <Loader2 size={14} className="animate-spin" />
+          Loading commit…
+        </div>
+      ) : error ? (
+        <p className="px-3 py-2 text-[10px] text-status-error">{String(error)}</p>
+      ) : detail ? (
+        <div className="flex-1 overflow-y-auto text-xs">
+          <div className="px-3 py-2 flex flex-col gap-1 border-b border-border-muted">
+            <div className="text-[10px] text-text-muted">
+              <span className="text-text-secondary">{detail.author}</span> &lt;{detail.email}&gt; ·{" "}
+              <span title={new Date(detail.timestamp * 1000).toLocaleString()}>{detail.date}</span>
+              {(detail.committer !== detail.author || detail.committerEmail !== detail.email) && (
+                <> · committed by {detail.committer}</>
+              )}
+            </div>
+            {detail.parents.length > 1 && (
+              <div className="text-[10px] text-text-muted">
+                Merge of {detail.parents.map((p) => p.slice(0, 7)).join(", ")}; changes shown against the first parent
+              </div>
+            )}
+            <pre className="whitespace-pre-wrap font-sans text-text-primary">{detail.message.trimEnd()}</pre>
+          </div>
+          {detail.files.length === 0 ? (
+            <div className="px-3 py-2 text-[10px] text-text-muted">No file changes</div>
+          ) : (
+            detail.files.map((file) => (
+              <CommitFileRow
+                key={file.path}
+                file={file}
+                open={openFile === file.path}
+                onToggle={() => setOpenFile(openFile === file.path ? null : file.path)}
+              />
+            ))
+          )}
+        </div>
+      ) : null}
+    </div>
+  );
+}
+
+function CommitFileRow({ file, open, onToggle }: { file: CommitFileChange; open: boolean; onToggle: () => void }) {
+  return (
+    <div>
+      <button
+        onClick={onToggle}
+        className="flex items-center gap-2 w-full px-3 py-0.5 text-left text-text-secondary hover:bg-bg-raised hover:text-text-primary"
+      >
+        {open ? <ChevronDown size={10} className="shrink-0" /> : <ChevronRight size={10} className="shrink-0" />}
+        <span className="font-mono text-[10px] text-text-muted w-3 shrink-0">{file.statusChar}</span>
+        <span className="flex-1 truncate">{file.oldPath ? `${file.oldPath} → ${file.path}` : file.path}</span>
+        {file.binary ? (
+          <span className="text-[10px] text-text-muted shrink-0">binary</span>
+        ) : (
+          <span className="text-[10px] font-mono shrink-0">
+            <span className="text-green-400">+{file.additions}</span>{" "}
+            <span className="text-red-400">-{file.deletions}</span>
+          </span>
+        )}
+      </button>
+      {open && (
+        <div className="font-mono text-xs border-y border-border-muted">
+          {file.diff.length === 0 ? (
+            <div className="px-4 py-1 text-[10px] text-text-muted">No textual diff</div>
+          ) : (
+            file.diff.map((line, i) => <DiffLineRow key={i} line={line} />)
+          )}
+        </div>
+      )}
+    </div>
+  );
+}
+
+// ─── Log filters ──────────────────────────────────────────────────────────────
+
+type LogFilters = Omit<LogQuery, "cursor" | "limit">;
+
+function LogFilterBar({ filters, onChange }: { filters: LogFilters; onChange: (f: LogFilters) => void }) {
+  const [draft, setDraft] = useState<LogFilters>(filters);
+  const apply = () => onChange(draft);
+  const field = (key: keyof LogFilters, placeholder: string, className: string) => (
+    <input
+      value={draft[key] ?? ""}
+      onChange={(e) => setDraft({ ...draft, [key]: e.target.value })}
+      onKeyDown={(e) => e.key === "Enter" && apply()}
+      onBlur={apply}
+      placeholder={placeholder}
+      className={cn(
+        "min-w-0 px-1.5 py-0.5 text-[11px] bg-bg-base border border-border-muted rounded text-text-primary placeholder-text-muted focus:outline-none focus:border-border-focus",
+        className
+      )}
+    />
+  );
+  const active = !!(filters.message || filters.author || filters.path);
+  return (
+    <div className="flex items-center gap-1.5 px-3 py-1 border-b border-border-muted shrink-0">
+      {field("message", "Message", "flex-1")}
+      {field("author", "Author", "w-28")}
+      {field("path", "Path", "w-36")}
+      {active && (
+        <button
+          onClick={() => {
+            setDraft({});
+            onChange({});
+          }}
+          className="text-text-muted hover:text-text-primary"
+          title="Clear filters"
+        >
+          <X size={11} />
+        </button>
+      )}
+    </div>
+  );
+}
+
 // ─── Branch tree ─────────────────────────────────────────────────────────────
 
 function BranchTree({
@@ -291,7 +473,17 @@ export function GitLogPanel() {
   const queryClient = useQueryClient();
   const { data: localBranches } = useGitBranches(cwd);
   const { data: currentBranchData } = useGitCurrentBranch(cwd);
-  const { data: commits, isLoading } = useGitLog(cwd);
+  const [filters, setFilters] = useState<LogFilters>({});
+  const {
+    data: log,
+    isLoading,
+    error: logError,
+    hasNextPage,
+    fetchNextPage,
+    isFetchingNextPage,
+  } = useGitLog(cwd, filters);
+  const commits = log?.pages.flatMap((p) => p.commits) ?? [];
+  const graphWidth = Math.max(1, ...commits.map((c) => c.graph?.width ?? 0));
   const runGitAction = useGitAction();
 
   const [selectedHash, setSelectedHash] = useState<string | null>(null);
@@ -399,8 +591,11 @@ export function GitLogPanel() {
 
       {/* Right: Commit log (65%) */}
       <div className="flex-1 flex flex-col overflow-hidden">
+        <LogFilterBar filters={filters} onChange={setFilters} />
+
         {/* Header row */}
         <div className="flex items-center gap-2 px-3 h-6 border-b border-border-muted bg-bg-surface text-[10px] text-text-muted font-semibold uppercase tracking-wider shrink-0">
+          <span className="shrink-0" style={{ width: commits[0]?.graph ? graphWidth * LANE_WIDTH : 10 }} />
           <span className="w-14 shrink-0">Hash</span>
           <span className="flex-1">Message</span>
           <span className="w-40 shrink-0">Refs</span>
@@ -413,22 +608,55 @@ export function GitLogPanel() {
             <Loader2 size={14} className="animate-spin" />
             Loading commits…
           </div>
-        ) : (commits?.length ?? 0) === 0 ? (
+        ) : logError ? (
+          <div className="flex items-center justify-center flex-1 px-3 text-xs text-status-error">
+            {String(logError)}
+          </div>
+        ) : commits.length === 0 ? (
           <div className="flex items-center justify-center flex-1 text-xs text-text-muted">
             No commits found
           </div>
         ) : (
-          <div className="flex-1 overflow-y-auto">
-            {(commits ?? []).map((commit) => (
+          <div
+            className="flex-1 overflow-y-auto"
+            onScroll={(e) => {
+              const el = e.currentTarget;
+              // Load the next page a few rows before the end
+              if (hasNextPage && !isFetchingNextPage && el.scrollHeight - el.scrollTop - el.clientHeight < ROW_HEIGHT * 10) {
+                fetchNextPage();
+              }
+            }}
+          >
+            {commits.map((commit) => (
               <CommitRow
                 key={commit.hash}
                 commit={commit}
+                graphWidth={graphWidth}
                 selected={selectedHash === commit.hash}
-                onClick={() => setSelectedHash(commit.hash)}
+                onClick={() => setSelectedHash(selectedHash === commit.hash ? null : commit.hash)}
               />
             ))}
+            {hasNextPage && (
+              <button
+                onClick={() => fetchNextPage()}
+                disabled={isFetchingNextPage}
+                className="flex items-center justify-center gap-1 w-full py-1.5 text-[10px] text-text-muted hover:text-text-primary"
+              >
+                {isFetchingNextPage && <Loader2 size={10} className="animate-spin" />}
+                Load more
+              </button>
+            )}
           </div>
         )}
+
+        {selectedHash && (
+          <CommitDetailPane
+            key={selectedHash}
+            cwd={cwd}
+            hash={selectedHash}
+            onClose={() => setSelectedHash(null)}
+          />
+        )}
       </div>
 
       {/* Branch context menu */}
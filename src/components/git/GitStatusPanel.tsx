@@ -1,22 +1,27 @@
 import { useState, useEffect } from "react";
 import {
   GitBranch, GitFork, RefreshCw, ChevronRight, ChevronDown,
-  Loader2, AlertTriangle, X, GitCommitHorizontal, Upload, GitPullRequest, FolderSearch,
-  GitMerge, Plus, Play, CheckCircle,
+  Loader2, X, GitCommitHorizontal, Upload, GitPullRequest, FolderSearch,
+  GitMerge, Plus, Play, CheckCircle, Scissors,
 } from "lucide-react";
 import { useQueryClient } from "@tanstack/react-query";
-import { useGitStatus, useGitBranches, useGitCurrentBranch, useWorktrees, useWorktreeCopy } from "@/hooks/useClaudeData";
+import { useGitStatus, useGitBranches, useGitCurrentBranch, useGitRemoteBranches, useWorktrees, useWorktreeCopy } from "@/hooks/useClaudeData";
 import { useProjectsStore } from "@/stores/projectsStore";
 import { pathToProjectId } from "@/lib/utils";
 import { useUIStore } from "@/stores/uiStore";
 import { useSessionStore } from "@/stores/sessionStore";
 import { useOutputStore } from "@/stores/outputStore";
 import { useGitAction } from "@/hooks/useGitAction";
-import type { GitFileEntry, FileEntry } from "@/lib/tauri";
-import { createWorktree, setWorktreeCopy, claudeGitAction, listDir, gitPull, gitCreateBranch, checkRemoteRunWorkflow, checkScheduledWorkflow, writeFile } from "@/lib/tauri";
+import type { GitFileEntry, FileEntry, WorktreeSource } from "@/lib/tauri";
+import { createWorktree, pruneWorktrees, setWorktreeCopy, claudeGitAction, listDir, gitPull, gitCreateBranch, checkRemoteRunWorkflow, checkScheduledWorkflow, writeFile } from "@/lib/tauri";
 import { REMOTE_RUN_YAML_CONTENT } from "@/lib/remoteRunYaml";
 import { SCHEDULED_REMOTE_RUN_YAML_CONTENT } from "@/lib/scheduledRemoteRunYaml";
 import { UntrackedContextMenu } from "./UntrackedContextMenu";
+import { CommitBox } from "./CommitBox";
+import { PushButton } from "./PushButton";
+import { StashSection } from "./StashSection";
+import { WorktreeRow } from "./WorktreeRow";
+import { ConflictPanel, CONFLICT_LABELS } from "./ConflictPanel";
 import { RemoteRunSecretsModal } from "./RemoteRunSecretsModal";
 import { cn } from "@/lib/utils";
 import { FileTreeNode } from "@/components/files/FileTreeNode";
@@ -37,6 +42,7 @@ export function GitStatusPanel() {
   const runGitAction = useGitAction();
 
   // File section collapse state
+  const [conflictedOpen, setConflictedOpen] = useState(true);
   const [stagedOpen, setStaggedOpen] = useState(true);
   const [unstagedOpen, setUnstagedOpen] = useState(true);
   const [untrackedOpen, setUntrackedOpen] = useState(false);
@@ -55,6 +61,9 @@ export function GitStatusPanel() {
   const [worktreeBranch, setWorktreeBranch] = useState("");
   const [worktreeLoading, setWorktreeLoading] = useState(false);
   const [worktreeError, setWorktreeError] = useState<string | null>(null);
+  const [worktreeSource, setWorktreeSource] = useState<WorktreeSource["type"]>("newBranch");
+  const [worktreeRemoteBranch, setWorktreeRemoteBranch] = useState("");
+  const [worktreePrNumber, setWorktreePrNumber] = useState("");
 
   // New branch form state
   const [showNewBranchForm, setShowNewBranchForm] = useState(false);
@@ -151,10 +160,11 @@ export function GitStatusPanel() {
     }
   };
 
-  const { data: gitStatus, isLoading, refetch } = useGitStatus(activeProjectDir ?? "");
+  const { data: gitStatus, isLoading, error: gitStatusError, refetch } = useGitStatus(activeProjectDir ?? "");
   const { data: branches } = useGitBranches(activeProjectDir ?? "");
   const { data: currentBranchData } = useGitCurrentBranch(activeProjectDir ?? "");
   const { data: worktrees } = useWorktrees(activeProjectDir ?? "");
+  const { data: remoteBranches } = useGitRemoteBranches(showWorktreeForm ? activeProjectDir ?? "" : "");
   const { data: copyEntries } = useWorktreeCopy(activeProjectDir ?? "");
 
   const currentBranch = currentBranchData ?? branches?.[0] ?? "main";
@@ -228,6 +238,9 @@ export function GitStatusPanel() {
 
   const handleOpenWorktreeForm = () => {
     setWorktreeBranch(`feature/${currentBranch}-wt`);
+    setWorktreeSource("newBranch");
+    setWorktreeRemoteBranch("");
+    setWorktreePrNumber("");
     setWorktreeError(null);
     setShowWorktreeForm(true);
     setShowNewBranchForm(false);
@@ -238,7 +251,14 @@ export function GitStatusPanel() {
     setWorktreeLoading(true);
     setWorktreeError(null);
     try {
-      const worktreePath = await createWorktree(activeProjectDir, worktreeBranch.trim());
+      const source: WorktreeSource =
+        worktreeSource === "remote"
+          ? { type: "remote", branch: worktreeRemoteBranch }
+          : worktreeSource === "pullRequest"
+            ? { type: "pullRequest", number: Number(worktreePrNumber) }
+            : { type: worktreeSource };
+      // The result carries copy warnings after the path, if any
+      const [worktreePath] = (await createWorktree(activeProjectDir, worktreeBranch.trim(), source)).split("\n");
       const newProjectId = pathToProjectId(worktreePath);
       addAndActivateProject(worktreePath);
       openTab(
@@ -259,6 +279,15 @@ export function GitStatusPanel() {
     }
   };
 
+  const handlePruneWorktrees = async () => {
+    if (!activeProjectDir) return;
+    await runGitAction("git worktree prune", async () => {
+      const pruned = await pruneWorktrees(activeProjectDir);
+      return pruned.length ? `Pruned ${pruned.join(", ")}` : "Nothing to prune";
+    });
+    queryClient.invalidateQueries({ queryKey: ["worktrees", activeProjectDir] });
+  };
+
   const handleSwitchToWorktree = (worktreePath: string) => {
     const projectId = pathToProjectId(worktreePath);
     addAndActivateProject(worktreePath);
@@ -407,7 +436,10 @@ export function GitStatusPanel() {
   const staged = gitStatus?.staged ?? [];
   const unstaged = gitStatus?.unstaged ?? [];
   const untracked = gitStatus?.untracked ?? [];
-  const totalChanges = staged.length + unstaged.length + untracked.length;
+  const conflicted = gitStatus?.conflicted ?? [];
+  const totalChanges = staged.length + unstaged.length + untracked.length + conflicted.length;
+  const ahead = gitStatus?.ahead ?? 0;
+  const behind = gitStatus?.behind ?? 0;
 
   return (
     <div className="flex flex-col h-full overflow-hidden">
@@ -417,6 +449,17 @@ export function GitStatusPanel() {
         <span className="text-xs text-text-primary font-medium truncate flex-1">
           {currentBranch}
         </span>
+        {gitStatus?.upstream && (ahead > 0 || behind > 0) && (
+          <span
+            className="text-[10px] font-mono text-text-muted shrink-0"
+            title={`${ahead} ahead, ${behind} behind ${gitStatus.upstream}`}
+          >
+            {ahead > 0 && `\u2191${ahead}`}
+            {ahead > 0 && behind > 0 && " "}
+            {behind > 0 && `\u2193${behind}`}
+          </span>
+        )}
+        <PushButton cwd={activeProjectDir} />
         <button
           onClick={handlePull}
           className="text-text-muted hover:text-accent-primary transition-colors"
@@ -440,7 +483,9 @@ export function GitStatusPanel() {
         </button>
       </div>
 
-      {/* Quick commit actions */}
+      <CommitBox cwd={activeProjectDir} stagedCount={staged.length} merging={gitStatus?.operation === "merge"} />
+
+      {/* Claude commit actions */}
       <div className="flex items-center gap-1 px-3 py-1.5 border-b border-border-muted">
         {[
           { action: "commit" as const, label: "Commit", icon: GitCommitHorizontal },
@@ -451,7 +496,7 @@ export function GitStatusPanel() {
             key={action}
             onClick={() => handleGitAction(action)}
             disabled={!!gitActionLoading}
-            title={action === "commit" ? "Commit all changes" : action === "commit_push" ? "Commit & push" : "Commit, push & create PR"}
+            title={action === "commit" ? "Have Claude commit all changes" : action === "commit_push" ? "Have Claude commit & push" : "Have Claude commit, push & create PR"}
             className="flex items-center gap-1 px-2.5 py-1 text-[10px] rounded-lg border border-border-muted text-text-muted hover:text-text-primary hover:border-border-focus disabled:opacity-50 transition-all duration-200"
           >
             {gitActionLoading === action
@@ -522,21 +567,87 @@ export function GitStatusPanel() {
       {showWorktreeForm && (
         <div className="px-3 py-2 border-b border-border-muted flex flex-col gap-1.5">
           <div className="flex items-center gap-1.5">
-            <input
-              type="text"
-              value={worktreeBranch}
-              onChange={(e) => setWorktreeBranch(e.target.value)}
-              onKeyDown={(e) => {
-                if (e.key === "Enter") handleCreateWorktree();
-                if (e.key === "Escape") setShowWorktreeForm(false);
+            <select
+              value={worktreeSource}
+              onChange={(e) => {
+                const type = e.target.value as WorktreeSource["type"];
+                setWorktreeSource(type);
+                setWorktreeBranch(type === "newBranch" ? `feature/${currentBranch}-wt` : "");
               }}
-              placeholder="branch name"
-              className="flex-1 min-w-0 px-2 py-1 text-xs bg-bg-base border border-border-muted rounded-lg text-text-primary placeholder-text-muted focus:outline-none focus:border-border-focus"
-              autoFocus
-            />
+              className="px-1.5 py-1 text-xs bg-bg-base border border-border-muted rounded-lg text-text-primary focus:outline-none focus:border-border-focus"
+            >
+              <option value="newBranch">New branch</option>
+              <option value="local">Existing branch</option>
+              <option value="remote">Remote branch</option>
+              <option value="pullRequest">Pull request</option>
+            </select>
+            {worktreeSource === "remote" && (
+              <select
+                value={worktreeRemoteBranch}
+                onChange={(e) => {
+                  setWorktreeRemoteBranch(e.target.value);
+                  setWorktreeBranch(e.target.value.slice(e.target.value.indexOf("/") + 1));
+                }}
+                className="flex-1 min-w-0 px-1.5 py-1 text-xs bg-bg-base border border-border-muted rounded-lg text-text-primary focus:outline-none focus:border-border-focus"
+              >
+                <option value="" disabled>remote branch</option>
+                {(remoteBranches ?? []).map((rb) => (
+                  <option key={`${rb.remote}/${rb.branch}`} value={`${rb.remote}/${rb.branch}`}>
+                    {rb.remote}/{rb.branch}
+                  </option>
+                ))}
+              </select>
+            )}
+            {worktreeSource === "pullRequest" && (
+              <input
+                type="number"
+                min={1}
+                value={worktreePrNumber}
+                onChange={(e) => {
+                  setWorktreePrNumber(e.target.value);
+                  setWorktreeBranch(e.target.value ? `pr-${e.target.value}` : "");
+                }}
+                placeholder="PR #"
+                className="w-16 px-2 py-1 text-xs bg-bg-base border border-border-muted rounded-lg text-text-primary placeholder-text-muted focus:outline-none focus:border-border-focus"
+              />
+            )}
+          </div>
+          <div className="flex items-center gap-1.5">
+            {worktreeSource === "local" ? (
+              <select
+                value={worktreeBranch}
+                onChange={(e) => setWorktreeBranch(e.target.value)}
+                className="flex-1 min-w-0 px-1.5 py-1 text-xs bg-bg-base border border-border-muted rounded-lg text-text-primary focus:outline-none focus:border-border-focus"
+              >
+                <option value="" disabled>branch</option>
+                {(branches ?? [])
+                  .filter((b) => !worktrees?.some((wt) => wt.branch === b))
+                  .map((b) => (
+                    <option key={b} value={b}>{b}</option>
+                  ))}
+              </select>
+            ) : (
+              <input
+                type="text"
+                value={worktreeBranch}
+                onChange={(e) => setWorktreeBranch(e.target.value)}
+                onKeyDown={(e) => {
+                  if (e.key === "Enter") handleCreateWorktree();
+                  if (e.key === "Escape") setShowWorktreeForm(false);
+                }}
+                placeholder={worktreeSource === "newBranch" ? "branch name" : "local branch name"}
+                className="flex-1 min-w-0 px-2 py-1 text-xs bg-bg-base border border-border-muted rounded-lg text-text-primary placeholder-text-muted focus:outline-none focus:border-border-focus"
+                autoFocus
+              />
+            )}
             <button
               onClick={handleCreateWorktree}
-              disabled={worktreeLoading || !worktreeBranch.trim()}
+              disabled={
+                worktreeLoading ||
+                !worktreeBranch.trim() ||
+                (worktreeSource === "remote" && !worktreeRemoteBranch) ||
+                (worktreeSource === "pullRequest" && !(Number(worktreePrNumber) > 0))
+              }
               className="flex items-center gap-1 px-2 py-1 text-xs bg-accent-primary text-white rounded-lg hover:opacity-90 disabled:opacity-50 transition-opacity shrink-0"
             >
               {worktreeLoading ? <Loader2 size={10} className="animate-spin" /> : null}
@@ -566,6 +677,15 @@ export function GitStatusPanel() {
               {worktreesOpen ? <ChevronDown size={10} /> : <ChevronRight size={10} />}
               Worktrees
               <span className="ml-auto font-normal normal-case">{worktrees.length}</span>
+              {worktrees.some((wt) => wt.isPrunable) && (
+                <button
+                  onClick={(e) => { e.stopPropagation(); handlePruneWorktrees(); }}
+                  className="ml-1 text-status-warning hover:text-accent-primary transition-colors"
+                  title="Prune worktrees whose directories are gone"
+                >
+                  <Scissors size={10} />
+                </button>
+              )}
               <button
                 onClick={(e) => { e.stopPropagation(); handleOpenWorktreeForm(); }}
                 className="ml-1 text-text-muted hover:text-accent-primary transition-colors"
@@ -586,40 +706,15 @@ export function GitStatusPanel() {
                   const visible = showAllWorktrees ? worktrees : primary;
                   return (
                     <>
-                      {visible.map((wt) => {
-                        const isActive = isActiveWt(wt);
-                        return (
-                          <div
-                            key={wt.path}
-                            className={cn(
-                              "flex items-center gap-2 px-4 py-1 text-xs",
-                              isActive ? "text-accent-primary" : "text-text-secondary"
-                            )}
-                          >
-                            <GitFork size={10} className="shrink-0 text-text-muted" />
-                            <span className={cn("truncate flex-1 font-medium", isActive && "text-accent-primary")}>
-                              {wt.branch || wt.head}
-                              {wt.isMain && (
-                                <span className="ml-1 text-[9px] text-text-muted font-normal">(main)</span>
-                              )}
-                            </span>
-                            {wt.isPrunable && (
-                              <span title="Prunable — worktree directory may be missing" className="shrink-0">
-                                <AlertTriangle size={10} className="text-status-warning" />
-                              </span>
-                            )}
-                            {!isActive && (
-                              <button
-                                onClick={() => handleSwitchToWorktree(wt.path)}
-                                className="shrink-0 text-text-muted hover:text-accent-primary transition-colors"
-                                title={`Switch to ${wt.path}`}
-                              >
-                                <ChevronRight size={10} />
-                              </button>
-                            )}
-                          </div>
-                        );
-                      })}
+                      {visible.map((wt) => (
+                        <WorktreeRow
+                          key={wt.path}
+                          projectDir={activeProjectDir ?? ""}
+                          worktree={wt}
+                          isActive={isActiveWt(wt)}
+                          onSwitch={handleSwitchToWorktree}
+                        />
+                      ))}
                       {!showAllWorktrees && extra.length > 0 && (
                         <button
                           onClick={() => setShowAllWorktrees(true)}
@@ -777,13 +872,32 @@ export function GitStatusPanel() {
           )}
         </div>
 
+        {gitStatus?.operation && <ConflictPanel cwd={activeProjectDir} operation={gitStatus.operation} />}
+
         {/* ── Changed files ── */}
-        {totalChanges === 0 ? (
+        {gitStatusError ? (
+          <div className="p-3 text-xs text-status-error">
+            {String(gitStatusError)}
+          </div>
+        ) : totalChanges === 0 ? (
           <div className="p-3 text-xs text-text-muted text-center">
             No changes
           </div>
         ) : (
           <div>
+            {/* With an operation in progress the conflict panel lists these */}
+            {conflicted.length > 0 && !gitStatus?.operation && (
+              <FileSection
+                title="Conflicts"
+                count={conflicted.length}
+                open={conflictedOpen}
+                onToggle={() => setConflictedOpen(!conflictedOpen)}
+                files={conflicted}
+                onFileClick={handleFileClick}
+                onFileDoubleClick={handleFileDoubleClick}
+                selectedFile={selectedFile}
+              />
+            )}
             {staged.length > 0 && (
               <FileSection
                 title="Staged"
@@ -838,6 +952,8 @@ export function GitStatusPanel() {
             )}
           </div>
         )}
+
+        <StashSection cwd={activeProjectDir} hasChanges={totalChanges > 0} />
       </div>
       {fileContextMenu && (
         <UntrackedContextMenu
@@ -916,6 +1032,7 @@ function FileEntry({ file, onClick, onDoubleClick, isSelected, onContextMenu }:
       onClick={onClick}
       onDoubleClick={onDoubleClick}
       onContextMenu={onContextMenu}
+      title={file.oldPath ? `${file.oldPath} \u2192 ${file.path}` : file.conflict ? CONFLICT_LABELS[file.conflict] : undefined}
       className={cn(
         "flex items-center gap-2 w-full px-4 py-1 text-xs text-left transition-all duration-200 rounded-md mx-1",
         isSelected
@@ -926,7 +1043,12 @@ function FileEntry({ file, onClick, onDoubleClick, isSelected, onContextMenu }:
       <span className={cn("font-mono shrink-0 w-3", statusColor)}>
         {file.statusChar}
       </span>
-      <span className="truncate flex-1">{filename}</span>
+      <span className="truncate flex-1">
+        {file.oldPath && (
+          <span className="text-text-muted">{file.oldPath.split(/[/\\]/).pop()} {"\u2192"} </span>
+        )}
+        {filename}
+      </span>
       {dir && (
         <span className="text-text-muted text-[10px] truncate max-w-20 shrink-0">
           {dir}
@@ -942,6 +1064,7 @@ function getStatusColor(char: string): string {
     case "A": return "text-status-success";
     case "D": return "text-status-error";
     case "R": return "text-blue-400";
+    case "U": return "text-status-error";
     case "?": return "text-text-muted";
     default: return "text-text-secondary";
   }
@@ -0,0 +1,94 @@
+import { useEffect, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
+import { ArrowUp, Loader2 } from "lucide-react";
+import { useQueryClient } from "@tanstack/react-query";
+import { gitPush } from "@/lib/tauri";
+import type { PushProgress, PushRejection, PushResult } from "@/lib/tauri";
+import { useOutputStore } from "@/stores/outputStore";
+
+const REJECTION_LABELS: Record<PushRejection, string> = {
+  nonFastForward: "the remote has commits you don't (pull first)",
+  fetchFirst: "the remote has changes you haven't fetched (pull first)",
+  staleLease: "the remote moved since your last fetch",
+  protectedBranch: "the branch is protected on the remote",
+  remoteRejected: "a remote hook declined the update",
+  other: "the remote refused the update",
+};
+
+/** Rejections a force-with-lease push can get past. */
+const FORCEABLE: PushRejection[] = ["nonFastForward", "fetchFirst"];
+
+function describe(result: PushResult): string {
+  const rejected = result.refs.find((r) => r.status === "rejected");
+  if (rejected) {
+    const reason = REJECTION_LABELS[rejected.rejection ?? "other"];
+    return `Push to ${result.remote}/${result.branch} rejected: ${reason}${result.output ? `\n${result.output}` : ""}`;
+  }
+  if (result.refs.every((r) => r.status === "upToDate")) return "Everything up to date";
+  const summary = result.refs.map((r) => r.summary).join(", ");
+  return `Pushed ${result.branch} to ${result.remote} (${summary})${result.upstreamSet ? `, now tracking ${result.remote}/${result.branch}` : ""}`;
+}
+
+export function PushButton({ cwd }: { cwd: string }) {
+  const queryClient = useQueryClient();
+  const addMessage = useOutputStore((s) => s.addMessage);
+  const [pushing, setPushing] = useState(false);
+  const [progress, setProgress] = useState<PushProgress | null>(null);
+  const [offerForce, setOfferForce] = useState(false);
+
+  useEffect(() => {
+    if (!pushing) return;
+    const unlisten = listen<PushProgress>("git-push-progress", ({ payload }) => {
+      if (payload.cwd === cwd) setProgress(payload);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [pushing, cwd]);
+
+  const push = async (forceWithLease: boolean) => {
+    if (pushing) return;
+    const label = forceWithLease ? "git push --force-with-lease" : "git push";
+    setPushing(true);
+    setProgress(null);
+    setOfferForce(false);
+    try {
+      const result = await gitPush(cwd, { forceWithLease });
+      addMessage(result.rejected ? "error" : "success", describe(result), label);
+      const rejection = result.refs.find((r) => r.status === "rejected")?.rejection;
+      setOfferForce(!forceWithLease && !!rejection && FORCEABLE.includes(rejection));
+    } catch (e) {
+      addMessage("error", String(e), label);
+    } finally {
+      setPushing(false);
+      setProgress(null);
+      queryClient.invalidateQueries({ queryKey: ["git-status", cwd] });
+      queryClient.invalidateQueries({ queryKey: ["git-remote-branches", cwd] });
+    }
+  };
+
+  return (
+    <>
+      {offerForce && !pushing && (
+        <button
+          onClick={() => push(true)}
+          className="text-[10px] px-1.5 rounded border border-border-muted text-text-muted hover:text-status-error hover:border-status-error transition-colors"
+          title="Overwrite the remote branch unless it changed since your last fetch"
+        >
+          Force (lease)
+        </button>
+      )}
+      <button
+        onClick={() => push(false)}
+        disabled={pushing}
+        className="flex items-center gap-0.5 text-text-muted hover:text-accent-primary transition-colors disabled:opacity-70"
+        title={progress ? progress.line : "Push"}
+      >
+        {pushing ? <Loader2 size={12} className="animate-spin" /> : <ArrowUp size={12} />}
+        {pushing && progress?.percent != null && (
+          <span className="text-[10px] font-mono">{progress.percent}%</span>
+        )}
+      </button>
+    </>
+  );
+}
@@ -0,0 +1,184 @@
+import { useState } from "react";
+import { ChevronDown, ChevronRight, Archive, Loader2 } from "lucide-react";
+import { useQueryClient } from "@tanstack/react-query";
+import { useGitStashes, useGitStashDiff } from "@/hooks/useClaudeData";
+import { gitStashSave, gitStashApply, gitStashPop, gitStashDrop } from "@/lib/tauri";
+import type { StashEntry } from "@/lib/tauri";
+import { DiffLineRow } from "./DiffViewer";
+import { cn } from "@/lib/utils";
+
+interface StashSectionProps {
+  cwd: string;
+  hasChanges: boolean;
+}
+
+export function StashSection({ cwd, hasChanges }: StashSectionProps) {
+  const queryClient = useQueryClient();
+  const { data: stashes } = useGitStashes(cwd);
+  const [open, setOpen] = useState(false);
+  const [showForm, setShowForm] = useState(false);
+  const [message, setMessage] = useState("");
+  const [includeUntracked, setIncludeUntracked] = useState(false);
+  const [keepIndex, setKeepIndex] = useState(false);
+  const [busy, setBusy] = useState<string | null>(null);
+  const [error, setError] = useState<string | null>(null);
+  const [expanded, setExpanded] = useState<string | null>(null);
+  // Dropping can't be undone, so it takes a second click
+  const [armedDrop, setArmedDrop] = useState<string | null>(null);
+
+  const run = async (key: string, fn: () => Promise<unknown>) => {
+    setBusy(key);
+    setError(null);
+    try {
+      await fn();
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setBusy(null);
+      setArmedDrop(null);
+      queryClient.invalidateQueries({ queryKey: ["git-stashes", cwd] });
+      queryClient.invalidateQueries({ queryKey: ["git-status", cwd] });
+    }
+  };
+
+  const handleSave = () =>
+    run("save", async () => {
+      await gitStashSave(cwd, { message, includeUntracked, keepIndex });
+      setMessage("");
+      setShowForm(false);
+      setOpen(true);
+    });
+
+  const handleDrop = (stash: StashEntry) => {
+    if (armedDrop !== stash.hash) {
+      setArmedDrop(stash.hash);
+      return;
+    }
+    run(`drop:${stash.hash}`, () => gitStashDrop(cwd, stash.hash));
+  };
+
+  const count = stashes?.length ?? 0;
+
+  return (
+    <div className="border-t border-border-muted">
+      <div className="flex items-center">
+        <button
+          onClick={() => setOpen(!open)}
+          className="flex items-center gap-1 flex-1 px-3 py-1.5 text-[10px] font-semibold tracking-wider text-text-muted hover:text-text-secondary uppercase"
+        >
+          {open ? <ChevronDown size={10} /> : <ChevronRight size={10} />}
+          Stashes
+          <span className="ml-auto font-normal normal-case">{count}</span>
+        </button>
+        <button
+          onClick={() => setShowForm(!showForm)}
+          disabled={!hasChanges}
+          className="px-2 text-text-muted hover:text-accent-primary disabled:opacity-40 transition-colors"
+          title="Stash changes"
+        >
+          <Archive size={11} />
+        </button>
+      </div>
+
+      {showForm && (
+        <div className="flex flex-col gap-1 px-3 pb-2 text-[10px]">
+          <input
+            value={message}
+            onChange={(e) => setMessage(e.target.value)}
+            onKeyDown={(e) => e.key === "Enter" && handleSave()}
+            placeholder="Message (optional)"
+            className="bg-bg-raised border border-border-muted rounded px-1.5 py-0.5 text-xs outline-none focus:border-border-focus"
+          />
+          <div className="flex items-center gap-3 text-text-muted">
+            <label className="flex items-center gap-1">
+              <input type="checkbox" checked={includeUntracked} onChange={(e) => setIncludeUntracked(e.target.checked)} />
+              Include untracked
+            </label>
+            <label className="flex items-center gap-1">
+              <input type="checkbox" checked={keepIndex} onChange={(e) => setKeepIndex(e.target.checked)} />
+              Keep staged
+            </label>
+            <button
+              onClick={handleSave}
+              disabled={!!busy}
+              className="ml-auto flex items-center gap-1 px-2 py-0.5 rounded bg-accent-primary/10 text-accent-primary disabled:opacity-40"
+            >
+              {busy === "save" && <Loader2 size={9} className="animate-spin" />}
+              Stash
+            </button>
+          </div>
+        </div>
+      )}
+
+      {error && <p className="px-3 pb-1 text-[10px] text-status-error whitespace-pre-wrap">{error}</p>}
+
+      {open &&
+        (stashes ?? []).map((stash) => (
+          <div key={stash.hash}>
+            <div className="group flex items-center gap-2 px-4 py-1 text-xs">
+              <button
+                onClick={() => setExpanded(expanded === stash.hash ? null : stash.hash)}
+                className="flex-1 min-w-0 text-left text-text-secondary hover:text-text-primary"
+                title={`stash@{${stash.index}}${stash.branch ? ` on ${stash.branch}` : ""}${stash.includesUntracked ? ", with untracked files" : ""}`}
+              >
+                <span className="truncate block">{stash.message}</span>
+                <span className="text-[10px] text-text-muted">
+                  {stash.branch ?? "detached"} · {stash.date}
+                </span>
+              </button>
+              <div className="flex items-center gap-1 opacity-0 group-hover:opacity-100 transition-opacity text-[10px]">
+                <StashAction disabled={!!busy} onClick={() => run(`apply:${stash.hash}`, () => gitStashApply(cwd, stash.hash))}>
+                  Apply
+                </StashAction>
+                <StashAction disabled={!!busy} onClick={() => run(`pop:${stash.hash}`, () => gitStashPop(cwd, stash.hash))}>
+                  Pop
+                </StashAction>
+                <StashAction disabled={!!busy} danger onClick={() => handleDrop(stash)}>
+                  {armedDrop === stash.hash ? "Confirm" : "Drop"}
+                </StashAction>
+              </div>
+            </div>
+            {expanded === stash.hash && <StashDiff cwd={cwd} hash={stash.hash} />}
+          </div>
+        ))}
+    </div>
+  );
+}
+
+function StashDiff({ cwd, hash }: { cwd: string; hash: string }) {
+  const { data: lines, isLoading, error } = useGitStashDiff(cwd, hash);
+  if (isLoading) return <div className="px-4 py-1 text-[10px] text-text-muted">Loading diff...</div>;
+  if (error) return <div className="px-4 py-1 text-[10px] text-status-error">{String(error)}</div>;
+  return (
+    <div className="mx-2 mb-1 max-h-64 overflow-auto rounded border border-border-muted font-mono text-[10px]">
+      {(lines ?? []).map((line, i) => (
+        <DiffLineRow key={i} line={line} />
+      ))}
+    </div>
+  );
+}
+
+function StashAction({
+  children,
+  onClick,
+  disabled,
+  danger,
+}: {
+  children: React.ReactNode;
+  onClick: () => void;
+  disabled?: boolean;
+  danger?: boolean;
+}) {
+  return (
+    <button
+      onClick={onClick}
+      disabled={disabled}
+      className={cn(
+        "px-1.5 py-0.5 rounded border border-border-muted text-text-muted disabled:opacity-50 transition-colors",
+        danger ? "hover:text-status-error hover:border-status-error" : "hover:text-text-primary hover:border-border-focus"
+      )}
+    >
+      {children}
+    </button>
+  );
+}
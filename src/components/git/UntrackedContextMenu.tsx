@@ -1,7 +1,7 @@
-import { useRef, useEffect } from "react";
+import { useRef, useEffect, useState } from "react";
 import { useQueryClient } from "@tanstack/react-query";
 import { useGitAction } from "@/hooks/useGitAction";
-import { gitAdd, gitIgnore, gitExclude } from "@/lib/tauri";
+import { gitAdd, gitIgnore, gitExclude, gitUnstage, gitDiscard } from "@/lib/tauri";
 import type { GitFileEntry } from "@/lib/tauri";
 import { cn } from "@/lib/utils";
 
@@ -27,10 +27,14 @@ export function UntrackedContextMenu({
   const queryClient = useQueryClient();
 
   const isUntracked = section === "Untracked" || file?.section === "Untracked";
+  const isStaged = section === "Staged";
+  const isUnstaged = section === "Unstaged";
   const isHeader = file === null;
+  // Discarding can't be undone, so it takes a second click
+  const [discardArmed, setDiscardArmed] = useState(false);
 
   const menuWidth = 200;
-  const menuHeight = isHeader ? 60 : isUntracked ? 140 : 100;
+  const menuHeight = isHeader ? 60 : isUntracked || isStaged || isUnstaged ? 140 : 100;
   const left = Math.min(x, window.innerWidth - menuWidth - 8);
   const top = Math.min(y, window.innerHeight - menuHeight - 8);
 
@@ -64,6 +68,32 @@ export function UntrackedContextMenu({
     queryClient.invalidateQueries({ queryKey: ["git-status", cwd] });
   };
 
+  const handleUnstageFile = async () => {
+    if (!file) return;
+    onClose();
+    await runGitAction(`unstage ${file.path}`, async () => {
+      await gitUnstage(cwd, file.path);
+      return "";
+    });
+    queryClient.invalidateQueries({ queryKey: ["git-status", cwd] });
+    queryClient.invalidateQueries({ queryKey: ["git-diff", cwd, file.path] });
+  };
+
+  const handleDiscardFile = async () => {
+    if (!file) return;
+    if (!discardArmed) {
+      setDiscardArmed(true);
+      return;
+    }
+    onClose();
+    await runGitAction(`discard changes to ${file.path}`, async () => {
+      await gitDiscard(cwd, file.path);
+      return "";
+    });
+    queryClient.invalidateQueries({ queryKey: ["git-status", cwd] });
+    queryClient.invalidateQueries({ queryKey: ["git-diff", cwd, file.path] });
+  };
+
   const handleIgnoreFile = async () => {
     if (!file) return;
     onClose();
@@ -94,6 +124,20 @@ export function UntrackedContextMenu({
               <div className="my-1 border-t border-[var(--color-border-muted)]" />
             </>
           )}
+          {isStaged && (
+            <>
+              <MenuItem onClick={handleUnstageFile}>Unstage</MenuItem>
+              <div className="my-1 border-t border-[var(--color-border-muted)]" />
+            </>
+          )}
+          {isUnstaged && (
+            <>
+              <MenuItem onClick={handleDiscardFile}>
+                {discardArmed ? "Click again to discard" : "Discard changes"}
+              </MenuItem>
+              <div className="my-1 border-t border-[var(--color-border-muted)]" />
+            </>
+          )}
           <MenuItem onClick={handleIgnoreFile}>Add to .gitignore</MenuItem>
           <MenuItem onClick={handleExcludeFile}>Add to .git/info/exclude</MenuItem>
         </>
@@ -0,0 +1,135 @@
+import { useState } from "react";
+import { GitFork, ChevronRight, AlertTriangle, Lock, Unlock, Trash2, Loader2 } from "lucide-react";
+import { useQueryClient } from "@tanstack/react-query";
+import { removeWorktree, lockWorktree, unlockWorktree } from "@/lib/tauri";
+import type { WorktreeInfo } from "@/lib/tauri";
+import { cn } from "@/lib/utils";
+
+interface WorktreeRowProps {
+  projectDir: string;
+  worktree: WorktreeInfo;
+  isActive: boolean;
+  onSwitch: (path: string) => void;
+}
+
+export function WorktreeRow({ projectDir, worktree: wt, isActive, onSwitch }: WorktreeRowProps) {
+  const queryClient = useQueryClient();
+  const [confirming, setConfirming] = useState(false);
+  const [force, setForce] = useState(false);
+  const [cleanClaudeData, setCleanClaudeData] = useState(false);
+  const [busy, setBusy] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  const run = async (fn: () => Promise<unknown>) => {
+    setBusy(true);
+    setError(null);
+    try {
+      await fn();
+      setConfirming(false);
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setBusy(false);
+      queryClient.invalidateQueries({ queryKey: ["worktrees", projectDir] });
+    }
+  };
+
+  const handleLockToggle = () =>
+    run(() => (wt.isLocked ? unlockWorktree(projectDir, wt.path) : lockWorktree(projectDir, wt.path)));
+
+  const handleRemove = () => run(() => removeWorktree(projectDir, wt.path, { force, cleanClaudeData }));
+
+  // The main worktree holds the repository, and the active one is open here
+  const removable = !wt.isMain && !isActive;
+
+  return (
+    <div>
+      <div
+        className={cn(
+          "group flex items-center gap-2 px-4 py-1 text-xs",
+          isActive ? "text-accent-primary" : "text-text-secondary"
+        )}
+      >
+        <GitFork size={10} className="shrink-0 text-text-muted" />
+        <span className={cn("truncate flex-1 font-medium", isActive && "text-accent-primary")} title={wt.path}>
+          {wt.branch || wt.head}
+          {wt.isMain && <span className="ml-1 text-[9px] text-text-muted font-normal">(main)</span>}
+          {wt.isMerged && (
+            <span className="ml-1 text-[9px] text-status-success font-normal" title="Branch is merged into the main worktree's branch">
+              merged
+            </span>
+          )}
+        </span>
+        {wt.isPrunable && (
+          <span title="Prunable — worktree directory may be missing" className="shrink-0">
+            <AlertTriangle size={10} className="text-status-warning" />
+          </span>
+        )}
+        {!wt.isMain && (
+          <button
+            onClick={handleLockToggle}
+            disabled={busy}
+            className={cn(
+              "shrink-0 transition-colors",
+              wt.isLocked
+                ? "text-status-warning hover:text-text-primary"
+                : "text-text-muted hover:text-accent-primary opacity-0 group-hover:opacity-100"
+            )}
+            title={wt.isLocked ? `Locked${wt.lockReason ? `: ${wt.lockReason}` : ""} — click to unlock` : "Lock"}
+          >
+            {wt.isLocked ? <Lock size={10} /> : <Unlock size={10} />}
+          </button>
+        )}
+        {removable && (
+          <button
+            onClick={() => {
+              setConfirming(!confirming);
+              setError(null);
+            }}
+            className="shrink-0 text-text-muted hover:text-status-error opacity-0 group-hover:opacity-100 transition-all"
+            title="Remove worktree"
+          >
+            <Trash2 size={10} />
+          </button>
+        )}
+        {!isActive && (
+          <button
+            onClick={() => onSwitch(wt.path)}
+            className="shrink-0 text-text-muted hover:text-accent-primary transition-colors"
+            title={`Switch to ${wt.path}`}
+          >
+            <ChevronRight size={10} />
+          </button>
+        )}
+      </div>
+
+      {confirming && (
+        <div className="flex flex-col gap-1 px-6 pb-1.5 text-[10px] text-text-muted">
+          <span className="truncate">Remove {wt.path}? The branch is kept.</span>
+          <label className="flex items-center gap-1">
+            <input type="checkbox" checked={cleanClaudeData} onChange={(e) => setCleanClaudeData(e.target.checked)} />
+            Delete its Claude sessions
+          </label>
+          <label className="flex items-center gap-1">
+            <input type="checkbox" checked={force} onChange={(e) => setForce(e.target.checked)} />
+            Discard uncommitted changes
+          </label>
+          <div className="flex items-center gap-1.5">
+            <button
+              onClick={handleRemove}
+              disabled={busy}
+              className="flex items-center gap-1 px-2 py-0.5 rounded bg-status-error/10 text-status-error disabled:opacity-40"
+            >
+              {busy && <Loader2 size={9} className="animate-spin" />}
+              Remove
+            </button>
+            <button onClick={() => setConfirming(false)} className="px-2 py-0.5 hover:text-text-primary">
+              Cancel
+            </button>
+          </div>
+        </div>
+      )}
+      {error && <p className="px-6 pb-1 text-[10px] text-status-error whitespace-pre-wrap">{error}</p>}
+    </div>
+  );
+}
@@ -6,10 +6,14 @@ import { GitLogPanel } from "@/components/git/GitLogPanel";
 import { OutputPanel } from "@/components/layout/OutputPanel";
 import { DebugPanel } from "@/components/debug/DebugPanel";
 import { WorkflowsPanel } from "@/components/workflows/WorkflowsPanel";
+import { BroadcastPanel } from "@/components/terminal/BroadcastPanel";
+import { RecordingsPanel } from "@/components/terminal/RecordingsPanel";
 
 const BASE_TABS: { id: BottomTab; label: string }[] = [
   { id: "git", label: "Git" },
   { id: "workflows", label: "Workflows" },
+  { id: "broadcast", label: "Broadcast" },
+  { id: "recordings", label: "Recordings" },
   { id: "output", label: "Output" },
 ];
 
@@ -17,6 +21,8 @@ const tabPlaceholders: Record<BottomTab, string> = {
   git: "Select a project to view git log.",
   output: "No output.",
   workflows: "Open a project to see workflows.",
+  broadcast: "No Claude sessions running.",
+  recordings: "Open a project to see its recordings.",
   debug: "No debug entries.",
 };
 
@@ -68,6 +74,14 @@ function BottomPanelComponent() {
         <div className="flex-1 overflow-hidden">
           <WorkflowsPanel />
         </div>
+      ) : activeTab === "broadcast" ? (
+        <div className="flex-1 overflow-hidden">
+          <BroadcastPanel />
+        </div>
+      ) : activeTab === "recordings" ? (
+        <div className="flex-1 overflow-hidden">
+          <RecordingsPanel />
+        </div>
       ) : activeTab === "debug" ? (
         <div className="flex-1 overflow-hidden">
           <DebugPanel />
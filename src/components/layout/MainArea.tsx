@@ -1,5 +1,5 @@
 import { memo, useState, useCallback, useMemo } from "react";
-import { X, FileText, BookOpen, Settings, GitBranch, History, Terminal, Code2, CheckCircle2, GitPullRequest, Puzzle, CircleDot, Play } from "lucide-react";
+import { X, FileText, BookOpen, Settings, GitBranch, History, Terminal, SquareTerminal, Code2, CheckCircle2, GitPullRequest, Puzzle, CircleDot, Play } from "lucide-react";
 import { useSessionStore } from "@/stores/sessionStore";
 import type { SessionTab } from "@/stores/sessionStore";
 import { useActiveProjectTabs } from "@/hooks/useActiveProjectTabs";
@@ -7,6 +7,7 @@ import { useGlobalNotes, useProjectNotes } from "@/hooks/useClaudeData";
 import { useProjectsStore } from "@/stores/projectsStore";
 import { useUIStore } from "@/stores/uiStore";
 import { TerminalView } from "../terminal/TerminalView";
+import { PromptQueueBar } from "../terminal/PromptQueueBar";
 import { SessionView } from "../sessions/SessionView";
 import { SummaryView } from "../sessions/SummaryView";
 import { PlanEditorView } from "../plan/PlanEditorView";
@@ -205,6 +206,9 @@ function MainAreaComponent({ projectId: projectIdProp }: { projectId?: string })
               {(!tab.type || tab.type === "terminal") && (
                 <Terminal size={10} className={cn("shrink-0", isActive ? accent.icon : "text-text-muted")} />
               )}
+              {tab.type === "shell" && (
+                <SquareTerminal size={10} className={cn("shrink-0", isActive ? accent.icon : "text-text-muted")} />
+              )}
               {tab.title}
               {tab.type === "file" && tab.filePath && (
                 <TabNoteIndicator filePath={tab.filePath} noteFileSet={noteFileSet} />
@@ -293,14 +297,28 @@ function MainAreaComponent({ projectId: projectIdProp }: { projectId?: string })
                 <ExtensionView tab={tab} />
               ) : tab.type === "workflow-run" ? (
                 <WorkflowRunView tab={tab} />
-              ) : (
+              ) : tab.type === "shell" ? (
                 <TerminalView
                   sessionId={tab.id}
-                  resumeSessionId={tab.sessionId}
-                  forkSession={tab.forkSession}
                   cwd={tab.projectDir || "C:/dev"}
                   isActive={activeTabId === tab.id}
+                  shell
+                  profileId={tab.profileId}
                 />
+              ) : (
+                <div className="flex flex-col w-full h-full">
+                  <div className="flex-1 min-h-0">
+                    <TerminalView
+                      sessionId={tab.id}
+                      resumeSessionId={tab.sessionId}
+                      forkSession={tab.forkSession}
+                      cwd={tab.projectDir || "C:/dev"}
+                      isActive={activeTabId === tab.id}
+                      launchProfileId={tab.launchProfileId}
+                    />
+                  </div>
+                  <PromptQueueBar tabId={tab.id} />
+                </div>
               )}
             </div>
           ))
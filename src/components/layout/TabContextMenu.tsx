@@ -1,5 +1,6 @@
 import { useEffect, useRef } from "react";
 import type { SessionTab } from "@/stores/sessionStore";
+import { ptySnapshot } from "@/lib/tauri";
 
 export type TabCloseAction = "close" | "closeAll" | "closeOthers" | "closeLeft" | "closeRight";
 
@@ -18,6 +19,7 @@ export function TabContextMenu({ x, y, tab, tabs, onClose, onAction }: TabContex
   const isFirst = tabIndex === 0;
   const isLast = tabIndex === tabs.length - 1;
   const isOnly = tabs.length === 1;
+  const isTerminal = !tab.type || tab.type === "terminal" || tab.type === "shell";
 
   // Clamp position to viewport
   const menuWidth = 180;
@@ -47,6 +49,13 @@ export function TabContextMenu({ x, y, tab, tabs, onClose, onAction }: TabContex
     onClose();
   };
 
+  const copyScreen = () => {
+    ptySnapshot(tab.id)
+      .then((snap) => navigator.clipboard.writeText(snap.lines.join("\n").trimEnd()))
+      .catch(console.error);
+    onClose();
+  };
+
   return (
     <div
       ref={menuRef}
@@ -54,6 +63,7 @@ export function TabContextMenu({ x, y, tab, tabs, onClose, onAction }: TabContex
       className="fixed min-w-[180px] py-1 panel-card-overlay"
     >
       <MenuItem onClick={() => handleAction("close")}>Close</MenuItem>
+      {isTerminal && <MenuItem onClick={copyScreen}>Copy Screen as Text</MenuItem>}
       <div className="my-1 border-t border-[var(--color-border-muted)]" />
       <MenuItem onClick={() => handleAction("closeAll")}>Close All</MenuItem>
       <MenuItem
@@ -1,4 +1,4 @@
-import { Terminal, GitBranch, ChevronRight, ChevronDown, FileText, Scroll, Bot } from "lucide-react";
+import { Terminal, SquareTerminal, GitBranch, ChevronRight, ChevronDown, FileText, Scroll, Bot } from "lucide-react";
 import { useState, useEffect } from "react";
 import { useQueryClient } from "@tanstack/react-query";
 import { useProjectsStore } from "@/stores/projectsStore";
@@ -6,8 +6,8 @@ import { useSessionStore } from "@/stores/sessionStore";
 import { useSettingsStore } from "@/stores/settingsStore";
 import { useActiveProjectTabs } from "@/hooks/useActiveProjectTabs";
 import { useSessions, useSummaries, useSubagentSessions } from "@/hooks/useClaudeData";
-import type { SessionEntry, SummaryFile, SubagentSessionEntry } from "@/lib/tauri";
-import { deleteSession } from "@/lib/tauri";
+import type { SessionEntry, SummaryFile, SubagentSessionEntry, TerminalProfile, LaunchProfile } from "@/lib/tauri";
+import { deleteSession, listTerminalProfiles, listLaunchProfiles } from "@/lib/tauri";
 import { SessionContextMenu } from "@/components/sessions/SessionContextMenu";
 import { cn } from "@/lib/utils";
 
@@ -18,6 +18,19 @@ export function ProjectSwitcher() {
     x: number; y: number; session: SessionEntry; isLive: boolean;
   } | null>(null);
   const [expandedSessions, setExpandedSessions] = useState<Set<string>>(new Set());
+  const [terminalProfiles, setTerminalProfiles] = useState<TerminalProfile[]>([]);
+  const [profileId, setProfileId] = useState<string>("");
+  const [launchProfiles, setLaunchProfiles] = useState<LaunchProfile[]>([]);
+  const [launchProfileId, setLaunchProfileId] = useState<string>("");
+
+  useEffect(() => {
+    listTerminalProfiles()
+      .then((profiles) => {
+        setTerminalProfiles(profiles);
+        setProfileId((current) => current || profiles[0]?.id || "");
+      })
+      .catch(() => {});
+  }, []);
 
   const { openTabs, activeTabId, projectId } = useActiveProjectTabs();
   const openTab = useSessionStore((s) => s.openTab);
@@ -33,6 +46,14 @@ export function ProjectSwitcher() {
   const activeProjectPath = activeProject?.path ?? "";
   const { data: sessions, isLoading } = useSessions(activeProject?.path ?? "");
 
+  // Launch profiles are per project (merged with global ones), so reload on switch
+  useEffect(() => {
+    setLaunchProfileId("");
+    listLaunchProfiles(activeProjectPath || undefined)
+      .then(setLaunchProfiles)
+      .catch(() => setLaunchProfiles([]));
+  }, [activeProjectPath]);
+
   // Auto-open README + CLAUDE.md tabs when the active project changes (opt-in)
   useEffect(() => {
     if (!activeProject || !projectId || !openStartupFiles) return;
@@ -62,7 +83,28 @@ export function ProjectSwitcher() {
     if (!projectId) return;
     const id = `session-${Date.now()}`;
     openTab(
-      { id, title: "New Session", projectDir: activeProject?.path ?? "", spawnedAt: Date.now() },
+      {
+        id,
+        title: "New Session",
+        projectDir: activeProject?.path ?? "",
+        spawnedAt: Date.now(),
+        launchProfileId: launchProfileId || undefined,
+      },
+      projectId
+    );
+  };
+
+  const handleNewTerminal = () => {
+    if (!projectId) return;
+    const profile = terminalProfiles.find((p) => p.id === profileId);
+    openTab(
+      {
+        id: `shell-${Date.now()}`,
+        type: "shell",
+        title: profile?.name ?? "Terminal",
+        profileId: profile?.id,
+        projectDir: activeProject?.path ?? "",
+      },
       projectId
     );
   };
@@ -179,6 +221,43 @@ export function ProjectSwitcher() {
           <Terminal size={12} />
           New Claude Session
         </button>
+        {launchProfiles.length > 0 && (
+          <select
+            value={launchProfileId}
+            onChange={(e) => setLaunchProfileId(e.target.value)}
+            className="w-full mt-1.5 text-[10px] bg-[var(--color-bg-raised)] border border-[var(--color-border-muted)] rounded-lg px-1.5 py-1 text-[var(--color-text-secondary)] outline-none"
+            aria-label="Launch profile"
+          >
+            <option value="">Default launch</option>
+            {launchProfiles.map((p) => (
+              <option key={p.id} value={p.id}>
+                {p.name}{p.scope === "project" ? " (project)" : ""}
+              </option>
+            ))}
+          </select>
+        )}
+        <div className="flex items-center gap-1 mt-1.5">
+          <button
+            onClick={handleNewTerminal}
+            disabled={!projectId || terminalProfiles.length === 0}
+            className="flex items-center gap-2 flex-1 px-3 py-1.5 rounded-xl text-xs text-[var(--color-text-muted)] hover:bg-[var(--color-bg-overlay)] hover:text-[var(--color-text-primary)] transition-all duration-200 disabled:opacity-40 disabled:cursor-not-allowed"
+          >
+            <SquareTerminal size={12} />
+            New Terminal
+          </button>
+          {terminalProfiles.length > 1 && (
+            <select
+              value={profileId}
+              onChange={(e) => setProfileId(e.target.value)}
+              className="text-[10px] bg-[var(--color-bg-raised)] border border-[var(--color-border-muted)] rounded-lg px-1.5 py-1 text-[var(--color-text-secondary)] outline-none"
+              aria-label="Terminal profile"
+            >
+              {terminalProfiles.map((p) => (
+                <option key={p.id} value={p.id}>{p.name}</option>
+              ))}
+            </select>
+          )}
+        </div>
       </div>
 
       {/* Session list */}
@@ -4,7 +4,27 @@ import { useSettingsStore } from "@/stores/settingsStore";
 import { useProjectsStore } from "@/stores/projectsStore";
 import { CheckCircle, AlertCircle, Loader, ExternalLink, Info, ArrowUpCircle } from "lucide-react";
 import { cn } from "@/lib/utils";
-import { checkRemoteRunWorkflow, writeFile, getAppVersion } from "@/lib/tauri";
+import {
+  checkRemoteRunWorkflow,
+  writeFile,
+  getAppVersion,
+  getAutoResumePolicy,
+  getResourceLimits,
+  saveResourceLimits,
+  saveAutoResumePolicy,
+  hooksConfigured,
+  listClaudeHomes,
+  pickFolder,
+  saveClaudeHomes,
+  setHookScope,
+  switchClaudeHome,
+  type AutoResumePolicy,
+  type ClaudeHomeInfo,
+  type LimitAction,
+  type ResourceLimits,
+  type HookScope,
+  type HookStatus,
+} from "@/lib/tauri";
 import { REMOTE_RUN_YAML_CONTENT } from "@/lib/remoteRunYaml";
 
 const FONT_FAMILIES = [
@@ -527,14 +547,110 @@ function JiraSection() {
 
 // ── Session tracking section ──────────────────────────────────────────────────
 
+function ClaudeHomesSection() {
+  const [homes, setHomes] = useState<ClaudeHomeInfo[]>([]);
+  const [name, setName] = useState("");
+  const [path, setPath] = useState("");
+  const [loading, setLoading] = useState(false);
+  const [error, setError] = useState("");
+
+  useEffect(() => {
+    listClaudeHomes().then(setHomes).catch((e) => setError(String(e)));
+  }, []);
+
+  const registered = homes.filter((h) => !h.builtin).map(({ id, name, path }) => ({ id, name, path }));
+
+  const run = async (action: () => Promise<ClaudeHomeInfo[]>) => {
+    setLoading(true);
+    setError("");
+    try {
+      setHomes(await action());
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  const add = () =>
+    run(async () => {
+      const next = await saveClaudeHomes([...registered, { id: crypto.randomUUID(), name: name.trim(), path: path.trim() }]);
+      setName("");
+      setPath("");
+      return next;
+    });
+  const remove = (id: string) => run(() => saveClaudeHomes(registered.filter((h) => h.id !== id)));
+  const activate = (id: string) =>
+    run(async () => {
+      await switchClaudeHome(id);
+      return listClaudeHomes();
+    });
+  const browse = () => {
+    pickFolder()
+      .then((folder) => folder && setPath(folder))
+      .catch(console.error);
+  };
+
+  const field = "text-xs px-2 py-1 rounded-md bg-bg-raised border border-border-muted text-text-primary outline-none";
+  return (
+    <div className="space-y-3">
+      <SectionLabel>Claude Homes</SectionLabel>
+      <p className="text-[11px] text-text-muted">
+        Claude config roots to read sessions, teams, plans and hooks from, e.g. separate work and personal accounts. New Claude sessions get <code className="text-[10px] bg-bg-raised px-1 rounded-md">CLAUDE_CONFIG_DIR</code> set to the active one.
+      </p>
+      <div className="space-y-1">
+        {homes.map((h) => (
+          <div key={h.id} className="flex items-center gap-2">
+            <input
+              type="radio"
+              name="claude-home"
+              checked={h.active}
+              disabled={loading}
+              onChange={() => activate(h.id)}
+            />
+            <span className="text-xs text-text-secondary">{h.name}</span>
+            <span className="flex-1 truncate text-[11px] text-text-muted" title={h.path}>
+              {h.path}
+              {!h.exists && " (not created yet)"}
+            </span>
+            {!h.builtin && (
+              <button
+                onClick={() => remove(h.id)}
+                disabled={loading}
+                className="text-[11px] text-text-muted hover:text-status-error"
+              >
+                Remove
+              </button>
+            )}
+          </div>
+        ))}
+      </div>
+      <div className="flex items-center gap-2">
+        <input value={name} onChange={(e) => setName(e.target.value)} placeholder="Name" className={cn(field, "w-28")} />
+        <input value={path} onChange={(e) => setPath(e.target.value)} placeholder="/home/me/.claude-work" className={cn(field, "flex-1")} />
+        <Btn onClick={browse}>Browse</Btn>
+        <Btn onClick={add} loading={loading} disabled={!name.trim() || !path.trim()}>
+          Add
+        </Btn>
+      </div>
+      {error && (
+        <p className="text-[11px] text-status-error flex items-center gap-1">
+          <AlertCircle size={11} />
+          {error}
+        </p>
+      )}
+    </div>
+  );
+}
+
 function SessionTrackingSection() {
   const [configured, setConfigured] = useState<boolean | null>(null);
   const [loading, setLoading] = useState(false);
   const [error, setError] = useState("");
 
   useEffect(() => {
-    invoke<boolean>("cmd_hooks_configured")
-      .then(setConfigured)
+    invoke<HookStatus>("cmd_hooks_configured")
+      .then((status) => setConfigured(status.userInstalled))
       .catch(() => setConfigured(false));
   }, []);
 
@@ -602,6 +718,81 @@ function SessionTrackingSection() {
           {error}
         </p>
       )}
+      <ProjectHookScope />
+    </div>
+  );
+}
+
+function ProjectHookScope() {
+  const activeProjectId = useProjectsStore((s) => s.activeProjectId);
+  const projects = useProjectsStore((s) => s.projects);
+  const cwd = projects.find((p) => p.id === activeProjectId)?.path ?? null;
+  const [status, setStatus] = useState<HookStatus | null>(null);
+  const [loading, setLoading] = useState(false);
+  const [error, setError] = useState("");
+
+  useEffect(() => {
+    if (!cwd) return;
+    setStatus(null);
+    hooksConfigured(cwd)
+      .then(setStatus)
+      .catch((e) => setError(String(e)));
+  }, [cwd]);
+
+  if (!cwd) return null;
+
+  const choose = async (scope: HookScope) => {
+    setLoading(true);
+    setError("");
+    try {
+      await setHookScope(cwd, scope);
+      setStatus(await hooksConfigured(cwd));
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  return (
+    <div className="space-y-2 pt-1">
+      <div className="flex items-center justify-between">
+        <span className="text-xs text-text-secondary">This project</span>
+        <span className="text-[11px] text-text-muted">
+          {status === null
+            ? "Checking..."
+            : status.effectiveScope === "project"
+              ? "Project-local hooks"
+              : status.effectiveScope === "user"
+                ? "User-level hooks"
+                : "No hooks"}
+        </span>
+      </div>
+      <div className="flex gap-2">
+        <Btn
+          onClick={() => choose("user")}
+          loading={loading}
+          disabled={loading || status?.preferredScope === "user"}
+        >
+          User level
+        </Btn>
+        <Btn
+          onClick={() => choose("project")}
+          loading={loading}
+          disabled={loading || status?.preferredScope === "project"}
+        >
+          Project only
+        </Btn>
+      </div>
+      <p className="text-[11px] text-text-muted">
+        Project only writes hooks to <code className="text-[10px] bg-bg-raised px-1 rounded-md">.claude/settings.local.json</code> and removes the user-level hooks from <code className="text-[10px] bg-bg-raised px-1 rounded-md">~/.claude/settings.json</code>. They are reinstalled at launch only while another recently opened project uses user level.
+      </p>
+      {error && (
+        <p className="text-[11px] text-status-error flex items-center gap-1">
+          <AlertCircle size={11} />
+          {error}
+        </p>
+      )}
     </div>
   );
 }
@@ -634,6 +825,131 @@ function LiveDangerouslySection() {
   );
 }
 
+function AutoResumeSection() {
+  const [policy, setPolicy] = useState<AutoResumePolicy | null>(null);
+
+  useEffect(() => {
+    getAutoResumePolicy().then(setPolicy).catch(() => {});
+  }, []);
+
+  const update = (patch: Partial<AutoResumePolicy>) => {
+    if (!policy) return;
+    const next = { ...policy, ...patch };
+    setPolicy(next);
+    saveAutoResumePolicy(next).catch(console.error);
+  };
+
+  if (!policy) return null;
+  return (
+    <div className="space-y-3">
+      <SectionLabel>Auto-resume</SectionLabel>
+      <div>
+        <label className="flex items-center gap-2 cursor-pointer">
+          <input
+            type="checkbox"
+            checked={policy.enabled}
+            onChange={(e) => update({ enabled: e.target.checked })}
+            className="rounded-md"
+          />
+          <span className="text-xs font-medium text-text-secondary">
+            Resume Claude sessions that crash
+          </span>
+        </label>
+        <p className="text-[11px] text-text-muted mt-1 ml-5">
+          Relaunches <code className="text-[10px] bg-bg-raised px-1 rounded-md">claude --resume</code> with the same directory and launch profile when a session exits with an error. A clean <code className="text-[10px] bg-bg-raised px-1 rounded-md">/exit</code> is never resumed.
+        </p>
+      </div>
+      {policy.enabled && (
+        <div className="flex items-center gap-2 ml-5 text-[11px] text-text-muted">
+          At most
+          <input
+            type="number"
+            min={1}
+            value={policy.maxRetries}
+            onChange={(e) => update({ maxRetries: Math.max(1, Number(e.target.value) || 1) })}
+            className="w-12 text-xs px-1.5 py-0.5 rounded-md bg-bg-raised border border-border-muted text-text-primary outline-none"
+          />
+          retries per
+          <input
+            type="number"
+            min={1}
+            value={Math.round(policy.windowSecs / 60)}
+            onChange={(e) => update({ windowSecs: Math.max(1, Number(e.target.value) || 1) * 60 })}
+            className="w-12 text-xs px-1.5 py-0.5 rounded-md bg-bg-raised border border-border-muted text-text-primary outline-none"
+          />
+          minutes
+        </div>
+      )}
+    </div>
+  );
+}
+
+function ResourceLimitsSection() {
+  const [limits, setLimits] = useState<ResourceLimits | null>(null);
+
+  useEffect(() => {
+    getResourceLimits().then(setLimits).catch(() => {});
+  }, []);
+
+  const update = (patch: Partial<ResourceLimits>) => {
+    if (!limits) return;
+    const next = { ...limits, ...patch };
+    setLimits(next);
+    saveResourceLimits(next).catch(console.error);
+  };
+  const optionalNumber = (value: string) => (value.trim() === "" ? null : Math.max(1, Number(value) || 1));
+
+  if (!limits) return null;
+  const field = "w-16 text-xs px-1.5 py-0.5 rounded-md bg-bg-raised border border-border-muted text-text-primary outline-none";
+  return (
+    <div className="space-y-3">
+      <SectionLabel>Resource Limits</SectionLabel>
+      <div>
+        <label className="flex items-center gap-2 cursor-pointer">
+          <input
+            type="checkbox"
+            checked={limits.enabled}
+            onChange={(e) => update({ enabled: e.target.checked })}
+            className="rounded-md"
+          />
+          <span className="text-xs font-medium text-text-secondary">
+            Enforce limits on terminal process trees
+          </span>
+        </label>
+        <p className="text-[11px] text-text-muted mt-1 ml-5">
+          Each session's process and everything it started is sampled every few seconds. Usage of the active tab shows in the status bar. Leave a limit blank to skip it.
+        </p>
+      </div>
+      {limits.enabled && (
+        <div className="ml-5 space-y-2 text-[11px] text-text-muted">
+          <div className="flex items-center gap-2">
+            CPU %
+            <input type="number" min={1} value={limits.maxCpuPercent ?? ""} onChange={(e) => update({ maxCpuPercent: optionalNumber(e.target.value) })} className={field} />
+            Memory MB
+            <input type="number" min={1} value={limits.maxMemoryMb ?? ""} onChange={(e) => update({ maxMemoryMb: optionalNumber(e.target.value) })} className={field} />
+            Processes
+            <input type="number" min={1} value={limits.maxProcesses ?? ""} onChange={(e) => update({ maxProcesses: optionalNumber(e.target.value) })} className={field} />
+          </div>
+          <div className="flex items-center gap-2">
+            After
+            <input type="number" min={0} value={limits.sustainSecs} onChange={(e) => update({ sustainSecs: Math.max(0, Number(e.target.value) || 0) })} className={field} />
+            seconds over a limit:
+            <select
+              value={limits.action}
+              onChange={(e) => update({ action: e.target.value as LimitAction })}
+              className="text-xs px-1.5 py-0.5 rounded-md bg-bg-raised border border-border-muted text-text-primary outline-none"
+            >
+              <option value="warn">Warn</option>
+              <option value="killChildren">Kill child processes</option>
+              <option value="killTree">Kill the session</option>
+            </select>
+          </div>
+        </div>
+      )}
+    </div>
+  );
+}
+
 function NotificationsSection() {
   const { nativeNotificationsEnabled, setNativeNotificationsEnabled } = useSettingsStore();
   return (
@@ -1027,8 +1343,11 @@ export function SettingsTab() {
         <section>
           <SectionHeader>Session</SectionHeader>
           <div className="space-y-6">
+            <ClaudeHomesSection />
             <SessionTrackingSection />
             <LiveDangerouslySection />
+            <AutoResumeSection />
+            <ResourceLimitsSection />
             <div className="border-t border-border-muted" />
             <NotificationsSection />
             <div className="border-t border-border-muted" />
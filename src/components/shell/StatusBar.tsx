@@ -1,16 +1,32 @@
-import { memo } from "react";
-import { GitBranch } from "lucide-react";
+import { memo, useEffect, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
+import { Activity, GitBranch } from "lucide-react";
 import { cn } from "@/lib/utils";
 import { useProjectsStore } from "@/stores/projectsStore";
 import { useActiveProjectTabs } from "@/hooks/useActiveProjectTabs";
 import { useGitCurrentBranch, useGitStatus } from "@/hooks/useClaudeData";
 import { useSessionStore } from "@/stores/sessionStore";
 import { useUIStore } from "@/stores/uiStore";
+import type { PtyStats } from "@/lib/tauri";
 
 function getParentDir(path: string): string {
   return path.replace(/\\/g, "/").split("/").slice(0, -1).join("/");
 }
 
+/** Latest `pty-stats` sample per PTY session id. */
+function usePtyStats(): Record<string, PtyStats> {
+  const [stats, setStats] = useState<Record<string, PtyStats>>({});
+  useEffect(() => {
+    const unlisten = listen<PtyStats[]>("pty-stats", ({ payload }) => {
+      setStats(Object.fromEntries(payload.map((s) => [s.sessionId, s])));
+    });
+    return () => {
+      unlisten.then((f) => f());
+    };
+  }, []);
+  return stats;
+}
+
 function StatusBarComponent() {
   const activeProject = useProjectsStore((s) =>
     s.projects.find((p) => p.id === s.activeProjectId)
@@ -19,6 +35,8 @@ function StatusBarComponent() {
   const setActiveProject = useProjectsStore((s) => s.setActiveProject);
   const { openTabs, activeTabId } = useActiveProjectTabs();
   const activeTab = openTabs.find((t) => t.id === activeTabId);
+  const ptyStats = usePtyStats();
+  const tabStats = activeTab ? ptyStats[activeTab.id] : undefined;
   const toggleNeuralField = useUIStore((s) => s.toggleNeuralField);
   const activeSubagents = useSessionStore((s) => s.activeSubagents);
   const runningAgents = Object.values(activeSubagents).reduce(
@@ -45,7 +63,8 @@ function StatusBarComponent() {
   const changeCount =
     (gitStatus?.staged?.length ?? 0) +
     (gitStatus?.unstaged?.length ?? 0) +
-    (gitStatus?.untracked?.length ?? 0);
+    (gitStatus?.untracked?.length ?? 0) +
+    (gitStatus?.conflicted?.length ?? 0);
 
   return (
     <div
@@ -84,6 +103,15 @@ function StatusBarComponent() {
 
       {/* Right items */}
       <div className="flex items-center gap-3">
+        {tabStats && (
+          <span
+            className="flex items-center gap-1"
+            title={tabStats.top.map((p) => `${p.name} (${p.pid}): ${p.cpuPercent.toFixed(0)}% CPU, ${Math.round(p.memoryBytes / 1048576)} MB`).join("\n")}
+          >
+            <Activity size={10} />
+            {tabStats.cpuPercent.toFixed(0)}% · {Math.round(tabStats.memoryBytes / 1048576)} MB · {tabStats.processCount} proc
+          </span>
+        )}
         <button
           onClick={toggleNeuralField}
           className="hover:text-accent-primary transition-colors"
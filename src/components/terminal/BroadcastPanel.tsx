@@ -0,0 +1,158 @@
+import { useEffect, useMemo, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
+import { RefreshCw, Send, X } from "lucide-react";
+import { broadcastPrompt, dismissBroadcast, listBroadcasts, ptyList } from "@/lib/tauri";
+import type { Broadcast, BroadcastTarget, PtySessionInfo } from "@/lib/tauri";
+import { useSessionStore } from "@/stores/sessionStore";
+import { cn } from "@/lib/utils";
+
+function targetLabel(target: BroadcastTarget): string {
+  switch (target.state) {
+    case "running":
+      return "running";
+    case "question":
+      return `asking: ${target.question}`;
+    case "finished":
+      return "finished";
+    case "exited":
+      return "exited";
+    case "failed":
+      return `failed: ${target.error}`;
+  }
+}
+
+/** Send one prompt to several running Claude tabs and follow each one to its Stop. */
+export function BroadcastPanel() {
+  const tabsByProject = useSessionStore((s) => s.tabsByProject);
+  const [sessions, setSessions] = useState<PtySessionInfo[]>([]);
+  const [selected, setSelected] = useState<Set<string>>(new Set());
+  const [text, setText] = useState("");
+  const [broadcasts, setBroadcasts] = useState<Broadcast[]>([]);
+
+  const titles = useMemo(() => {
+    const map: Record<string, string> = {};
+    for (const tabs of Object.values(tabsByProject)) {
+      for (const tab of tabs) map[tab.id] = tab.title;
+    }
+    return map;
+  }, [tabsByProject]);
+
+  const refresh = () => {
+    ptyList()
+      .then((list) => setSessions(list.filter((s) => s.kind === "claude")))
+      .catch(() => {});
+  };
+
+  useEffect(() => {
+    refresh();
+    listBroadcasts().then(setBroadcasts).catch(() => {});
+    const unlisten = listen<Broadcast>("broadcast-changed", ({ payload }) => {
+      setBroadcasts((prev) => [payload, ...prev.filter((b) => b.id !== payload.id)].sort((a, b) => b.startedAt - a.startedAt));
+    });
+    return () => {
+      unlisten.then((f) => f());
+    };
+  }, []);
+
+  const toggle = (id: string) => {
+    setSelected((prev) => {
+      const next = new Set(prev);
+      if (next.has(id)) next.delete(id);
+      else next.add(id);
+      return next;
+    });
+  };
+
+  const send = () => {
+    if (!text.trim() || selected.size === 0) return;
+    broadcastPrompt([...selected], text)
+      .then(() => setText(""))
+      .catch(console.error);
+  };
+
+  const dismiss = (id: string) => {
+    dismissBroadcast(id)
+      .then(() => setBroadcasts((prev) => prev.filter((b) => b.id !== id)))
+      .catch(console.error);
+  };
+
+  return (
+    <div className="flex h-full bg-bg-surface text-xs">
+      {/* Targets + prompt */}
+      <div className="w-72 shrink-0 flex flex-col border-r border-border-muted/60 p-2 gap-2">
+        <div className="flex items-center justify-between text-text-muted">
+          <span className="text-[10px] font-semibold tracking-wider uppercase">Targets</span>
+          <button onClick={refresh} aria-label="Refresh sessions" className="hover:text-text-primary">
+            <RefreshCw size={11} />
+          </button>
+        </div>
+        <div className="flex-1 overflow-y-auto space-y-0.5">
+          {sessions.length === 0 ? (
+            <p className="text-text-muted">No Claude sessions running.</p>
+          ) : (
+            sessions.map((s) => (
+              <label key={s.sessionId} className="flex items-center gap-2 cursor-pointer text-text-secondary hover:text-text-primary">
+                <input type="checkbox" checked={selected.has(s.sessionId)} onChange={() => toggle(s.sessionId)} />
+                <span className="truncate" title={s.cwd}>{titles[s.sessionId] ?? s.sessionId}</span>
+              </label>
+            ))
+          )}
+        </div>
+        <textarea
+          value={text}
+          onChange={(e) => setText(e.target.value)}
+          placeholder="Prompt to send to every selected session…"
+          rows={3}
+          className="bg-bg-raised border border-border-muted rounded-lg px-2 py-1 outline-none resize-none"
+        />
+        <button
+          onClick={send}
+          disabled={!text.trim() || selected.size === 0}
+          className="flex items-center justify-center gap-1.5 rounded-lg px-2 py-1 bg-accent-primary/10 text-accent-primary disabled:opacity-40"
+        >
+          <Send size={11} />
+          Send to {selected.size} session{selected.size === 1 ? "" : "s"}
+        </button>
+      </div>
+
+      {/* Status */}
+      <div className="flex-1 overflow-y-auto p-2 space-y-2">
+        {broadcasts.length === 0 && <p className="text-text-muted">No broadcasts yet.</p>}
+        {broadcasts.map((b) => (
+          <div key={b.id} className="rounded-lg bg-bg-raised px-2 py-1.5">
+            <div className="flex items-center gap-2">
+              <span className="flex-1 truncate text-text-primary" title={b.prompt}>{b.prompt}</span>
+              <span className={cn(b.summary.done ? "text-status-success" : "text-text-muted")}>
+                {b.summary.finished}/{b.targets.length} finished
+                {b.summary.question > 0 && ` · ${b.summary.question} asking`}
+                {b.summary.running > 0 && ` · ${b.summary.running} running`}
+              </span>
+              <button onClick={() => dismiss(b.id)} aria-label="Dismiss" className="text-text-muted hover:text-text-primary">
+                <X size={11} />
+              </button>
+            </div>
+            <div className="mt-1 space-y-0.5">
+              {b.targets.map((t) => (
+                <div key={t.tabId} className="flex gap-2">
+                  <span className="w-40 shrink-0 truncate text-text-secondary">{titles[t.tabId] ?? t.tabId}</span>
+                  <span
+                    className={cn(
+                      "flex-1 truncate",
+                      t.state === "finished" && "text-status-success",
+                      t.state === "question" && "text-status-warning",
+                      (t.state === "failed" || t.state === "exited") && "text-status-error",
+                      t.state === "running" && "text-text-muted"
+                    )}
+                    title={t.state === "finished" ? t.lastMessage ?? undefined : undefined}
+                  >
+                    {targetLabel(t)}
+                  </span>
+                </div>
+              ))}
+            </div>
+          </div>
+        ))}
+      </div>
+    </div>
+  );
+}
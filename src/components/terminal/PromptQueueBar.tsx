@@ -0,0 +1,135 @@
+import { useEffect, useState } from "react";
+import { listen } from "@tauri-apps/api/event";
+import { ListOrdered, ChevronUp, ChevronDown, X, Pause, Play, Send } from "lucide-react";
+import {
+  getPromptQueue,
+  enqueuePrompt,
+  removeQueuedPrompt,
+  reorderPromptQueue,
+  setPromptQueuePaused,
+  sendNextPrompt,
+} from "@/lib/tauri";
+import type { PromptQueue } from "@/lib/tauri";
+import { cn } from "@/lib/utils";
+
+const EMPTY: PromptQueue = { items: [], paused: false, history: [] };
+
+/** Prompts queued for a Claude tab; the backend sends the next one on each Stop. */
+export function PromptQueueBar({ tabId }: { tabId: string }) {
+  const [queue, setQueue] = useState<PromptQueue>(EMPTY);
+  const [open, setOpen] = useState(false);
+  const [text, setText] = useState("");
+  const [onlyIf, setOnlyIf] = useState("");
+
+  useEffect(() => {
+    getPromptQueue(tabId).then(setQueue).catch(() => {});
+    const unlisten = listen<{ tab_id: string; queue: PromptQueue }>("prompt-queue-changed", ({ payload }) => {
+      if (payload.tab_id === tabId) setQueue(payload.queue);
+    });
+    return () => {
+      unlisten.then((f) => f());
+    };
+  }, [tabId]);
+
+  const add = () => {
+    if (!text.trim()) return;
+    const condition = onlyIf.trim() ? { type: "lastMessageContains" as const, text: onlyIf.trim() } : undefined;
+    enqueuePrompt(tabId, text, condition)
+      .then(() => {
+        setText("");
+        setOnlyIf("");
+      })
+      .catch(console.error);
+  };
+
+  const move = (index: number, delta: number) => {
+    const ids = queue.items.map((p) => p.id);
+    const target = index + delta;
+    if (target < 0 || target >= ids.length) return;
+    [ids[index], ids[target]] = [ids[target], ids[index]];
+    reorderPromptQueue(tabId, ids).catch(console.error);
+  };
+
+  return (
+    <div className="shrink-0 border-t border-[var(--color-border-muted)] bg-[var(--color-bg-surface)] text-xs">
+      <button
+        onClick={() => setOpen((o) => !o)}
+        className="flex items-center gap-2 w-full px-3 py-1 text-[var(--color-text-muted)] hover:text-[var(--color-text-primary)]"
+      >
+        <ListOrdered size={12} />
+        Prompt queue
+        {queue.items.length > 0 && <span className="text-[var(--color-accent-primary)]">({queue.items.length})</span>}
+        {queue.paused && <span className="text-[var(--color-status-warning)]">paused</span>}
+        <span className="ml-auto">{open ? <ChevronDown size={12} /> : <ChevronUp size={12} />}</span>
+      </button>
+      {open && (
+        <div className="px-3 pb-2 space-y-1.5 max-h-64 overflow-y-auto">
+          {queue.items.map((p, i) => (
+            <div key={p.id} className="flex items-start gap-1.5 rounded-lg bg-[var(--color-bg-raised)] px-2 py-1">
+              <span className="text-[var(--color-text-muted)] w-4 shrink-0">{i + 1}.</span>
+              <div className="flex-1 min-w-0">
+                <div className="truncate text-[var(--color-text-primary)]" title={p.text}>{p.text}</div>
+                {p.condition && (
+                  <div className="text-[10px] text-[var(--color-text-muted)]">
+                    only if last message {p.condition.type === "lastMessageContains" ? "contains" : "lacks"} “{p.condition.text}”
+                  </div>
+                )}
+              </div>
+              <button onClick={() => move(i, -1)} aria-label="Move up" className="text-[var(--color-text-muted)] hover:text-[var(--color-text-primary)]"><ChevronUp size={12} /></button>
+              <button onClick={() => move(i, 1)} aria-label="Move down" className="text-[var(--color-text-muted)] hover:text-[var(--color-text-primary)]"><ChevronDown size={12} /></button>
+              <button onClick={() => removeQueuedPrompt(tabId, p.id).catch(console.error)} aria-label="Remove" className="text-[var(--color-text-muted)] hover:text-[var(--color-status-error)]"><X size={12} /></button>
+            </div>
+          ))}
+          <div className="flex gap-1.5">
+            <input
+              value={text}
+              onChange={(e) => setText(e.target.value)}
+              onKeyDown={(e) => e.key === "Enter" && add()}
+              placeholder="Next prompt…"
+              className="flex-1 bg-[var(--color-bg-raised)] border border-[var(--color-border-muted)] rounded-lg px-2 py-1 outline-none"
+            />
+            <input
+              value={onlyIf}
+              onChange={(e) => setOnlyIf(e.target.value)}
+              onKeyDown={(e) => e.key === "Enter" && add()}
+              placeholder="Only if last message contains…"
+              className="w-48 bg-[var(--color-bg-raised)] border border-[var(--color-border-muted)] rounded-lg px-2 py-1 outline-none"
+            />
+          </div>
+          <div className="flex items-center gap-3 text-[var(--color-text-muted)]">
+            <button onClick={() => setPromptQueuePaused(tabId, !queue.paused).catch(console.error)} className="flex items-center gap-1 hover:text-[var(--color-text-primary)]">
+              {queue.paused ? <Play size={11} /> : <Pause size={11} />}
+              {queue.paused ? "Resume" : "Pause"}
+            </button>
+            <button
+              onClick={() => sendNextPrompt(tabId).catch(console.error)}
+              disabled={queue.items.length === 0}
+              className="flex items-center gap-1 hover:text-[var(--color-text-primary)] disabled:opacity-40"
+            >
+              <Send size={11} />
+              Send next now
+            </button>
+          </div>
+          {queue.history.length > 0 && (
+            <div className="pt-1 space-y-0.5">
+              {queue.history.slice(0, 10).map((h) => (
+                <div
+                  key={`${h.id}-${h.at}`}
+                  className={cn(
+                    "truncate",
+                    h.outcome === "skipped" && "text-[var(--color-text-muted)] line-through",
+                    h.outcome === "sent" && "text-[var(--color-text-secondary)]",
+                    h.outcome === "failed" && "text-[var(--color-status-error)]"
+                  )}
+                  title={h.error ?? undefined}
+                >
+                  {new Date(h.at).toLocaleTimeString()} · {h.outcome === "failed" ? "failed: " : ""}{h.text}
+                </div>
+              ))}
+            </div>
+          )}
+        </div>
+      )}
+    </div>
+  );
+}
@@ -0,0 +1,211 @@
+import { useEffect, useRef, useState } from "react";
+import { Channel } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { Terminal } from "@xterm/xterm";
+import { Play, RefreshCw, RotateCcw, Square } from "lucide-react";
+import { useRecordings } from "@/hooks/useClaudeData";
+import { useProjectsStore } from "@/stores/projectsStore";
+import { useSettingsStore } from "@/stores/settingsStore";
+import { playRecording, stopPlayback } from "@/lib/tauri";
+import type { RecordingInfo } from "@/lib/tauri";
+import { TERMINAL_THEME } from "./TerminalView";
+import { cn } from "@/lib/utils";
+import "@xterm/xterm/css/xterm.css";
+
+const SPEEDS = [0.5, 1, 2, 4];
+/** Longest pause kept, in seconds; null plays pauses as recorded */
+const IDLE_LIMITS: (number | null)[] = [null, 1, 2, 5];
+
+function formatDuration(secs: number): string {
+  const total = Math.round(secs);
+  const m = Math.floor(total / 60);
+  const s = total % 60;
+  return `${m}:${String(s).padStart(2, "0")}`;
+}
+
+/** Recorded sessions of the active project, and a player for one of them. */
+export function RecordingsPanel() {
+  const projectPath = useProjectsStore((s) =>
+    s.projects.find((p) => p.id === s.activeProjectId)?.path ?? null
+  );
+  const { data: recordings, isLoading, error, refetch } = useRecordings(projectPath);
+  const [selected, setSelected] = useState<RecordingInfo | null>(null);
+
+  // Drop the selection when switching projects
+  useEffect(() => setSelected(null), [projectPath]);
+
+  if (!projectPath) {
+    return <p className="p-4 text-sm text-text-muted text-center">Open a project to see its recordings.</p>;
+  }
+
+  return (
+    <div className="flex h-full">
+      <div className="flex flex-col w-64 shrink-0 border-r border-border-muted">
+        <div className="flex items-center px-3 py-1.5 text-[10px] font-semibold tracking-wider text-text-muted uppercase">
+          Recordings
+          <button
+            onClick={() => refetch()}
+            className="ml-auto text-text-muted hover:text-text-primary transition-colors"
+            title="Refresh"
+          >
+            <RefreshCw size={11} />
+          </button>
+        </div>
+        <div className="flex-1 overflow-y-auto">
+          {isLoading && <p className="px-3 py-1 text-xs text-text-muted">Loading...</p>}
+          {error && <p className="px-3 py-1 text-xs text-status-error">{String(error)}</p>}
+          {recordings?.length === 0 && (
+            <p className="px-3 py-1 text-xs text-text-muted">No recordings. Start one from a terminal tab.</p>
+          )}
+          {recordings?.map((rec) => (
+            <button
+              key={rec.id}
+              onClick={() => setSelected(rec)}
+              className={cn(
+                "flex flex-col w-full px-3 py-1 text-left text-xs transition-colors",
+                selected?.id === rec.id
+                  ? "bg-bg-raised text-text-primary"
+                  : "text-text-secondary hover:bg-bg-raised hover:text-text-primary"
+              )}
+              title={rec.path}
+            >
+              <span className="truncate">{rec.title || rec.id}</span>
+              <span className="text-[10px] text-text-muted">
+                {rec.startedAt ? new Date(rec.startedAt * 1000).toLocaleString() : rec.id} · {formatDuration(rec.durationSecs)} · {rec.width}x{rec.height}
+              </span>
+            </button>
+          ))}
+        </div>
+      </div>
+      <div className="flex-1 min-w-0">
+        {selected ? (
+          <RecordingPlayer key={selected.id} projectPath={projectPath} recording={selected} />
+        ) : (
+          <p className="p-4 text-sm text-text-muted text-center">Select a recording to play it.</p>
+        )}
+      </div>
+    </div>
+  );
+}
+
+type PlayerStatus = "playing" | "finished" | "stopped" | "failed";
+
+function RecordingPlayer({ projectPath, recording }: { projectPath: string; recording: RecordingInfo }) {
+  const containerRef = useRef<HTMLDivElement>(null);
+  const fontSize = useSettingsStore((s) => s.fontSize);
+  const fontFamily = useSettingsStore((s) => s.fontFamily);
+  const [speed, setSpeed] = useState(1);
+  const [idleLimit, setIdleLimit] = useState<number | null>(2);
+  // Bumped to restart playback from the beginning
+  const [run, setRun] = useState(0);
+  const [status, setStatus] = useState<PlayerStatus>("playing");
+  const [error, setError] = useState<string | null>(null);
+  const playbackIdRef = useRef<string | null>(null);
+
+  useEffect(() => {
+    if (!containerRef.current) return;
+    const term = new Terminal({
+      theme: TERMINAL_THEME,
+      fontFamily,
+      fontSize,
+      lineHeight: 1.4,
+      cols: recording.width,
+      rows: recording.height,
+      cursorBlink: false,
+      disableStdin: true,
+      scrollback: 5000,
+    });
+    term.open(containerRef.current);
+
+    const playbackId = `playback-${crypto.randomUUID()}`;
+    playbackIdRef.current = playbackId;
+    setStatus("playing");
+    setError(null);
+
+    const output = new Channel<ArrayBuffer>();
+    output.onmessage = (chunk) => term.write(new Uint8Array(chunk));
+    const unlistenResize = listen<{ cols: number; rows: number }>(`playback-resize-${playbackId}`, ({ payload }) => {
+      term.resize(payload.cols, payload.rows);
+    });
+    const unlistenExit = listen(`playback-exit-${playbackId}`, () => setStatus("finished"));
+
+    // Subscribe before starting so the first resize isn't missed
+    Promise.all([unlistenResize, unlistenExit])
+      .then(() => playRecording(projectPath, recording.id, playbackId, output, speed, idleLimit ?? undefined))
+      .catch((e: unknown) => {
+        setStatus("failed");
+        setError(String(e));
+      });
+
+    return () => {
+      stopPlayback(playbackId).catch(() => {});
+      unlistenResize.then((f) => f());
+      unlistenExit.then((f) => f());
+      term.dispose();
+      playbackIdRef.current = null;
+    };
+    // Font changes apply on the next run; restarting mid-playback would lose the position
+  }, [projectPath, recording, run, speed, idleLimit]); // eslint-disable-line react-hooks/exhaustive-deps
+
+  const handleStop = () => {
+    if (playbackIdRef.current) stopPlayback(playbackIdRef.current).catch(() => {});
+    setStatus("stopped");
+  };
+
+  return (
+    <div className="flex flex-col h-full">
+      <div className="flex items-center gap-2 px-3 py-1.5 border-b border-border-muted text-[10px] text-text-muted">
+        <span className="truncate text-xs text-text-secondary">{recording.title || recording.id}</span>
+        <span className="shrink-0">{formatDuration(recording.durationSecs)}</span>
+        <span className={cn("shrink-0", status === "failed" && "text-status-error")}>{status}</span>
+        <div className="ml-auto flex items-center gap-2">
+          <label className="flex items-center gap-1" title="Playback speed">
+            Speed
+            <select
+              value={speed}
+              onChange={(e) => setSpeed(Number(e.target.value))}
+              className="bg-bg-raised border border-border-muted rounded px-1 py-0.5 outline-none"
+            >
+              {SPEEDS.map((s) => (
+                <option key={s} value={s}>
+                  {s}x
+                </option>
+              ))}
+            </select>
+          </label>
+          <label className="flex items-center gap-1" title="Shorten pauses longer than this">
+            Max pause
+            <select
+              value={idleLimit ?? ""}
+              onChange={(e) => setIdleLimit(e.target.value ? Number(e.target.value) : null)}
+              className="bg-bg-raised border border-border-muted rounded px-1 py-0.5 outline-none"
+            >
+              {IDLE_LIMITS.map((l) => (
+                <option key={l ?? "off"} value={l ?? ""}>
+                  {l === null ? "as recorded" : `${l}s`}
+                </option>
+              ))}
+            </select>
+          </label>
+          {status === "playing" ? (
+            <button onClick={handleStop} className="text-text-muted hover:text-status-error transition-colors" title="Stop">
+              <Square size={11} />
+            </button>
+          ) : (
+            <button
+              onClick={() => setRun((r) => r + 1)}
+              className="text-text-muted hover:text-accent-primary transition-colors"
+              title="Play again"
+            >
+              {status === "finished" ? <RotateCcw size={11} /> : <Play size={11} />}
+            </button>
+          )}
+        </div>
+      </div>
+      {error && <p className="px-3 py-1 text-[10px] text-status-error whitespace-pre-wrap">{error}</p>}
+      <div className="flex-1 overflow-auto p-2 bg-[#1A1A22]">
+        <div ref={containerRef} />
+      </div>
+    </div>
+  );
+}
@@ -4,19 +4,49 @@ import { FitAddon } from "@xterm/addon-fit";
 import { WebLinksAddon } from "@xterm/addon-web-links";
 import { SearchAddon } from "@xterm/addon-search";
 import { listen } from "@tauri-apps/api/event";
-import { invoke } from "@tauri-apps/api/core";
+import { invoke, Channel } from "@tauri-apps/api/core";
 import { useSettingsStore } from "@/stores/settingsStore";
+import type { PtyExit, PtyOutputSnapshot } from "@/lib/tauri";
 import "@xterm/xterm/css/xterm.css";
 
+export const TERMINAL_THEME = {
+  background: "#1A1A22",
+  foreground: "#F5F5F8",
+  cursor: "#D4A853",
+  cursorAccent: "#1A1A22",
+  selectionBackground: "rgba(212, 168, 83, 0.25)",
+  black: "#32323D",
+  red: "#F85149",
+  green: "#4ACA62",
+  yellow: "#E0A82E",
+  blue: "#D4A853",
+  magenta: "#BC8CFF",
+  cyan: "#39C5CF",
+  white: "#F5F5F8",
+  brightBlack: "#7A7A92",
+  brightRed: "#F85149",
+  brightGreen: "#4ACA62",
+  brightYellow: "#E8C97A",
+  brightBlue: "#E8C97A",
+  brightMagenta: "#D2A8FF",
+  brightCyan: "#56D364",
+  brightWhite: "#FFFFFF",
+};
+
 interface TerminalViewProps {
   sessionId: string;
   resumeSessionId?: string;
   forkSession?: boolean;
   cwd: string;
   isActive: boolean;
+  /** Open a plain shell from a terminal profile instead of Claude */
+  shell?: boolean;
+  profileId?: string;
+  /** Claude launch profile whose flags and env are applied on spawn */
+  launchProfileId?: string;
 }
 
-export function TerminalView({ sessionId, resumeSessionId, forkSession, cwd, isActive }: TerminalViewProps) {
+export function TerminalView({ sessionId, resumeSessionId, forkSession, cwd, isActive, shell, profileId, launchProfileId }: TerminalViewProps) {
   const containerRef = useRef<HTMLDivElement>(null);
   const termRef = useRef<Terminal | null>(null);
   const fitAddonRef = useRef<FitAddon | null>(null);
@@ -47,29 +77,7 @@ export function TerminalView({ sessionId, resumeSessionId, forkSession, cwd, isA
     spawnedRef.current = true;
 
     const term = new Terminal({
-      theme: {
-        background: "#1A1A22",
-        foreground: "#F5F5F8",
-        cursor: "#D4A853",
-        cursorAccent: "#1A1A22",
-        selectionBackground: "rgba(212, 168, 83, 0.25)",
-        black: "#32323D",
-        red: "#F85149",
-        green: "#4ACA62",
-        yellow: "#E0A82E",
-        blue: "#D4A853",
-        magenta: "#BC8CFF",
-        cyan: "#39C5CF",
-        white: "#F5F5F8",
-        brightBlack: "#7A7A92",
-        brightRed: "#F85149",
-        brightGreen: "#4ACA62",
-        brightYellow: "#E8C97A",
-        brightBlue: "#E8C97A",
-        brightMagenta: "#D2A8FF",
-        brightCyan: "#56D364",
-        brightWhite: "#FFFFFF",
-      },
+      theme: TERMINAL_THEME,
       fontFamily: fontFamilyRef.current,
       fontSize: fontSizeRef.current,
       lineHeight: 1.4,
@@ -121,27 +129,98 @@ export function TerminalView({ sessionId, resumeSessionId, forkSession, cwd, isA
       invoke("pty_write", { sessionId, data }).catch(console.error);
     });
 
-    // Spawn the Claude process with actual terminal dimensions
-    invoke("pty_spawn", {
-      sessionId,
-      resumeSessionId: resumeSessionId ?? null,
-      forkSession: forkSession ?? false,
-      cwd,
-      rows: dims.rows,
-      cols: dims.cols,
-      skipPermissions: dangerouslySkipPermissions,
-    }).catch((e: unknown) => {
-      term.writeln(`\x1b[31mFailed to start Claude: ${e}\x1b[0m`);
-    });
+    // Terminal output arrives as batched raw bytes, each prefixed with the stream
+    // offset of its first byte; ack each batch once xterm.js has parsed it so the
+    // backend can throttle when rendering falls behind
+    const ack = (bytes: number) => invoke("pty_ack", { sessionId, bytes }).catch(() => {});
+    // Live batches can arrive before the attach snapshot, so they are held until
+    // it is written; anything the snapshot already covers is dropped
+    let snapshotEnd: number | null = null;
+    let held: ArrayBuffer[] = [];
+    const writeChunk = (chunk: ArrayBuffer) => {
+      const start = Number(new DataView(chunk).getBigUint64(0, true));
+      const bytes = new Uint8Array(chunk, 8);
+      const skip = Math.max(0, (snapshotEnd ?? 0) - start);
+      if (skip >= bytes.byteLength) {
+        ack(bytes.byteLength);
+        return;
+      }
+      term.write(bytes.subarray(skip), () => ack(bytes.byteLength));
+    };
+    const output = new Channel<ArrayBuffer>();
+    output.onmessage = (chunk) => {
+      if (snapshotEnd === null) held.push(chunk);
+      else writeChunk(chunk);
+    };
+    const attach = async () => {
+      snapshotEnd = null;
+      const snapshot = await invoke<PtyOutputSnapshot | null>("pty_attach", { sessionId, output });
+      if (snapshot) {
+        if (snapshot.data) term.write(snapshot.data);
+        snapshotEnd = snapshot.offset;
+        const pending = held;
+        held = [];
+        pending.forEach(writeChunk);
+      }
+      return snapshot;
+    };
+    const spawn = () =>
+      shell
+        ? invoke("pty_spawn_shell", {
+            sessionId,
+            profileId: profileId ?? null,
+            cwd,
+            rows: dims.rows,
+            cols: dims.cols,
+          })
+        : invoke("pty_spawn", {
+            options: {
+              sessionId,
+              resumeSessionId: resumeSessionId ?? null,
+              forkSession: forkSession ?? false,
+              cwd,
+              rows: dims.rows,
+              cols: dims.cols,
+              skipPermissions: dangerouslySkipPermissions,
+              launchProfileId: launchProfileId ?? null,
+            },
+          });
 
-    // Listen for terminal output
-    const unlisten = listen<string>(`pty-data-${sessionId}`, (event) => {
-      term.write(event.payload);
+    const unlistenExit = listen<PtyExit>(`pty-exit-${sessionId}`, ({ payload }) => {
+      if (payload.reason !== "crashed") {
+        term.writeln("\r\n\x1b[33m[Process exited]\x1b[0m");
+        return;
+      }
+      term.writeln(`\r\n\x1b[31m[Process crashed: ${payload.description}]\x1b[0m`);
+      if (payload.resume) {
+        const { attempt, maxRetries, delayMs } = payload.resume;
+        term.writeln(`\x1b[33m[Resuming in ${Math.round(delayMs / 1000)}s, attempt ${attempt}/${maxRetries}]\x1b[0m`);
+      }
     });
-
-    const unlistenExit = listen(`pty-exit-${sessionId}`, () => {
-      term.writeln("\r\n\x1b[33m[Process exited]\x1b[0m");
+    // Auto-resume replaced the process (and its output stream): subscribe again
+    const unlistenResumed = listen(`pty-resumed-${sessionId}`, () => {
+      term.writeln("\x1b[32m[Session resumed]\x1b[0m");
+      attach().catch(() => {});
     });
+    const unlistenResumeFailed = listen<string>(`pty-resume-failed-${sessionId}`, ({ payload }) => {
+      term.writeln(`\x1b[31m[Resume failed: ${payload}]\x1b[0m`);
+    });
+
+    // Reattach to a PTY that outlived the webview (reload) by replaying its
+    // scrollback; otherwise spawn the Claude process (or shell) with actual terminal
+    // dimensions, then attach to pick up whatever it printed in the meantime
+    attach()
+      .then(async (replay) => {
+        if (replay !== null) {
+          invoke("pty_resize", { sessionId, rows: dims.rows, cols: dims.cols }).catch(() => {});
+          return;
+        }
+        await spawn();
+        await attach();
+      })
+      .catch((e: unknown) => {
+        term.writeln(`\x1b[31mFailed to start ${shell ? "terminal" : "Claude"}: ${e}\x1b[0m`);
+      });
 
     // Resize observer: fit xterm then sync PTY size
     const resizeObserver = new ResizeObserver(() => {
@@ -158,8 +237,9 @@ export function TerminalView({ sessionId, resumeSessionId, forkSession, cwd, isA
 
     return () => {
       spawnedRef.current = false;
-      unlisten.then((f) => f());
       unlistenExit.then((f) => f());
+      unlistenResumed.then((f) => f());
+      unlistenResumeFailed.then((f) => f());
       resizeObserver.disconnect();
       dataDisposable.dispose();
       invoke("pty_kill", { sessionId }).catch(() => {});
@@ -187,20 +267,17 @@ export function TerminalView({ sessionId, resumeSessionId, forkSession, cwd, isA
     }
   }, [isActive, sessionId]);
 
-  // When tab goes to background, periodically trigger a redraw by toggling the
-  // PTY height by +1 row and immediately restoring it.
+  // When tab goes to background, periodically trigger a redraw: the backend
+  // grows the PTY by one row and restores it (see `pty_nudge`).
   // Rationale: Windows ConPTY (ResizePseudoConsole) only fires a
   // WINDOW_BUFFER_SIZE_EVENT into the child process's input queue when the size
   // *actually changes*. Sending the same dimensions repeatedly is a silent no-op,
   // so enquirer never redraws. Toggling forces two real resize events per cycle.
+  // The nudge bypasses the screen model and recorder, so neither sees it.
   useEffect(() => {
     if (isActive) return;
     const ping = () => {
-      const { rows, cols } = lastDimsRef.current;
-      invoke("pty_resize", { sessionId, rows: rows + 1, cols }).catch(() => {});
-      setTimeout(() => {
-        invoke("pty_resize", { sessionId, rows, cols }).catch(() => {});
-      }, 100);
+      invoke("pty_nudge", { sessionId }).catch(() => {});
     };
     ping();
     const id = setInterval(ping, 5000);
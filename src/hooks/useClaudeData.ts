@@ -1,5 +1,5 @@
 import { useEffect, useRef } from "react";
-import { useQuery, useQueryClient } from "@tanstack/react-query";
+import { useInfiniteQuery, useQuery, useQueryClient } from "@tanstack/react-query";
 import { listen } from "@tauri-apps/api/event";
 import { getCurrentWindow } from "@tauri-apps/api/window";
 import { isPermissionGranted, requestPermission, sendNotification } from "@tauri-apps/plugin-notification";
@@ -175,6 +175,15 @@ export function useGitDiff(cwd: string, path: string, staged: boolean) {
   });
 }
 
+export function useGitBlame(cwd: string, path: string) {
+  return useQuery({
+    queryKey: ["git-blame", cwd, path],
+    queryFn: () => tauri.gitBlame(cwd, path),
+    enabled: !!cwd && !!path,
+    staleTime: 10_000,
+  });
+}
+
 export function useGitBranches(cwd: string) {
   return useQuery({
     queryKey: ["git-branches", cwd],
@@ -193,15 +202,55 @@ export function useGitCurrentBranch(cwd: string) {
   });
 }
 
-export function useGitLog(cwd: string, limit = 100) {
+/** Paged history; `fetchNextPage` loads the next `limit` commits. */
+export function useGitLog(cwd: string, filters: Omit<tauri.LogQuery, "cursor"> = {}) {
+  return useInfiniteQuery({
+    queryKey: ["git-log", cwd, filters],
+    queryFn: ({ pageParam }) => tauri.gitLog(cwd, { ...filters, cursor: pageParam }),
+    initialPageParam: null as string | null,
+    getNextPageParam: (page) => page.nextCursor,
+    enabled: !!cwd,
+    staleTime: 10_000,
+  });
+}
+
+export function useGitCommitDetail(cwd: string, hash: string | null) {
+  return useQuery({
+    queryKey: ["git-commit-detail", cwd, hash],
+    queryFn: () => tauri.gitCommitDetail(cwd, hash!),
+    enabled: !!cwd && !!hash,
+    // A commit never changes; only its refs might
+    staleTime: 60_000,
+  });
+}
+
+export function useGitConflicts(cwd: string, enabled: boolean) {
+  return useQuery({
+    queryKey: ["git-conflicts", cwd],
+    queryFn: () => tauri.gitConflicts(cwd),
+    enabled: !!cwd && enabled,
+    staleTime: 5_000,
+  });
+}
+
+export function useGitStashes(cwd: string) {
   return useQuery({
-    queryKey: ["git-log", cwd, limit],
-    queryFn: () => tauri.gitLog(cwd, limit),
+    queryKey: ["git-stashes", cwd],
+    queryFn: () => tauri.gitStashList(cwd),
     enabled: !!cwd,
     staleTime: 10_000,
   });
 }
 
+export function useGitStashDiff(cwd: string, hash: string | null) {
+  return useQuery({
+    queryKey: ["git-stash-diff", cwd, hash],
+    queryFn: () => tauri.gitStashShow(cwd, hash!),
+    enabled: !!cwd && !!hash,
+    staleTime: 60_000,
+  });
+}
+
 export function useGitRemoteBranches(cwd: string) {
   return useQuery({
     queryKey: ["git-remote-branches", cwd],
@@ -211,6 +260,15 @@ export function useGitRemoteBranches(cwd: string) {
   });
 }
 
+export function useRecordings(projectPath: string | null) {
+  return useQuery({
+    queryKey: ["recordings", projectPath],
+    queryFn: () => tauri.listRecordings(projectPath!),
+    enabled: !!projectPath,
+    staleTime: 10_000,
+  });
+}
+
 export function useWorktrees(projectPath: string) {
   return useQuery({
     queryKey: ["worktrees", projectPath],
@@ -678,6 +736,13 @@ export function useClaudeWatcher() {
       })
     );
 
+    unlisteners.push(
+      listen("claude-home-changed", () => {
+        // Everything read from the Claude home is stale after a switch
+        useProjectsStore.getState().loadProjects();
+        queryClient.invalidateQueries();
+      })
+    );
     unlisteners.push(
       listen("inbox-changed", () => {
         queryClient.invalidateQueries({ queryKey: ["inbox"] });
@@ -776,6 +841,53 @@ export function useClaudeWatcher() {
       })
     );
 
+    // Trigger rule "notify" action — same surfaces as a Claude question
+    unlisteners.push(
+      listen<{ tab_id: string; rule_id: string; title: string; text: string }>("terminal-alert", async ({ payload }) => {
+        const { tabsByProject } = useSessionStore.getState();
+        for (const [projectId, tabs] of Object.entries(tabsByProject)) {
+          const tab = tabs.find((t) => t.id === payload.tab_id);
+          if (tab) {
+            useNotificationStore.getState().addNotification({
+              tabId: payload.tab_id,
+              projectId,
+              sessionTitle: tab.title ?? "Terminal",
+              question: `${payload.title}: ${payload.text}`,
+            });
+            const { nativeNotificationsEnabled } = useSettingsStore.getState();
+            const body = `${tab.title ?? "Terminal"}: ${payload.text.slice(0, 100)}${payload.text.length > 100 ? "…" : ""}`;
+            await maybeSendNativeNotification(payload.title, body, nativeNotificationsEnabled);
+            break;
+          }
+        }
+      })
+    );
+
+    // Trigger rule "link file" action — open the file in the terminal's project
+    unlisteners.push(
+      listen<{ tab_id: string; rule_id: string; text: string }>("file-linked", ({ payload }) => {
+        const s = useSessionStore.getState();
+        for (const [projectId, tabs] of Object.entries(s.tabsByProject)) {
+          const tab = tabs.find((t) => t.id === payload.tab_id);
+          if (!tab) continue;
+          const path = payload.text.trim();
+          const isAbsolute = /^([a-zA-Z]:[/\\]|[/\\])/.test(path);
+          const absPath = isAbsolute ? path : `${tab.projectDir}/${path.replace(/^\.[/\\]/, "")}`;
+          s.openTab(
+            {
+              id: `file:${absPath}`,
+              type: "file",
+              title: absPath.split(/[/\\]/).pop() ?? absPath,
+              filePath: absPath,
+              projectDir: tab.projectDir,
+            },
+            projectId
+          );
+          break;
+        }
+      })
+    );
+
     // Git branch changed — invalidate all git-related queries
     unlisteners.push(
       listen<{ cwd: string; branch: string }>("git-branch-changed", ({ payload }) => {
@@ -783,11 +895,34 @@ export function useClaudeWatcher() {
         queryClient.invalidateQueries({ queryKey: ["git-current-branch"] });
         queryClient.invalidateQueries({ queryKey: ["git-branches"] });
         queryClient.invalidateQueries({ queryKey: ["git-status"] });
+        queryClient.invalidateQueries({ queryKey: ["git-conflicts"] });
         queryClient.invalidateQueries({ queryKey: ["git-log"] });
         queryClient.invalidateQueries({ queryKey: ["git-remote-branches"] });
       })
     );
 
+    // Working tree or index changed (edits, staging, a merge stopping)
+    unlisteners.push(
+      listen<{ cwd: string }>("git-status-changed", ({ payload }) => {
+        queryClient.invalidateQueries({ queryKey: ["git-status", payload.cwd] });
+        queryClient.invalidateQueries({ queryKey: ["git-diff", payload.cwd] });
+        queryClient.invalidateQueries({ queryKey: ["git-conflicts", payload.cwd] });
+      })
+    );
+
+    // Branches, tags, remotes or stashes moved (commit, fetch, reset, ...)
+    unlisteners.push(
+      listen<{ cwd: string }>("git-refs-changed", ({ payload }) => {
+        queryClient.invalidateQueries({ queryKey: ["git-status", payload.cwd] });
+        queryClient.invalidateQueries({ queryKey: ["git-current-branch", payload.cwd] });
+        queryClient.invalidateQueries({ queryKey: ["git-branches", payload.cwd] });
+        queryClient.invalidateQueries({ queryKey: ["git-remote-branches", payload.cwd] });
+        queryClient.invalidateQueries({ queryKey: ["git-log", payload.cwd] });
+        queryClient.invalidateQueries({ queryKey: ["git-stashes", payload.cwd] });
+        queryClient.invalidateQueries({ queryKey: ["worktrees"] });
+      })
+    );
+
     // Session completion summary saved
     unlisteners.push(
       listen<{ session_id: string; project_path: string; project_dir: string; filename: string; preview: string }>(
@@ -835,21 +970,48 @@ export function useClaudeWatcher() {
 }
 
 /**
- * Watches the `.git/HEAD` file for the active project directory.
- * When the project changes, a new file watcher is started on the backend.
- * Branch changes are detected automatically and all git queries are invalidated
- * via the `git-branch-changed` event handled in `useClaudeWatcher`.
+ * Keeps a backend repository watcher running for the active project and every
+ * project with open tabs, so background tabs stay fresh too. Watchers for
+ * projects that drop out of that set are stopped. The resulting
+ * `git-status-changed`, `git-refs-changed` and `git-branch-changed` events are
+ * handled in `useClaudeWatcher`.
  */
-export function useGitBranchWatcher() {
+export function useGitRepoWatcher() {
   const activeProjectId = useProjectsStore((s) => s.activeProjectId);
   const projects = useProjectsStore((s) => s.projects);
+  const tabsByProject = useSessionStore((s) => s.tabsByProject);
+  const watchedRef = useRef<Set<string>>(new Set());
+
+  // A string so the effect only reruns when the set of paths changes
+  const paths = projects
+    .filter((p) => p.path && (p.id === activeProjectId || (tabsByProject[p.id]?.length ?? 0) > 0))
+    .map((p) => p.path)
+    .sort()
+    .join("\n");
 
   useEffect(() => {
-    const project = projects.find((p) => p.id === activeProjectId);
-    if (!project?.path) return;
+    const wanted = new Set(paths ? paths.split("\n") : []);
+    const watched = watchedRef.current;
+    for (const path of wanted) {
+      if (watched.has(path)) continue;
+      watched.add(path);
+      tauri.watchGit(path).catch((err) => {
+        watched.delete(path);
+        debugLog("Hooks", "watchGit failed", { path, error: String(err) }, "warn");
+      });
+    }
+    for (const path of [...watched]) {
+      if (wanted.has(path)) continue;
+      watched.delete(path);
+      tauri.unwatchGit(path).catch(() => {});
+    }
+  }, [paths]);
 
-    tauri.watchGitHead(project.path).catch((err) => {
-      debugLog("Hooks", "watchGitHead failed", { error: String(err) }, "warn");
-    });
-  }, [activeProjectId, projects]);
+  useEffect(() => {
+    const watched = watchedRef.current;
+    return () => {
+      watched.forEach((path) => tauri.unwatchGit(path).catch(() => {}));
+      watched.clear();
+    };
+  }, []);
 }
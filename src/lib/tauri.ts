@@ -1,4 +1,4 @@
-import { invoke } from "@tauri-apps/api/core";
+import { invoke, type Channel } from "@tauri-apps/api/core";
 
 // ---- Session Types ----
 
@@ -183,16 +183,47 @@ export interface WorktreeInfo {
   branch: string;     // short branch name, e.g. "feature/auth"
   isMain: boolean;
   isPrunable: boolean;
+  isLocked: boolean;
+  lockReason?: string;
+  /** Branch is fully merged into the main worktree's branch */
+  isMerged: boolean;
+}
+
+/** What a new worktree checks out; defaults to a new branch from HEAD */
+export type WorktreeSource =
+  | { type: "newBranch" }
+  | { type: "local" }
+  /** e.g. "origin/feature" */
+  | { type: "remote"; branch: string }
+  | { type: "pullRequest"; number: number; remote?: string };
+
+export interface RemoveWorktreeOptions {
+  /** Remove even with uncommitted changes */
+  force?: boolean;
+  /** Also delete the worktree's Claude session data */
+  cleanClaudeData?: boolean;
 }
 
 // ---- Git Types ----
 
-export type GitFileSection = "Staged" | "Unstaged" | "Untracked";
+export type GitFileSection = "Staged" | "Unstaged" | "Untracked" | "Conflicted";
+
+export type ConflictKind =
+  | "bothModified"
+  | "bothAdded"
+  | "bothDeleted"
+  | "addedByUs"
+  | "addedByThem"
+  | "deletedByUs"
+  | "deletedByThem";
 
 export interface GitFileEntry {
   path: string;
   section: GitFileSection;
   statusChar: string;
+  /** Previous path of a rename */
+  oldPath?: string;
+  conflict?: ConflictKind;
 }
 
 export type DiffLineKind = "Header" | "Add" | "Remove" | "Hunk" | "Context";
@@ -200,14 +231,81 @@ export type DiffLineKind = "Header" | "Add" | "Remove" | "Hunk" | "Context";
 export interface DiffLine {
   kind: DiffLineKind;
   text: string;
+  /** Index of the hunk the line belongs to, its "@@" line included */
+  hunk?: number;
+  oldLine?: number;
+  newLine?: number;
+}
+
+/** An added (newLine) or removed (oldLine) line of a diff */
+export interface DiffLineId {
+  oldLine: number | null;
+  newLine: number | null;
+}
+
+export interface DiffSelection {
+  hunks: number[];
+  lines: DiffLineId[];
+}
+
+export interface SubmoduleStatus {
+  name: string;
+  path: string;
+  headId: string | null;
+  workdirId: string | null;
+  initialized: boolean;
+  commitChanged: boolean;
+  dirty: boolean;
 }
 
 export interface GitStatus {
   staged: GitFileEntry[];
   unstaged: GitFileEntry[];
   untracked: GitFileEntry[];
+  conflicted: GitFileEntry[];
+  /** Short branch name, or "HEAD" when detached */
+  branch: string;
+  upstream: string | null;
+  ahead: number;
+  behind: number;
+  submodules: SubmoduleStatus[];
+  /** Merge, rebase, cherry-pick or revert stopped partway */
+  operation: GitOperation | null;
 }
 
+export type GitOperation = "merge" | "rebase" | "cherryPick" | "revert";
+
+export interface ConflictFile {
+  path: string;
+  kind: ConflictKind;
+  /** Contents are null for binary files */
+  binary: boolean;
+  base: string | null;
+  /** HEAD's version; during a rebase, the branch being rebased onto */
+  ours: string | null;
+  /** The incoming version; during a rebase, the commit being replayed */
+  theirs: string | null;
+  /** The working-tree file, usually with conflict markers */
+  working: string | null;
+}
+
+export interface ConflictState {
+  operation: GitOperation | null;
+  incomingHash: string | null;
+  incomingSummary: string | null;
+  rebaseBranch: string | null;
+  rebaseOnto: string | null;
+  /** 1-based rebase step */
+  step: number | null;
+  totalSteps: number | null;
+  conflicts: ConflictFile[];
+}
+
+export type ConflictResolution =
+  | { type: "ours" }
+  | { type: "theirs" }
+  | { type: "content"; content: string };
+
 // ---- File System Types ----
 
 export interface FileEntry {
@@ -364,6 +462,28 @@ export async function gitDiff(
   return invoke("cmd_git_diff", { cwd, path, staged });
 }
 
+export interface BlameLine {
+  /** 1-based line number in the working-tree file */
+  line: number;
+  content: string;
+  /** null for lines changed since the last commit */
+  hash: string | null;
+  shortHash: string | null;
+  author: string;
+  email: string;
+  /** Unix seconds */
+  timestamp: number;
+  date: string;
+  summary: string;
+  /** From a commit with the studio's Co-Authored-By trailer */
+  agent: boolean;
+}
+
+/** Blame a file; `path` is repo-relative (as in git status) or absolute. */
+export async function gitBlame(cwd: string, path: string): Promise<BlameLine[]> {
+  return invoke("cmd_git_blame", { cwd, path });
+}
+
 export async function gitBranches(cwd: string): Promise<string[]> {
   return invoke("cmd_git_branches", { cwd });
 }
@@ -374,16 +494,87 @@ export async function gitCurrentBranch(cwd: string): Promise<string> {
 
 // ---- Git Log Types ----
 
+export interface LogQuery {
+  /** nextCursor of the previous page; omit to start from HEAD */
+  cursor?: string | null;
+  limit?: number;
+  /** Case-insensitive match on author name or email */
+  author?: string;
+  /** File or directory, repo-relative or absolute */
+  path?: string;
+  /** Case-insensitive match anywhere in the message */
+  message?: string;
+}
+
+/** A commit's place in the log graph; numbers are lane columns */
+export interface GraphRow {
+  lane: number;
+  /** Lines from the row above ending at the node */
+  up: number[];
+  /** Lines from the node down to its parents */
+  down: number[];
+  /** Lines passing straight through */
+  through: number[];
+  width: number;
+}
+
 export interface CommitInfo {
   hash: string;
+  shortHash: string;
+  parents: string[];
+  /** Subject line */
+  message: string;
+  author: string;
+  email: string;
+  timestamp: number;
+  date: string;
+  refs: string[];
+  /** Null when the log is filtered */
+  graph: GraphRow | null;
+}
+
+export interface LogPage {
+  commits: CommitInfo[];
+  nextCursor: string | null;
+}
+
+export async function gitLog(cwd: string, query: LogQuery = {}): Promise<LogPage> {
+  return invoke("cmd_git_log", { cwd, query });
+}
+
+export interface CommitFileChange {
+  path: string;
+  oldPath?: string;
+  /** A, M, D, R, C or T */
+  statusChar: string;
+  additions: number;
+  deletions: number;
+  binary: boolean;
+  diff: DiffLine[];
+}
+
+export interface CommitDetail {
+  hash: string;
+  shortHash: string;
+  parents: string[];
+  /** Full message, trailers included */
   message: string;
   author: string;
+  email: string;
+  timestamp: number;
   date: string;
+  committer: string;
+  committerEmail: string;
+  commitTimestamp: number;
   refs: string[];
+  /** Carries the studio's Co-Authored-By trailer */
+  agent: boolean;
+  /** Changes against the first parent */
+  files: CommitFileChange[];
 }
 
-export async function gitLog(cwd: string, limit = 100): Promise<CommitInfo[]> {
-  return invoke("cmd_git_log", { cwd, limit });
+export async function gitCommitDetail(cwd: string, hash: string): Promise<CommitDetail> {
+  return invoke("cmd_git_commit_detail", { cwd, hash });
 }
 
 // ---- Remote Branch Types ----
@@ -400,9 +591,27 @@ export async function gitRemoteBranches(cwd: string): Promise<RemoteBranch[]> {
 
 export async function createWorktree(
   projectPath: string,
-  branchName: string
+  branchName: string,
+  source?: WorktreeSource
 ): Promise<string> {
-  return invoke<string>("cmd_create_worktree", { projectPath, branchName });
+  return invoke<string>("cmd_create_worktree", { projectPath, branchName, source });
+}
+
+export function removeWorktree(projectPath: string, path: string, options: RemoveWorktreeOptions = {}): Promise<void> {
+  return invoke("cmd_remove_worktree", { projectPath, path, options });
+}
+
+/** Drop entries of worktrees whose directories are gone; returns their paths */
+export function pruneWorktrees(projectPath: string): Promise<string[]> {
+  return invoke("cmd_prune_worktrees", { projectPath });
+}
+
+export function lockWorktree(projectPath: string, path: string, reason?: string): Promise<void> {
+  return invoke("cmd_lock_worktree", { projectPath, path, reason });
+}
+
+export function unlockWorktree(projectPath: string, path: string): Promise<void> {
+  return invoke("cmd_unlock_worktree", { projectPath, path });
 }
 
 export function listWorktrees(projectPath: string): Promise<WorktreeInfo[]> {
@@ -417,6 +626,30 @@ export function setWorktreeCopy(projectPath: string, entries: string[]): Promise
   return invoke("cmd_set_worktree_copy", { projectPath, entries });
 }
 
+export interface CommitOptions {
+  /** Empty keeps the message when amending */
+  message: string;
+  amend?: boolean;
+  signOff?: boolean;
+  author?: { name: string; email: string } | null;
+}
+
+export interface CreatedCommit {
+  hash: string;
+  shortHash: string;
+  summary: string;
+  signed: boolean;
+}
+
+export async function gitCommit(cwd: string, options: CommitOptions): Promise<CreatedCommit> {
+  return invoke("cmd_git_commit", { cwd, options });
+}
+
+/** Ask Claude for a message for the staged diff; nothing is committed. */
+export async function gitSuggestCommitMessage(cwd: string): Promise<string> {
+  return invoke("cmd_git_suggest_commit_message", { cwd });
+}
+
 export async function claudeGitAction(
   cwd: string,
   action: "commit" | "commit_push" | "commit_push_pr"
@@ -432,6 +665,90 @@ export async function gitPull(cwd: string): Promise<string> {
   return invoke("cmd_git_pull", { cwd });
 }
 
+export type PushRefStatus = "fastForward" | "forced" | "new" | "deleted" | "upToDate" | "rejected";
+
+export type PushRejection =
+  | "nonFastForward"
+  | "fetchFirst"
+  | "staleLease"
+  | "protectedBranch"
+  | "remoteRejected"
+  | "other";
+
+export interface PushRefUpdate {
+  localRef: string;
+  remoteRef: string;
+  status: PushRefStatus;
+  rejection?: PushRejection;
+  summary: string;
+}
+
+export interface PushResult {
+  remote: string;
+  branch: string;
+  upstreamSet: boolean;
+  refs: PushRefUpdate[];
+  rejected: boolean;
+  /** Non-progress stderr, including remote: messages */
+  output: string;
+}
+
+/** Payload of "git-push-progress" */
+export interface PushProgress {
+  cwd: string;
+  stage: string;
+  percent: number | null;
+  line: string;
+}
+
+export interface StashEntry {
+  /** stash@{index}; shifts as stashes are added and dropped */
+  index: number;
+  /** Stable id the other stash commands take */
+  hash: string;
+  message: string;
+  branch: string | null;
+  /** Unix seconds */
+  timestamp: number;
+  date: string;
+  includesUntracked: boolean;
+}
+
+export interface StashOptions {
+  message?: string;
+  includeUntracked?: boolean;
+  keepIndex?: boolean;
+}
+
+export async function gitStashList(cwd: string): Promise<StashEntry[]> {
+  return invoke("cmd_git_stash_list", { cwd });
+}
+
+export async function gitStashSave(cwd: string, options: StashOptions): Promise<StashEntry> {
+  return invoke("cmd_git_stash_save", { cwd, options });
+}
+
+export async function gitStashShow(cwd: string, hash: string): Promise<DiffLine[]> {
+  return invoke("cmd_git_stash_show", { cwd, hash });
+}
+
+export async function gitStashApply(cwd: string, hash: string): Promise<void> {
+  return invoke("cmd_git_stash_apply", { cwd, hash });
+}
+
+export async function gitStashPop(cwd: string, hash: string): Promise<void> {
+  return invoke("cmd_git_stash_pop", { cwd, hash });
+}
+
+export async function gitStashDrop(cwd: string, hash: string): Promise<void> {
+  return invoke("cmd_git_stash_drop", { cwd, hash });
+}
+
+/** Push the current branch; rejections come back in the result, not as errors. */
+export async function gitPush(cwd: string, options: { forceWithLease?: boolean } = {}): Promise<PushResult> {
+  return invoke("cmd_git_push", { cwd, options });
+}
+
 export async function gitCreateBranch(
   cwd: string,
   branchName: string,
@@ -444,10 +761,60 @@ export async function gitRebase(cwd: string, ontoBranch: string): Promise<string
   return invoke("cmd_git_rebase", { cwd, ontoBranch });
 }
 
+export async function gitConflicts(cwd: string): Promise<ConflictState> {
+  return invoke("cmd_git_conflicts", { cwd });
+}
+
+export async function gitResolveConflict(cwd: string, path: string, resolution: ConflictResolution): Promise<void> {
+  return invoke("cmd_git_resolve_conflict", { cwd, path, resolution });
+}
+
+/** Stage files as edited in the working tree; refused while markers remain. */
+export async function gitMarkResolved(cwd: string, paths: string[]): Promise<void> {
+  return invoke("cmd_git_mark_resolved", { cwd, paths });
+}
+
+export async function gitOperationContinue(cwd: string): Promise<ConflictState> {
+  return invoke("cmd_git_operation_continue", { cwd });
+}
+
+export async function gitOperationSkip(cwd: string): Promise<ConflictState> {
+  return invoke("cmd_git_operation_skip", { cwd });
+}
+
+export async function gitOperationAbort(cwd: string): Promise<ConflictState> {
+  return invoke("cmd_git_operation_abort", { cwd });
+}
+
+/** Agent-proposed merged content for one conflicted file; nothing is written. */
+export async function gitSuggestResolution(cwd: string, path: string): Promise<string> {
+  return invoke("cmd_git_suggest_resolution", { cwd, path });
+}
+
 export async function gitAdd(cwd: string, path: string): Promise<string> {
   return invoke("cmd_git_add", { cwd, path });
 }
 
+export async function gitStageLines(cwd: string, path: string, selection: DiffSelection): Promise<void> {
+  return invoke("cmd_git_stage_lines", { cwd, path, selection });
+}
+
+export async function gitUnstageLines(cwd: string, path: string, selection: DiffSelection): Promise<void> {
+  return invoke("cmd_git_unstage_lines", { cwd, path, selection });
+}
+
+export async function gitDiscardLines(cwd: string, path: string, selection: DiffSelection): Promise<void> {
+  return invoke("cmd_git_discard_lines", { cwd, path, selection });
+}
+
+export async function gitUnstage(cwd: string, path: string): Promise<void> {
+  return invoke("cmd_git_unstage", { cwd, path });
+}
+
+export async function gitDiscard(cwd: string, path: string): Promise<void> {
+  return invoke("cmd_git_discard", { cwd, path });
+}
+
 export async function gitIgnore(cwd: string, filePath: string): Promise<string> {
   return invoke("cmd_git_ignore", { cwd, filePath });
 }
@@ -456,8 +823,13 @@ export async function gitExclude(cwd: string, filePath: string): Promise<string>
   return invoke("cmd_git_exclude", { cwd, filePath });
 }
 
-export async function watchGitHead(cwd: string): Promise<void> {
-  return invoke("cmd_watch_git_head", { cwd });
+/** Watch a repository's HEAD, index, refs and working tree; idempotent per `cwd`. */
+export async function watchGit(cwd: string): Promise<void> {
+  return invoke("cmd_watch_git", { cwd });
+}
+
+export async function unwatchGit(cwd: string): Promise<void> {
+  return invoke("cmd_unwatch_git", { cwd });
 }
 
 // ─── PR / Issues Types ────────────────────────────────────────────────────────
@@ -675,22 +1047,449 @@ export interface HookEvent {
   agent_type?: string;
   last_assistant_message?: string;
   stop_hook_active?: boolean;
+  /** Unix millis when hook.js logged the event; absent from older logs */
+  timestamp?: number;
 }
 
-export function setupHooks(): Promise<void> {
-  return invoke("cmd_setup_hooks");
+export type HookScope = "user" | "project";
+
+export interface HookStatus {
+  configured: boolean;
+  effectiveScope?: HookScope;
+  preferredScope: HookScope;
+  userInstalled: boolean;
+  projectInstalled: boolean;
 }
 
-export function removeHooks(): Promise<void> {
-  return invoke("cmd_remove_hooks");
+export function setupHooks(projectPath?: string): Promise<void> {
+  return invoke("cmd_setup_hooks", { projectPath });
+}
+
+export function removeHooks(projectPath?: string): Promise<void> {
+  return invoke("cmd_remove_hooks", { projectPath });
+}
+
+export function setHookScope(projectPath: string, scope: HookScope): Promise<void> {
+  return invoke("cmd_set_hook_scope", { projectPath, scope });
+}
+
+export interface SettingsPreview {
+  path: string;
+  changed: boolean;
+  diff: DiffLine[];
+}
+
+export function previewHooks(remove = false, projectPath?: string): Promise<SettingsPreview> {
+  return invoke("cmd_preview_hooks", { remove, projectPath });
 }
 
 export function getActiveSessions(): Promise<ActiveSession[]> {
   return invoke("cmd_get_active_sessions");
 }
 
-export function hooksConfigured(): Promise<boolean> {
-  return invoke("cmd_hooks_configured");
+export function hooksConfigured(projectPath?: string): Promise<HookStatus> {
+  return invoke("cmd_hooks_configured", { projectPath });
+}
+
+// ---- PTY ----
+
+export interface ScrollbackMatch {
+  line: number;
+  column: number;
+  text: string;
+}
+
+/**
+ * Subscribes `output` to a live session's raw output batches and returns the
+ * scrollback so far, or null if no PTY with this id is running. Confirm each
+ * batch with `ptyAck` once it has been written to the terminal.
+ */
+/** Scrollback returned by `pty_attach` and the stream offset it ends at. */
+export interface PtyOutputSnapshot {
+  data: string;
+  offset: number;
+}
+
+/**
+ * Subscribe `output` to a session's live output. Each message starts with the
+ * little-endian u64 stream offset of its first byte.
+ */
+export function ptyAttach(sessionId: string, output: Channel<ArrayBuffer>): Promise<PtyOutputSnapshot | null> {
+  return invoke("pty_attach", { sessionId, output });
+}
+
+export function ptyAck(sessionId: string, bytes: number): Promise<void> {
+  return invoke("pty_ack", { sessionId, bytes });
+}
+
+export function ptySearch(
+  sessionId: string,
+  query: string,
+  caseSensitive = false,
+  limit?: number,
+): Promise<ScrollbackMatch[]> {
+  return invoke("pty_search", { sessionId, query, caseSensitive, limit });
+}
+
+export interface ProcessStats {
+  pid: number;
+  name: string;
+  cpuPercent: number;
+  memoryBytes: number;
+}
+
+/** One `pty-stats` sample of a session's process tree. `cpuPercent` is summed; 100 = one core. */
+export interface PtyStats {
+  sessionId: string;
+  pid: number;
+  cpuPercent: number;
+  memoryBytes: number;
+  processCount: number;
+  top: ProcessStats[];
+}
+
+export type LimitAction = "warn" | "killChildren" | "killTree";
+
+export interface ResourceLimits {
+  enabled: boolean;
+  maxCpuPercent: number | null;
+  maxMemoryMb: number | null;
+  maxProcesses: number | null;
+  sustainSecs: number;
+  action: LimitAction;
+  sampleSecs: number;
+}
+
+export function getResourceLimits(): Promise<ResourceLimits> {
+  return invoke("cmd_get_resource_limits");
+}
+
+export function saveResourceLimits(limits: ResourceLimits): Promise<void> {
+  return invoke("cmd_save_resource_limits", { limits });
+}
+
+export interface ScreenPrompt {
+  question: string | null;
+  options: string[];
+  selected: number | null;
+}
+
+/** Rendered terminal screen from the backend's headless emulator. */
+export interface ScreenSnapshot {
+  rows: number;
+  cols: number;
+  lines: string[];
+  cursor: { row: number; col: number; visible: boolean };
+  alternateScreen: boolean;
+  title: string | null;
+  prompt: ScreenPrompt | null;
+  ansi?: string;
+}
+
+export function ptySnapshot(sessionId: string, ansi = false): Promise<ScreenSnapshot> {
+  return invoke("pty_snapshot", { sessionId, ansi });
+}
+
+export type PtySessionKind = "claude" | "shell";
+
+export interface ResumeAttempt {
+  claudeSessionId: string;
+  attempt: number;
+  maxRetries: number;
+  delayMs: number;
+}
+
+/** Payload of `pty-exit-{id}`. `tail` is the last lines of ANSI-stripped output. */
+export interface PtyExit {
+  reason: "clean" | "crashed" | "killed";
+  code: number | null;
+  description: string;
+  tail: string[];
+  resume: ResumeAttempt | null;
+}
+
+export interface PtySessionInfo {
+  sessionId: string;
+  kind: PtySessionKind;
+  cwd: string;
+  claudeSessionId: string | null;
+  /** Set once the process has ended */
+  exit: PtyExit | null;
+}
+
+export function ptyList(): Promise<PtySessionInfo[]> {
+  return invoke("pty_list");
+}
+
+/** Relaunch a crashed Claude session with `--resume`, within a retry budget. */
+export interface AutoResumePolicy {
+  enabled: boolean;
+  maxRetries: number;
+  windowSecs: number;
+  delayMs: number;
+}
+
+export function getAutoResumePolicy(): Promise<AutoResumePolicy> {
+  return invoke("cmd_get_auto_resume_policy");
+}
+
+export function saveAutoResumePolicy(policy: AutoResumePolicy): Promise<void> {
+  return invoke("cmd_save_auto_resume_policy", { policy });
+}
+
+export function ptyBindSession(sessionId: string, claudeSessionId: string): Promise<void> {
+  return invoke("pty_bind_session", { sessionId, claudeSessionId });
+}
+
+// ---- Prompt queue ----
+
+export type PromptCondition =
+  | { type: "lastMessageContains"; text: string; caseSensitive?: boolean }
+  | { type: "lastMessageNotContains"; text: string; caseSensitive?: boolean };
+
+export interface QueuedPrompt {
+  id: string;
+  text: string;
+  condition?: PromptCondition | null;
+}
+
+export interface PromptHistoryEntry {
+  id: string;
+  text: string;
+  outcome: "sent" | "skipped" | "failed";
+  /** Why delivery failed, for "failed" */
+  error: string | null;
+  at: number;
+}
+
+export interface PromptQueue {
+  items: QueuedPrompt[];
+  paused: boolean;
+  history: PromptHistoryEntry[];
+  lastMessage?: string | null;
+}
+
+export function getPromptQueue(tabId: string): Promise<PromptQueue> {
+  return invoke("cmd_get_prompt_queue", { tabId });
+}
+
+export function enqueuePrompt(tabId: string, text: string, condition?: PromptCondition): Promise<QueuedPrompt> {
+  return invoke("cmd_enqueue_prompt", { tabId, text, condition: condition ?? null });
+}
+
+export function updateQueuedPrompt(tabId: string, prompt: QueuedPrompt): Promise<void> {
+  return invoke("cmd_update_queued_prompt", { tabId, prompt });
+}
+
+export function removeQueuedPrompt(tabId: string, promptId: string): Promise<void> {
+  return invoke("cmd_remove_queued_prompt", { tabId, promptId });
+}
+
+export function reorderPromptQueue(tabId: string, promptIds: string[]): Promise<void> {
+  return invoke("cmd_reorder_prompt_queue", { tabId, promptIds });
+}
+
+export function setPromptQueuePaused(tabId: string, paused: boolean): Promise<void> {
+  return invoke("cmd_set_prompt_queue_paused", { tabId, paused });
+}
+
+/** Sends the next eligible prompt immediately, even when paused. */
+export function sendNextPrompt(tabId: string): Promise<QueuedPrompt | null> {
+  return invoke("cmd_send_next_prompt", { tabId });
+}
+
+// ---- Prompt broadcast ----
+
+export type BroadcastTargetState =
+  | { state: "running" }
+  | { state: "question"; question: string }
+  | { state: "finished"; lastMessage?: string | null }
+  | { state: "exited" }
+  | { state: "failed"; error: string };
+
+export type BroadcastTarget = BroadcastTargetState & {
+  tabId: string;
+  updatedAt: number;
+  /** When the prompt was typed in; null if it wasn't sent */
+  deliveredAt: number | null;
+};
+
+export interface BroadcastSummary {
+  running: number;
+  question: number;
+  finished: number;
+  exited: number;
+  failed: number;
+  /** True once no target is running or asking a question */
+  done: boolean;
+}
+
+export interface Broadcast {
+  id: string;
+  prompt: string;
+  startedAt: number;
+  targets: BroadcastTarget[];
+  summary: BroadcastSummary;
+}
+
+/** Types one prompt into several Claude tabs; progress arrives as `broadcast-changed` events. */
+export function broadcastPrompt(tabIds: string[], text: string): Promise<Broadcast> {
+  return invoke("cmd_broadcast_prompt", { tabIds, text });
+}
+
+export function getBroadcast(broadcastId: string): Promise<Broadcast> {
+  return invoke("cmd_get_broadcast", { broadcastId });
+}
+
+export function listBroadcasts(): Promise<Broadcast[]> {
+  return invoke("cmd_list_broadcasts");
+}
+
+export function dismissBroadcast(broadcastId: string): Promise<void> {
+  return invoke("cmd_dismiss_broadcast", { broadcastId });
+}
+
+// ---- Terminal trigger rules ----
+
+export type TriggerAction =
+  | { type: "emit"; event: string; payload: Record<string, string> }
+  | { type: "notify"; title: string; body: string }
+  | { type: "linkFile"; path: string }
+  | { type: "reply"; text: string };
+
+/** Regex rule over ANSI-stripped output. Action text uses `$0`, `$1`, `${name}` templates. */
+export interface TriggerRule {
+  id: string;
+  name: string;
+  pattern: string;
+  exclude?: string | null;
+  caseInsensitive: boolean;
+  wholeChunk: boolean;
+  /** "screen" rules match the rendered screen and fire when the match appears */
+  source?: "output" | "screen";
+  kinds: PtySessionKind[];
+  profiles: string[];
+  actions: TriggerAction[];
+  /** null = once per session for each distinct match */
+  dedupeSecs: number | null;
+  stop: boolean;
+  enabled: boolean;
+  builtin: boolean;
+}
+
+export function listTriggerRules(): Promise<TriggerRule[]> {
+  return invoke("cmd_list_trigger_rules");
+}
+
+/** Saves the full list; applies to terminals started afterwards. */
+export function saveTriggerRules(rules: TriggerRule[]): Promise<void> {
+  return invoke("cmd_save_trigger_rules", { rules });
+}
+
+// ---- Claude launch profiles ----
+
+export type LaunchProfileScope = "global" | "project";
+
+export interface LaunchProfile {
+  id: string;
+  name: string;
+  model?: string | null;
+  permissionMode?: string | null;
+  addDirs: string[];
+  mcpConfig?: string | null;
+  appendSystemPrompt?: string | null;
+  allowedTools: string[];
+  disallowedTools: string[];
+  env: Record<string, string>;
+  scope: LaunchProfileScope;
+}
+
+/** Global profiles, merged with the project's (project wins on id clashes). */
+export function listLaunchProfiles(projectPath?: string): Promise<LaunchProfile[]> {
+  return invoke("cmd_list_launch_profiles", { projectPath: projectPath ?? null });
+}
+
+/** Replaces every profile stored at `scope`. */
+export function saveLaunchProfiles(
+  scope: LaunchProfileScope,
+  profiles: LaunchProfile[],
+  projectPath?: string,
+): Promise<void> {
+  return invoke("cmd_save_launch_profiles", { scope, projectPath: projectPath ?? null, profiles });
+}
+
+// ---- Terminal profiles ----
+
+export interface TerminalProfile {
+  id: string;
+  name: string;
+  command: string;
+  args: string[];
+  env: Record<string, string>;
+  cwd?: string | null;
+  builtin: boolean;
+}
+
+export function listTerminalProfiles(): Promise<TerminalProfile[]> {
+  return invoke("cmd_list_terminal_profiles");
+}
+
+/** Saves the non-builtin profiles; built-ins are re-detected on every load. */
+export function saveTerminalProfiles(profiles: TerminalProfile[]): Promise<void> {
+  return invoke("cmd_save_terminal_profiles", { profiles });
+}
+
+// ---- Recordings (asciicast v2) ----
+
+export interface RecordingInfo {
+  id: string;
+  path: string;
+  title?: string;
+  startedAt?: number;
+  durationSecs: number;
+  width: number;
+  height: number;
+  sizeBytes: number;
+}
+
+export function ptyStartRecording(sessionId: string): Promise<string> {
+  return invoke("pty_start_recording", { sessionId });
+}
+
+export function ptyStopRecording(sessionId: string): Promise<string | null> {
+  return invoke("pty_stop_recording", { sessionId });
+}
+
+export function listRecordings(projectPath: string): Promise<RecordingInfo[]> {
+  return invoke("cmd_list_recordings", { projectPath });
+}
+
+/**
+ * Replays output into `output` as raw bytes, with `playback-resize-{playbackId}`
+ * ({ cols, rows }) and a final `playback-exit-{playbackId}` event.
+ */
+export function playRecording(
+  projectPath: string,
+  recordingId: string,
+  playbackId: string,
+  output: Channel<ArrayBuffer>,
+  speed?: number,
+  idleLimit?: number,
+): Promise<void> {
+  return invoke("cmd_play_recording", { projectPath, recordingId, playbackId, output, speed, idleLimit });
+}
+
+export function stopPlayback(playbackId: string): Promise<void> {
+  return invoke("cmd_stop_playback", { playbackId });
+}
+
+export function exportRecording(
+  projectPath: string,
+  recordingId: string,
+  dest: string,
+  format: "cast" | "txt" = "cast",
+): Promise<void> {
+  return invoke("cmd_export_recording", { projectPath, recordingId, dest, format });
 }
 
 // ---- Project Types ----
@@ -726,6 +1525,37 @@ export function listOrphanedProjects(): Promise<Project[]> {
   return invoke("cmd_list_orphaned_projects");
 }
 
+// ---- Claude homes ----
+
+/** A registered Claude config root, e.g. a second account in `~/.claude-work`. */
+export interface ClaudeHome {
+  id: string;
+  name: string;
+  path: string;
+}
+
+export interface ClaudeHomeInfo extends ClaudeHome {
+  active: boolean;
+  /** False until Claude has been run with this root */
+  exists: boolean;
+  /** The default root (`CLAUDE_CONFIG_DIR` or `~/.claude`) */
+  builtin: boolean;
+}
+
+export function listClaudeHomes(): Promise<ClaudeHomeInfo[]> {
+  return invoke("cmd_list_claude_homes");
+}
+
+/** Replace the registered (non-default) homes. */
+export function saveClaudeHomes(homes: ClaudeHome[]): Promise<ClaudeHomeInfo[]> {
+  return invoke("cmd_save_claude_homes", { homes });
+}
+
+/** Switch the studio, its watchers and new Claude sessions to another home. */
+export function switchClaudeHome(id: string): Promise<ClaudeHomeInfo> {
+  return invoke("cmd_switch_claude_home", { id });
+}
+
 export function readFile(path: string): Promise<string> {
   return invoke("cmd_read_file", { path });
 }
@@ -766,6 +1596,7 @@ export interface ProjectSettings {
     activeProviders?: string[];
     prState?: "open" | "closed" | "all";
   };
+  hookScope?: HookScope;       // undefined = user-level
 }
 
 export function getProjectSettings(projectPath: string): Promise<ProjectSettings> {
@@ -6,12 +6,8 @@ export function cn(...inputs: ClassValue[]) {
 }
 
 /// Mirror of Rust encode_project_path: C:\dev\ide → C--dev-ide
-/// Replaces path separators, dots, and underscores with dashes to match Claude CLI behavior.
-/// Example: C:\dev\apex_3.11.0 → C--dev-apex-3-11-0
+/// Replaces every non-alphanumeric character with a dash to match Claude CLI behavior.
+/// Example: C:\dev\apex_3.11.0 → C--dev-apex-3-11-0, /home/me/app → -home-me-app
 export function pathToProjectId(cwd: string): string {
-  let s = cwd.replace(/\//g, "\\");
-  s = s.replace(/:\\/g, "--");
-  s = s.replace(/\\/g, "-");
-  s = s.replace(/[._]/g, "-");
-  return s.replace(/-+$/, "");
+  return cwd.replace(/[/\\]+$/, "").replace(/[^a-zA-Z0-9]/g, "-");
 }
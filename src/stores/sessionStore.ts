@@ -5,7 +5,7 @@ import { useNotificationStore } from "./notificationStore";
 
 export interface SessionTab {
   id: string;
-  type?: "terminal" | "plan" | "readme" | "settings" | "diff" | "session-view" | "file" | "summary" | "pr-detail" | "extension" | "issue-detail" | "workflow-run";
+  type?: "terminal" | "shell" | "plan" | "readme" | "settings" | "diff" | "session-view" | "file" | "summary" | "pr-detail" | "extension" | "issue-detail" | "workflow-run";
   projectDir: string;
   sessionId?: string;
   title: string;
@@ -29,6 +29,8 @@ export interface SessionTab {
   remoteRunConclusion?: "success" | "failure" | "cancelled" | null;
   workflowRunId?: number; // only for type === "workflow-run"
   workflowRunUrl?: string; // external URL for the workflow run
+  profileId?: string; // terminal profile for type === "shell"; default profile when unset
+  launchProfileId?: string; // Claude launch profile (CLI flags + env) for new sessions
 }
 
 interface SessionStore {
@@ -201,7 +203,7 @@ export const useSessionStore = create<SessionStore>((set) => ({
   },
 
   // Scan all projects to find and update the tab (tab IDs are globally unique)
-  resolveTabSession: (tabId, realSessionId) =>
+  resolveTabSession: (tabId, realSessionId) => {
     set((s) => {
       const updated: Record<string, SessionTab[]> = {};
       for (const [pid, tabs] of Object.entries(s.tabsByProject)) {
@@ -210,7 +212,10 @@ export const useSessionStore = create<SessionStore>((set) => ({
         );
       }
       return { tabsByProject: updated };
-    }),
+    });
+    // Let the backend route hook events (e.g. Stop for the prompt queue) to this PTY
+    tauri.ptyBindSession(tabId, realSessionId).catch(() => { /* not a PTY tab */ });
+  },
 
   renameTab: (tabId, title) =>
     set((s) => {